@@ -0,0 +1,363 @@
+use crate::api::NusmodsApi;
+use crate::history;
+use crate::models::{ActivityType, ModuleStatus, Plan, SpecialActivity};
+use crate::plan_ops::{self, OperationResult, PlanOperation};
+use crate::storage::Storage;
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, ArgMatches};
+
+/// A `plan` subcommand as parsed from the command line. Semesters are kept
+/// as written until the plan is loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRequest {
+    pub plan_id: String,
+    pub action: BatchAction,
+    /// Print the result as JSON instead of text.
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchAction {
+    Add {
+        semester: String,
+        module_codes: Vec<String>,
+    },
+    Remove {
+        semester: String,
+        module_codes: Vec<String>,
+    },
+    Move {
+        from: String,
+        to: String,
+        module_codes: Vec<String>,
+    },
+    SetStatus {
+        module_code: String,
+        semester: Option<String>,
+        status: ModuleStatus,
+    },
+    SetGrade {
+        module_code: String,
+        semester: Option<String>,
+        grade: Option<String>,
+    },
+    AddActivity {
+        semester: String,
+        activity: SpecialActivity,
+    },
+    /// Reverts the last `count` logged edits, including ones saved in
+    /// earlier sessions.
+    Undo { count: usize },
+}
+
+/// Applies one `plan` subcommand to a stored plan without prompting.
+pub struct BatchEditCommand {
+    pub storage: Box<dyn Storage>,
+    pub api: Box<dyn NusmodsApi>,
+    pub request: BatchRequest,
+}
+
+impl super::Command for BatchEditCommand {
+    fn run(&self) -> Result<()> {
+        let result = self.execute()?;
+
+        if self.request.json {
+            println!("{}", serde_json::to_string_pretty(&result.report())?);
+            return Ok(());
+        }
+
+        for change in &result.changes {
+            println!("✅ {}", change);
+        }
+        for description in &result.undone {
+            println!("↩️  Undid: {}", description);
+        }
+        for warning in &result.warnings {
+            println!("⚠️  {}", warning);
+        }
+
+        Ok(())
+    }
+}
+
+impl BatchEditCommand {
+    /// Loads the plan, applies the request and saves the plan. Nothing is
+    /// saved when the request fails.
+    pub fn execute(&self) -> Result<OperationResult> {
+        let mut plan = self
+            .storage
+            .get_plan(&self.request.plan_id)?
+            .ok_or_else(|| anyhow!("Plan not found: {}", self.request.plan_id))?;
+
+        let registry = self.api.get_module_registry()?;
+        plan.repair_unit_totals(&registry);
+
+        let result = match &self.request.action {
+            BatchAction::Undo { count } => {
+                let undone = history::undo_logged(&mut plan, &registry, *count)?;
+                if undone.is_empty() {
+                    return Err(anyhow!("Nothing to undo"));
+                }
+                OperationResult {
+                    undone: undone.into_iter().map(|edit| edit.description).collect(),
+                    ..OperationResult::default()
+                }
+            }
+            action => {
+                let operation = action.resolve(&plan)?;
+                let result = plan_ops::apply(&mut plan, &registry, &operation)?;
+                history::record(&mut plan, result.changes.clone());
+                result
+            }
+        };
+
+        plan.modified_at = chrono::Utc::now();
+        self.storage.save_plan(&plan)?;
+
+        Ok(result)
+    }
+}
+
+impl BatchAction {
+    /// Turns semester arguments into plan indices. `Undo` is not an
+    /// operation on the plan's contents and does not resolve.
+    pub fn resolve(&self, plan: &Plan) -> Result<PlanOperation> {
+        let semester = |spec: &str| plan_ops::resolve_semester(plan, spec);
+        let optional = |spec: &Option<String>| spec.as_deref().map(semester).transpose();
+
+        Ok(match self {
+            BatchAction::Add {
+                semester: spec,
+                module_codes,
+            } => PlanOperation::Add {
+                semester: semester(spec)?,
+                module_codes: module_codes.clone(),
+            },
+            BatchAction::Remove {
+                semester: spec,
+                module_codes,
+            } => PlanOperation::Remove {
+                semester: semester(spec)?,
+                module_codes: module_codes.clone(),
+            },
+            BatchAction::Move {
+                from,
+                to,
+                module_codes,
+            } => PlanOperation::Move {
+                from: semester(from)?,
+                to: semester(to)?,
+                module_codes: module_codes.clone(),
+            },
+            BatchAction::SetStatus {
+                module_code,
+                semester: spec,
+                status,
+            } => PlanOperation::SetStatus {
+                module_code: module_code.clone(),
+                semester: optional(spec)?,
+                status: status.clone(),
+            },
+            BatchAction::SetGrade {
+                module_code,
+                semester: spec,
+                grade,
+            } => PlanOperation::SetGrade {
+                module_code: module_code.clone(),
+                semester: optional(spec)?,
+                grade: grade.clone(),
+            },
+            BatchAction::AddActivity {
+                semester: spec,
+                activity,
+            } => PlanOperation::AddActivity {
+                semester: semester(spec)?,
+                activity: activity.clone(),
+            },
+            BatchAction::Undo { .. } => {
+                return Err(anyhow!("Undo does not resolve to a plan operation"))
+            }
+        })
+    }
+}
+
+/// The `plan` command and its subcommands.
+pub fn plan_cli() -> clap::Command {
+    let plan = || Arg::new("plan").required(true).help("Plan ID");
+    let semester = |name: &'static str| {
+        Arg::new(name)
+            .required(true)
+            .help("Semester number (1-based) or year:semester, e.g. 2024/2025:1")
+    };
+    let codes = || {
+        Arg::new("codes")
+            .required(true)
+            .num_args(1..)
+            .help("Module codes")
+    };
+    let semester_flag = || {
+        Arg::new("semester")
+            .long("semester")
+            .help("Semester the module is in (defaults to its latest occurrence)")
+    };
+
+    clap::Command::new("plan")
+        .about("Edit a plan without prompts")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Print the result as JSON"),
+        )
+        .subcommand(
+            clap::Command::new("add")
+                .about("Add modules to a semester")
+                .arg(plan())
+                .arg(semester("semester"))
+                .arg(codes()),
+        )
+        .subcommand(
+            clap::Command::new("remove")
+                .about("Remove modules from a semester")
+                .arg(plan())
+                .arg(semester("semester"))
+                .arg(codes()),
+        )
+        .subcommand(
+            clap::Command::new("move")
+                .about("Move modules between semesters")
+                .arg(plan())
+                .arg(semester("from"))
+                .arg(semester("to"))
+                .arg(codes()),
+        )
+        .subcommand(
+            clap::Command::new("set-status")
+                .about("Set a module's status")
+                .arg(plan())
+                .arg(Arg::new("code").required(true))
+                .arg(
+                    Arg::new("status")
+                        .required(true)
+                        .help("planned, current, completed or failed"),
+                )
+                .arg(semester_flag()),
+        )
+        .subcommand(
+            clap::Command::new("set-grade")
+                .about("Set or clear a module's grade")
+                .arg(plan())
+                .arg(Arg::new("code").required(true))
+                .arg(
+                    Arg::new("grade")
+                        .required(true)
+                        .help("Grade, or an empty string to clear it"),
+                )
+                .arg(semester_flag()),
+        )
+        .subcommand(
+            clap::Command::new("add-activity")
+                .about("Add a special activity to a semester")
+                .arg(plan())
+                .arg(semester("semester"))
+                .arg(
+                    Arg::new("type")
+                        .long("type")
+                        .required(true)
+                        .help("internship, exchange, research, community-service or a custom name"),
+                )
+                .arg(Arg::new("description").long("description").required(true))
+                .arg(
+                    Arg::new("credits")
+                        .long("credits")
+                        .value_parser(clap::value_parser!(u32)),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("undo")
+                .about("Undo the most recent saved edits")
+                .arg(plan())
+                .arg(
+                    Arg::new("count")
+                        .long("count")
+                        .default_value("1")
+                        .value_parser(clap::value_parser!(u64).range(1..))
+                        .help("How many edits to undo"),
+                ),
+        )
+}
+
+/// Parses `plan <subcommand> ...` arguments, including the leading `plan`.
+pub fn parse_plan_args<I, T>(args: I) -> Result<BatchRequest>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let matches = plan_cli().try_get_matches_from(args)?;
+    let json = matches.get_flag("json");
+
+    let (name, sub) = matches
+        .subcommand()
+        .ok_or_else(|| anyhow!("Missing plan subcommand"))?;
+
+    let action = match name {
+        "add" => BatchAction::Add {
+            semester: value(sub, "semester"),
+            module_codes: module_codes(sub),
+        },
+        "remove" => BatchAction::Remove {
+            semester: value(sub, "semester"),
+            module_codes: module_codes(sub),
+        },
+        "move" => BatchAction::Move {
+            from: value(sub, "from"),
+            to: value(sub, "to"),
+            module_codes: module_codes(sub),
+        },
+        "set-status" => BatchAction::SetStatus {
+            module_code: value(sub, "code").to_uppercase(),
+            semester: sub.get_one::<String>("semester").cloned(),
+            status: value(sub, "status").parse()?,
+        },
+        "set-grade" => BatchAction::SetGrade {
+            module_code: value(sub, "code").to_uppercase(),
+            semester: sub.get_one::<String>("semester").cloned(),
+            grade: Some(value(sub, "grade")).filter(|g| !g.trim().is_empty()),
+        },
+        "add-activity" => BatchAction::AddActivity {
+            semester: value(sub, "semester"),
+            activity: SpecialActivity {
+                activity_type: value(sub, "type").parse::<ActivityType>()?,
+                description: value(sub, "description"),
+                credits: sub.get_one::<u32>("credits").copied(),
+            },
+        },
+        "undo" => BatchAction::Undo {
+            count: sub
+                .get_one::<u64>("count")
+                .map_or(1, |&count| count as usize),
+        },
+        other => return Err(anyhow!("Unknown plan subcommand '{}'", other)),
+    };
+
+    Ok(BatchRequest {
+        plan_id: value(sub, "plan"),
+        action,
+        json,
+    })
+}
+
+fn value(matches: &ArgMatches, name: &str) -> String {
+    matches.get_one::<String>(name).cloned().unwrap_or_default()
+}
+
+fn module_codes(matches: &ArgMatches) -> Vec<String> {
+    matches
+        .get_many::<String>("codes")
+        .into_iter()
+        .flatten()
+        .map(|code| code.trim().to_uppercase())
+        .collect()
+}
@@ -0,0 +1,122 @@
+use crate::models::{promote_branch, Plan};
+use crate::storage::Storage;
+use anyhow::{anyhow, Result};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BranchAction {
+    List,
+    /// Forks the plan into a new branch with the given name.
+    Fork(String),
+    /// Makes the plan the primary branch of its family.
+    Promote,
+}
+
+/// Manages alternative-scenario branches of a plan.
+pub struct BranchCommand {
+    pub storage: Box<dyn Storage>,
+    pub plan_id: String,
+    pub action: BranchAction,
+}
+
+impl super::Command for BranchCommand {
+    fn run(&self) -> Result<()> {
+        match &self.action {
+            BranchAction::List => {
+                for plan in self.branches()? {
+                    println!(
+                        "{} {} — {} [{}]",
+                        if plan.is_primary() { "★" } else { " " },
+                        plan.branch_name(),
+                        plan.name,
+                        plan.id
+                    );
+                }
+            }
+            BranchAction::Fork(name) => {
+                let branch = self.fork(name)?;
+                println!("✅ Created branch '{}' of {}", name, self.plan_id);
+                println!("Plan ID: {}", branch.id);
+            }
+            BranchAction::Promote => {
+                self.promote()?;
+                println!("✅ {} is now the primary branch", self.plan_id);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BranchCommand {
+    fn plan(&self) -> Result<Plan> {
+        self.storage
+            .get_plan(&self.plan_id)?
+            .ok_or_else(|| anyhow!("Plan not found: {}", self.plan_id))
+    }
+
+    /// Every branch in the plan's family, primary first.
+    pub fn branches(&self) -> Result<Vec<Plan>> {
+        let plan = self.plan()?;
+        plan_family(self.storage.as_ref(), plan.family_id())
+    }
+
+    /// Forks the plan, saving both the new branch and the original (which
+    /// becomes `main` the first time it is forked).
+    pub fn fork(&self, branch_name: &str) -> Result<Plan> {
+        let branch_name = branch_name.trim();
+        if branch_name.is_empty() {
+            return Err(anyhow!("Branch name must not be empty"));
+        }
+
+        let mut plan = self.plan()?;
+        let family = plan_family(self.storage.as_ref(), plan.family_id())?;
+        if family
+            .iter()
+            .any(|p| p.branch_name().eq_ignore_ascii_case(branch_name))
+        {
+            return Err(anyhow!("Branch '{}' already exists", branch_name));
+        }
+
+        let at_version = self
+            .storage
+            .list_plan_versions(&plan.id)?
+            .last()
+            .map(|v| v.version);
+        let is_new_family = plan.branch.is_none();
+        let branch = plan.fork(Uuid::new_v4().to_string(), branch_name, at_version);
+
+        if is_new_family {
+            self.storage.save_plan(&plan)?;
+        }
+        self.storage.save_plan(&branch)?;
+
+        Ok(branch)
+    }
+
+    pub fn promote(&self) -> Result<()> {
+        let mut family = self.branches()?;
+        for id in promote_branch(&mut family, &self.plan_id)? {
+            if let Some(plan) = family.iter().find(|p| p.id == id) {
+                self.storage.save_plan(plan)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Loads every stored plan in a branch family, primary first and then by
+/// branch name.
+pub(crate) fn plan_family(storage: &dyn Storage, family_id: &str) -> Result<Vec<Plan>> {
+    let mut family: Vec<Plan> = storage
+        .list_plans()?
+        .into_iter()
+        .filter(|p| p.family_id() == family_id)
+        .collect();
+    family.sort_by(|a, b| {
+        b.is_primary()
+            .cmp(&a.is_primary())
+            .then_with(|| a.branch_name().cmp(b.branch_name()))
+    });
+    Ok(family)
+}
@@ -1,10 +1,23 @@
 use crate::api::NusmodsApi;
-use crate::models::{Plan, Student};
+use crate::models::{
+    academic_year_on, academic_years_around, validate_matriculation_year,
+    validate_target_graduation, CandidatureType, Curriculum, Plan, PlanTemplate, SemesterPlan,
+    Student, TemplateReport,
+};
 use crate::storage::Storage;
-use anyhow::{anyhow, Result};
-use dialoguer::{Input, Select};
+use anyhow::{anyhow, Context, Result};
+use chrono::{Local, NaiveDate};
+use clap::{Arg, ArgAction, ArgMatches};
+use dialoguer::{Confirm, Input, Select};
+use std::collections::{BTreeSet, HashSet};
 use uuid::Uuid;
 
+/// Academic years offered for the target graduation, starting from the
+/// current one.
+pub(crate) const GRADUATION_YEARS: usize = 7;
+/// Academic years offered for matriculation, ending with the current one.
+const MATRICULATION_YEARS: usize = 7;
+
 pub struct CreateCommand {
     pub storage: Box<dyn Storage>,
     pub api: Box<dyn NusmodsApi>,
@@ -14,7 +27,10 @@ impl super::Command for CreateCommand {
     fn run(&self) -> Result<()> {
         println!("Creating a new academic plan");
 
-        let student = self.get_or_create_student()?;
+        let student = super::student::ensure_student_id(
+            self.storage.as_ref(),
+            self.get_or_create_student()?,
+        )?;
 
         let plan_name: String = Input::new()
             .with_prompt("Enter a name for your academic plan")
@@ -22,25 +38,33 @@ impl super::Command for CreateCommand {
 
         let plan_id = Uuid::new_v4().to_string();
 
-        let mut plan = Plan::new(plan_id, plan_name, student.name.clone());
+        let mut plan = Plan::new(plan_id, plan_name, student.id.clone());
 
-        let years = ["2025/2026", "2026/2027", "2027/2028", "2028/2029"];
+        let today = Local::now().date_naive();
+        let years = academic_years_around(today, 0, GRADUATION_YEARS);
+        let expected = expected_graduation_term(&student);
         let year_index = Select::new()
             .with_prompt("Select target graduation academic year")
             .items(&years)
-            .default(0)
+            .default(
+                expected
+                    .as_ref()
+                    .and_then(|(year, _)| years.iter().position(|y| y == year))
+                    .unwrap_or(0),
+            )
             .interact()?;
 
         let semesters = ["Semester 1", "Semester 2"];
         let sem_index = Select::new()
             .with_prompt("Select target graduation semester")
             .items(&semesters)
-            .default(1)
+            .default(expected.map_or(1, |(_, semester)| usize::from(semester == 2)))
             .interact()?;
 
         plan.target_graduation = format!("{} {}", years[year_index], semesters[sem_index]);
 
         self.initialize_plan_semesters(&mut plan, &student)?;
+        self.offer_template(&mut plan, &student)?;
 
         self.storage.save_plan(&plan)?;
 
@@ -75,67 +99,80 @@ impl CreateCommand {
 
         let name: String = Input::new().with_prompt("Enter your name").interact()?;
 
-        let matriculation_years = ["2025/2026", "2024/2025", "2023/2024", "2022/2023"];
+        let today = Local::now().date_naive();
+        let mut matriculation_years =
+            academic_years_around(today, 1 - MATRICULATION_YEARS as i32, MATRICULATION_YEARS);
+        matriculation_years.reverse();
         let year_index = Select::new()
             .with_prompt("Select matriculation academic year")
             .items(&matriculation_years)
             .default(0)
             .interact()?;
 
-        let faculties = [
-            "College of Design and Engineering",
-            "Computing",
-            "Science",
-            "Arts and Social Sciences",
-            "Business",
-        ];
-        let faculty_index = Select::new()
-            .with_prompt("Select your faculty")
-            .items(&faculties)
-            .default(0)
-            .interact()?;
-
-        let major = if faculty_index == 0 {
-            let majors = [
-                "Industrial & Systems Engineering",
-                "Computer Engineering",
-                "Electrical Engineering",
-                "Mechanical Engineering",
-            ];
-            let major_index = Select::new()
-                .with_prompt("Select your major")
-                .items(&majors)
-                .default(0)
-                .interact()?;
-
-            majors[major_index].to_string()
+        let curricula = self.storage.list_curricula()?;
+
+        let faculty = select_or_enter("Select your faculty", faculties(&curricula))?;
+        let major = select_or_enter("Select your major", majors(&curricula, Some(&faculty)))?;
+
+        let second_major = if Confirm::new()
+            .with_prompt("Do you have a second major?")
+            .default(false)
+            .interact()?
+        {
+            let options = majors(&curricula, None)
+                .into_iter()
+                .filter(|m| *m != major)
+                .collect();
+            Some(select_or_enter("Select your second major", options)?)
         } else {
-            Input::new().with_prompt("Enter your major").interact()?
+            None
         };
 
+        let minors: String = Input::new()
+            .with_prompt("Enter your minors, separated by commas (leave empty for none)")
+            .allow_empty(true)
+            .interact()?;
+
+        let candidature_index = Select::new()
+            .with_prompt("Select your candidature type")
+            .items(&CandidatureType::ALL)
+            .default(0)
+            .interact()?;
+        let candidature_type = CandidatureType::ALL[candidature_index].clone();
+        let max_semesters = candidature_type.rules().max_semesters;
+
         let current_sem: u8 = Input::new()
-            .with_prompt("Enter your current semester (1-8)")
+            .with_prompt(format!("Enter your current semester (1-{})", max_semesters))
             .validate_with(|input: &u8| {
-                if *input >= 1 && *input <= 8 {
+                if *input >= 1 && *input <= max_semesters {
                     Ok(())
                 } else {
-                    Err("Please enter a value between 1 and 8")
+                    Err(format!(
+                        "Please enter a value between 1 and {}",
+                        max_semesters
+                    ))
                 }
             })
             .interact()?;
 
+        let exemptions: String = Input::new()
+            .with_prompt("Enter exempted module codes, separated by commas (leave empty for none)")
+            .allow_empty(true)
+            .interact()?;
+
         let student = Student {
+            id: Uuid::new_v4().to_string(),
             name,
-            matriculation_year: matriculation_years[year_index].to_string(),
-            faculty: faculties[faculty_index].to_string(),
+            matriculation_year: matriculation_years[year_index].clone(),
+            faculty,
             major,
-            second_major: None,
-            minors: Vec::new(),
-            completed_modules: std::collections::HashSet::new(),
-            exempted_modules: std::collections::HashSet::new(),
+            second_major,
+            minors: split_list(&minors),
+            completed_modules: HashSet::new(),
+            exempted_modules: module_codes(&split_list(&exemptions)),
             advanced_placement_credits: 0,
             current_semester: current_sem,
-            candidature_type: crate::models::CandidatureType::Standard,
+            candidature_type,
         };
 
         self.storage.save_student(&student)?;
@@ -145,42 +182,441 @@ impl CreateCommand {
         Ok(student)
     }
 
-    fn initialize_plan_semesters(&self, plan: &mut Plan, student: &Student) -> Result<()> {
-        let remaining_semesters = student.remaining_semesters();
+    /// Templates published for the student's curriculum and intake, by name.
+    pub fn templates_for(&self, student: &Student) -> Result<Vec<PlanTemplate>> {
+        let mut templates: Vec<PlanTemplate> = self
+            .storage
+            .list_plan_templates()?
+            .into_iter()
+            .filter(|t| t.matches(&student.major, &student.matriculation_year))
+            .collect();
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
 
-        let mat_year_parts: Vec<&str> = student.matriculation_year.split('/').collect();
-        if mat_year_parts.len() != 2 {
-            return Err(anyhow!("Invalid matriculation year format"));
+    fn offer_template(&self, plan: &mut Plan, student: &Student) -> Result<()> {
+        let templates = self.templates_for(student)?;
+        if templates.is_empty() {
+            return Ok(());
         }
 
-        let start_year = mat_year_parts[0].parse::<u32>()?;
-        let mut current_year = start_year + u32::from(student.current_semester - 1) / 2;
-        let mut current_sem = student.current_semester % 2;
-        if current_sem == 0 {
-            current_sem = 2;
+        let mut options = vec!["Start with an empty plan".to_string()];
+        options.extend(
+            templates
+                .iter()
+                .map(|t| format!("Use template: {}", t.name)),
+        );
+        let selection = Select::new()
+            .with_prompt("A recommended study plan is available for your cohort")
+            .items(&options)
+            .default(1)
+            .interact()?;
+        if selection == 0 {
+            return Ok(());
         }
 
-        for _ in 0..remaining_semesters {
-            let year_str = format!("{}/{}", current_year, current_year + 1);
+        let registry = self.api.get_module_registry()?;
+        let report = templates[selection - 1].apply(plan, student, &registry);
+        print_template_report(&report);
+
+        Ok(())
+    }
 
-            let semester_plan = crate::models::SemesterPlan {
-                year: year_str,
-                semester: current_sem,
-                modules: Vec::new(),
-                total_units: 0,
-                special_activities: Vec::new(),
-            };
+    /// Lays out every semester from the student's current one to the end of
+    /// their normal candidature, including any special terms the programme
+    /// schedules.
+    pub fn initialize_plan_semesters(&self, plan: &mut Plan, student: &Student) -> Result<()> {
+        initialize_plan_semesters(plan, student)
+    }
+}
 
-            plan.semesters.push(semester_plan);
+/// Where a non-interactive `create` gets its student profile from.
+#[derive(Debug, Clone)]
+pub enum StudentSource {
+    /// A profile already in storage, by name or ID.
+    Existing(String),
+    /// A new profile, from flags or a profile file.
+    New(Box<Student>),
+}
 
-            if current_sem == 2 {
-                current_sem = 1;
-                current_year += 1;
-            } else {
-                current_sem += 1;
+/// A `create` invocation as parsed from the command line.
+#[derive(Debug, Clone)]
+pub struct CreateRequest {
+    pub plan_name: String,
+    pub student: StudentSource,
+    /// Defaults to the end of the student's normal candidature.
+    pub target_graduation: Option<String>,
+    /// Name of a published template to start from.
+    pub template: Option<String>,
+}
+
+/// Creates a plan (and, if needed, a student profile) without prompting.
+pub struct BatchCreateCommand {
+    pub storage: Box<dyn Storage>,
+    pub api: Box<dyn NusmodsApi>,
+    pub request: CreateRequest,
+}
+
+impl super::Command for BatchCreateCommand {
+    fn run(&self) -> Result<()> {
+        let plan = self.execute()?;
+
+        println!("✅ Created new academic plan: {}", plan.name);
+        println!("Plan ID: {}", plan.id);
+
+        Ok(())
+    }
+}
+
+impl BatchCreateCommand {
+    /// Validates the request, saves any new student profile and the new
+    /// plan, and returns the plan. Nothing is saved if validation fails.
+    pub fn execute(&self) -> Result<Plan> {
+        if let Some(target) = &self.request.target_graduation {
+            validate_target_graduation(target)?;
+        }
+
+        let student = match &self.request.student {
+            StudentSource::Existing(key) => super::student::ensure_student_id(
+                self.storage.as_ref(),
+                super::student::find_student(self.storage.as_ref(), key)?,
+            )?,
+            StudentSource::New(student) => {
+                validate_student(student, &self.storage.list_curricula()?)?;
+                let mut student = student.as_ref().clone();
+                if student.id.is_empty() {
+                    student.id = Uuid::new_v4().to_string();
+                }
+                student
             }
+        };
+
+        let template = match &self.request.template {
+            Some(name) => Some(
+                self.storage
+                    .list_plan_templates()?
+                    .into_iter()
+                    .find(|t| t.name == *name)
+                    .ok_or_else(|| anyhow!("Template not found: {}", name))?,
+            ),
+            None => None,
+        };
+
+        let mut plan = Plan::new(
+            Uuid::new_v4().to_string(),
+            self.request.plan_name.clone(),
+            student.id.clone(),
+        );
+        plan.target_graduation = match &self.request.target_graduation {
+            Some(target) => target.trim().to_string(),
+            None => expected_graduation_term(&student)
+                .map(|(year, semester)| format!("{} Semester {}", year, semester))
+                .ok_or_else(|| anyhow!("Invalid matriculation year format"))?,
+        };
+        initialize_plan_semesters(&mut plan, &student)?;
+
+        if let Some(template) = template {
+            let registry = self.api.get_module_registry()?;
+            print_template_report(&template.apply(&mut plan, &student, &registry));
         }
 
-        Ok(())
+        if let StudentSource::New(_) = &self.request.student {
+            self.storage.save_student(&student)?;
+        }
+        self.storage.save_plan(&plan)?;
+
+        Ok(plan)
     }
 }
+
+pub fn create_cli() -> clap::Command {
+    let list = |name: &'static str, help: &'static str| {
+        Arg::new(name)
+            .long(name)
+            .action(ArgAction::Append)
+            .value_delimiter(',')
+            .help(help)
+    };
+
+    clap::Command::new("create")
+        .about("Create a plan without prompts")
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .required(true)
+                .help("Plan name"),
+        )
+        .arg(
+            Arg::new("graduation")
+                .long("graduation")
+                .help("Target graduation, e.g. \"2027/2028 Semester 2\""),
+        )
+        .arg(
+            Arg::new("template")
+                .long("template")
+                .help("Published template to start from"),
+        )
+        .arg(
+            Arg::new("student")
+                .long("student")
+                .help("Name or ID of an existing student profile"),
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .help("JSON file with a new student profile"),
+        )
+        .arg(
+            Arg::new("student-name")
+                .long("student-name")
+                .help("Name for a new student profile"),
+        )
+        .arg(Arg::new("matriculation-year").long("matriculation-year"))
+        .arg(Arg::new("faculty").long("faculty"))
+        .arg(Arg::new("major").long("major"))
+        .arg(Arg::new("second-major").long("second-major"))
+        .arg(list("minor", "Minor (repeat or separate with commas)"))
+        .arg(list(
+            "exempt",
+            "Exempted module code (repeat or separate with commas)",
+        ))
+        .arg(
+            Arg::new("candidature")
+                .long("candidature")
+                .help("standard, double-honours, ddp, concurrent-degree or engineering-scholars"),
+        )
+        .arg(
+            Arg::new("current-semester")
+                .long("current-semester")
+                .value_parser(clap::value_parser!(u8)),
+        )
+        .group(
+            clap::ArgGroup::new("source")
+                .args(["student", "profile", "student-name"])
+                .required(true),
+        )
+}
+
+/// Parses `create ...` arguments, including the leading `create`. New
+/// profiles given by flags default to the current academic year and first
+/// semester of study.
+pub fn parse_create_args<I, T>(args: I) -> Result<CreateRequest>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let matches = create_cli().try_get_matches_from(args)?;
+    let optional = |name: &str| matches.get_one::<String>(name).cloned();
+
+    let student = if let Some(name) = optional("student") {
+        StudentSource::Existing(name)
+    } else if let Some(path) = optional("profile") {
+        let json =
+            std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path))?;
+        let student: Student =
+            serde_json::from_str(&json).with_context(|| format!("Invalid profile {}", path))?;
+        StudentSource::New(Box::new(student))
+    } else {
+        StudentSource::New(Box::new(student_from_flags(
+            &matches,
+            Local::now().date_naive(),
+        )?))
+    };
+
+    let target_graduation = optional("graduation");
+    if let Some(target) = &target_graduation {
+        validate_target_graduation(target)?;
+    }
+
+    Ok(CreateRequest {
+        plan_name: optional("name").unwrap_or_default(),
+        student,
+        target_graduation,
+        template: optional("template"),
+    })
+}
+
+fn student_from_flags(matches: &ArgMatches, today: NaiveDate) -> Result<Student> {
+    let optional = |name: &str| matches.get_one::<String>(name).cloned();
+    let required = |name: &str| {
+        optional(name).ok_or_else(|| anyhow!("--{} is required for a new student", name))
+    };
+    let list = |name: &str| -> Vec<String> {
+        matches
+            .get_many::<String>(name)
+            .into_iter()
+            .flatten()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect()
+    };
+
+    Ok(Student {
+        id: Uuid::new_v4().to_string(),
+        name: required("student-name")?,
+        matriculation_year: optional("matriculation-year")
+            .unwrap_or_else(|| academic_year_on(today)),
+        faculty: required("faculty")?,
+        major: required("major")?,
+        second_major: optional("second-major"),
+        minors: list("minor"),
+        completed_modules: HashSet::new(),
+        exempted_modules: module_codes(&list("exempt")),
+        advanced_placement_credits: 0,
+        current_semester: matches
+            .get_one::<u8>("current-semester")
+            .copied()
+            .unwrap_or(1),
+        candidature_type: optional("candidature")
+            .map(|c| c.parse())
+            .transpose()?
+            .unwrap_or_default(),
+    })
+}
+
+/// Checks a new profile before it is saved. Majors are only checked when
+/// curriculum data is available.
+pub fn validate_student(student: &Student, curricula: &[Curriculum]) -> Result<()> {
+    if student.name.trim().is_empty() {
+        return Err(anyhow!("Student name must not be empty"));
+    }
+    validate_matriculation_year(&student.matriculation_year)?;
+
+    let max_semesters = student.candidature_type.rules().max_semesters;
+    if student.current_semester == 0 || student.current_semester > max_semesters {
+        return Err(anyhow!(
+            "Current semester must be between 1 and {} for {} candidature",
+            max_semesters,
+            student.candidature_type
+        ));
+    }
+
+    let known = majors(curricula, None);
+    if !known.is_empty() {
+        for major in std::iter::once(&student.major).chain(&student.second_major) {
+            if !known.contains(major) {
+                return Err(anyhow!(
+                    "Unknown major '{}' (available: {})",
+                    major,
+                    known.join(", ")
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn initialize_plan_semesters(plan: &mut Plan, student: &Student) -> Result<()> {
+    let rules = student.candidature_type.rules();
+    let last_semester = rules.normal_semesters.max(student.current_semester);
+
+    for number in student.current_semester..=last_semester {
+        let (year, semester) = student
+            .academic_term(number)
+            .ok_or_else(|| anyhow!("Invalid matriculation year format"))?;
+
+        plan.semesters
+            .push(SemesterPlan::empty(year.clone(), semester));
+
+        if semester == 2 && number < last_semester {
+            for &special_term in &rules.special_terms {
+                plan.semesters
+                    .push(SemesterPlan::empty(year.clone(), special_term));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The last regular semester of the student's normal candidature.
+fn expected_graduation_term(student: &Student) -> Option<(String, u8)> {
+    let rules = student.candidature_type.rules();
+    student.academic_term(rules.normal_semesters.max(student.current_semester))
+}
+
+fn print_template_report(report: &TemplateReport) {
+    println!(
+        "✅ Added {} module(s) from the template",
+        report.added.len()
+    );
+    if !report.skipped.is_empty() {
+        println!(
+            "Skipped already completed or exempted: {}",
+            report.skipped.join(", ")
+        );
+    }
+    if !report.carried_forward.is_empty() {
+        println!(
+            "⚠️  Template shifted {} semester(s) later; now overdue: {}",
+            report.shifted_by,
+            report.carried_forward.join(", ")
+        );
+    }
+    if !report.unplaced.is_empty() {
+        let semesters: Vec<String> = report.unplaced.iter().map(u8::to_string).collect();
+        println!(
+            "⚠️  Template semester(s) {} fall outside this plan",
+            semesters.join(", ")
+        );
+    }
+}
+
+/// Faculties named in the curriculum data, sorted.
+fn faculties(curricula: &[Curriculum]) -> Vec<String> {
+    curricula
+        .iter()
+        .filter_map(|c| c.faculty.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Majors with curriculum data, optionally limited to one faculty, sorted.
+fn majors(curricula: &[Curriculum], faculty: Option<&str>) -> Vec<String> {
+    curricula
+        .iter()
+        .filter(|c| faculty.is_none_or(|f| c.faculty.as_deref() == Some(f)))
+        .map(|c| c.name.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Offers a list with a free-text fallback; asks for text straight away when
+/// there is nothing to choose from.
+fn select_or_enter(prompt: &str, options: Vec<String>) -> Result<String> {
+    if options.is_empty() {
+        return Ok(Input::new().with_prompt(prompt).interact()?);
+    }
+
+    let mut items = options.clone();
+    items.push("Other (enter manually)".to_string());
+    let index = Select::new()
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    match options.get(index) {
+        Some(option) => Ok(option.clone()),
+        None => Ok(Input::new().with_prompt(prompt).interact()?),
+    }
+}
+
+fn split_list(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn module_codes(codes: &[String]) -> HashSet<String> {
+    codes
+        .iter()
+        .map(|code| code.trim().to_uppercase())
+        .collect()
+}
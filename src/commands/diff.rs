@@ -0,0 +1,88 @@
+use crate::diff::{diff_plans, DiffSign, PlanDiff};
+use crate::models::Plan;
+use crate::storage::Storage;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use std::str::FromStr;
+
+/// A plan as stored now, or one of its saved versions (`plan-id@3`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanRef {
+    Current(String),
+    Version(String, u32),
+}
+
+impl FromStr for PlanRef {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().rsplit_once('@') {
+            Some((id, version)) => {
+                let version = version
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid plan version '{}'", version))?;
+                Ok(PlanRef::Version(id.to_string(), version))
+            }
+            None => Ok(PlanRef::Current(s.trim().to_string())),
+        }
+    }
+}
+
+impl PlanRef {
+    pub fn load(&self, storage: &dyn Storage) -> Result<Plan> {
+        match self {
+            PlanRef::Current(id) => storage
+                .get_plan(id)?
+                .ok_or_else(|| anyhow!("Plan not found: {}", id)),
+            PlanRef::Version(id, version) => storage
+                .get_plan_version(id, *version)?
+                .map(|v| v.plan)
+                .ok_or_else(|| anyhow!("Version {} of plan {} not found", version, id)),
+        }
+    }
+}
+
+/// Shows what changed between two plans or plan versions.
+pub struct DiffCommand {
+    pub storage: Box<dyn Storage>,
+    pub from: PlanRef,
+    pub to: PlanRef,
+    /// Print the diff as JSON instead of text.
+    pub json: bool,
+}
+
+impl super::Command for DiffCommand {
+    fn run(&self) -> Result<()> {
+        let diff = self.execute()?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&diff)?);
+            return Ok(());
+        }
+
+        if diff.is_empty() {
+            println!("No differences");
+            return Ok(());
+        }
+
+        for (sign, line) in diff.lines() {
+            let line = format!("{} {}", sign.symbol(), line);
+            let line = match sign {
+                DiffSign::Added => line.green(),
+                DiffSign::Removed => line.red(),
+                DiffSign::Changed => line.yellow(),
+            };
+            println!("{}", line);
+        }
+
+        Ok(())
+    }
+}
+
+impl DiffCommand {
+    pub fn execute(&self) -> Result<PlanDiff> {
+        let from = self.from.load(self.storage.as_ref())?;
+        let to = self.to.load(self.storage.as_ref())?;
+        Ok(diff_plans(&from, &to))
+    }
+}
@@ -1,9 +1,19 @@
 use crate::api::NusmodsApi;
-use crate::models::{ModuleRegistry, ModuleStatus, Plan, PlannedModule};
+use crate::history::EditHistory;
+use crate::models::{
+    academic_years_around, Module, ModuleQuery, ModuleRegistry, ModuleStatus, Plan, PlanChange,
+    PlanField, Units,
+};
+use crate::plan_ops;
+use crate::repair;
 use crate::storage::Storage;
 use anyhow::{anyhow, Result};
+use chrono::Local;
 use dialoguer::{Confirm, Input, MultiSelect, Select};
 
+/// Search results beyond this many are not offered for selection.
+const MAX_SEARCH_RESULTS: usize = 30;
+
 pub struct EditCommand {
     pub storage: Box<dyn Storage>,
     pub api: Box<dyn NusmodsApi>,
@@ -18,17 +28,33 @@ impl super::Command for EditCommand {
             .ok_or_else(|| anyhow!("Plan not found"))?;
 
         let registry = self.api.get_module_registry()?;
+        super::reconcile_unit_totals(&mut plan, &registry);
 
         self.display_plan_summary(&plan);
 
+        let mut history = EditHistory::new();
+
         loop {
+            let undo_label = match history.next_undo(&plan) {
+                Some(edit) => format!("Undo: {}", edit.description),
+                None => "Undo (nothing to undo)".to_string(),
+            };
+            let redo_label = match history.next_redo() {
+                Some(edit) => format!("Redo: {}", edit.description),
+                None => "Redo (nothing to redo)".to_string(),
+            };
+
             let options = vec![
-                "Add modules to a semester",
-                "Remove modules from a semester",
-                "Move modules between semesters",
-                "Add special activities (e.g., internship)",
-                "Edit plan metadata",
-                "Save and exit",
+                "Add modules to a semester".to_string(),
+                "Remove modules from a semester".to_string(),
+                "Move modules between semesters".to_string(),
+                "Mark a module as failed".to_string(),
+                "Select timetable classes".to_string(),
+                "Add special activities (e.g., internship)".to_string(),
+                "Edit plan metadata".to_string(),
+                undo_label,
+                redo_label,
+                "Save and exit".to_string(),
             ];
 
             let selection = Select::new()
@@ -37,17 +63,38 @@ impl super::Command for EditCommand {
                 .default(0)
                 .interact()?;
 
-            match selection {
+            let changes = match selection {
                 0 => self.add_modules(&mut plan, &registry)?,
-                1 => self.remove_modules(&mut plan)?,
-                2 => self.move_modules(&mut plan)?,
-                3 => self.add_special_activities(&mut plan)?,
-                4 => self.edit_metadata(&mut plan)?,
-                5 => break,
+                1 => self.remove_modules(&mut plan, &registry)?,
+                2 => self.move_modules(&mut plan, &registry)?,
+                3 => self.mark_module_failed(&mut plan, &registry)?,
+                4 => self.select_classes(&mut plan, &registry)?,
+                5 => self.add_special_activities(&mut plan, &registry)?,
+                6 => self.edit_metadata(&mut plan)?,
+                7 => {
+                    match history.undo(&mut plan, &registry) {
+                        Ok(Some(edit)) => println!("↩️  Undid: {}", edit.description),
+                        Ok(None) => println!("Nothing to undo"),
+                        Err(error) => println!("❌ {}", error),
+                    }
+                    Vec::new()
+                }
+                8 => {
+                    match history.redo(&mut plan, &registry) {
+                        Ok(Some(edit)) => println!("↪️  Redid: {}", edit.description),
+                        Ok(None) => println!("Nothing to redo"),
+                        Err(error) => println!("❌ {}", error),
+                    }
+                    Vec::new()
+                }
+                9 => break,
                 _ => unreachable!(),
-            }
+            };
+
+            history.record(&mut plan, changes);
         }
 
+        plan.modified_at = chrono::Utc::now();
         self.storage.save_plan(&plan)?;
 
         println!("✅ Plan updated successfully");
@@ -111,7 +158,7 @@ impl EditCommand {
         println!("\nTotal units planned: {}", plan.total_units());
     }
 
-    fn add_modules(&self, plan: &mut Plan, registry: &ModuleRegistry) -> Result<()> {
+    fn add_modules(&self, plan: &mut Plan, registry: &ModuleRegistry) -> Result<Vec<PlanChange>> {
         let semester_options: Vec<String> = plan
             .semesters
             .iter()
@@ -125,112 +172,156 @@ impl EditCommand {
             .default(0)
             .interact()?;
 
-        let module_code: String = Input::new()
-            .with_prompt("Enter module code (or partial code to search)")
+        let input: String = Input::new()
+            .with_prompt("Enter module code, or words to search for")
+            .allow_empty(true)
             .interact()?;
 
-        if module_code.len() < 6 {
-            let matching_modules = self.api.search_modules(&module_code)?;
+        if let Some(module) = registry.get_module(&input.trim().to_uppercase()) {
+            return self.add_module_to_semester(plan, sem_index, module, registry);
+        }
 
-            if matching_modules.is_empty() {
-                println!("No modules found matching '{}'", module_code);
-                return Ok(());
-            }
+        let mut query = ModuleQuery::text(&input);
 
-            let module_options: Vec<String> = matching_modules
-                .iter()
-                .map(|m| format!("{}: {}", m.module_code, m.title))
-                .collect();
+        let filter = Confirm::new()
+            .with_prompt("Filter the results?")
+            .default(false)
+            .interact()?;
+        if filter {
+            self.prompt_search_filters(&mut query, plan.semesters[sem_index].semester)?;
+        }
 
-            let module_index = Select::new()
-                .with_prompt("Select a module")
-                .items(&module_options)
-                .default(0)
-                .interact()?;
+        let mut matching_modules: Vec<Module> =
+            registry.search(&query).into_iter().cloned().collect();
 
-            let selected_module = &matching_modules[module_index];
+        if matching_modules.is_empty() && !filter && !input.trim().is_empty() {
+            matching_modules = self.api.search_modules(&input)?;
+        }
 
-            let is_available =
-                self.check_module_availability(selected_module, plan.semesters[sem_index].semester);
+        if matching_modules.is_empty() {
+            println!("No modules found matching '{}'", input);
+            return Ok(Vec::new());
+        }
 
-            if !is_available {
-                let proceed = Confirm::new()
-                    .with_prompt(format!(
-                        "⚠️ {} is not typically offered in Semester {}. Add anyway?",
-                        selected_module.module_code, plan.semesters[sem_index].semester
-                    ))
-                    .default(false)
-                    .interact()?;
+        matching_modules.truncate(MAX_SEARCH_RESULTS);
 
-                if !proceed {
-                    return Ok(());
-                }
-            }
+        let module_options: Vec<String> = matching_modules
+            .iter()
+            .map(|m| format!("{}: {} ({} units)", m.module_code, m.title, m.module_credit))
+            .collect();
 
-            let planned_module = PlannedModule {
-                module_code: selected_module.module_code.clone(),
-                status: ModuleStatus::Planned,
-                grade: None,
-                s_u_option: false,
-            };
+        let module_index = Select::new()
+            .with_prompt("Select a module")
+            .items(&module_options)
+            .default(0)
+            .interact()?;
 
-            if let Ok(credits_str) = selected_module.module_credit.parse::<u32>() {
-                plan.semesters[sem_index].total_units += credits_str;
-            }
+        self.add_module_to_semester(plan, sem_index, &matching_modules[module_index], registry)
+    }
 
-            plan.semesters[sem_index].modules.push(planned_module);
+    fn prompt_search_filters(&self, query: &mut ModuleQuery, semester: u8) -> Result<()> {
+        let levels = [1000, 2000, 3000, 4000, 5000, 6000];
+        let level_options: Vec<String> = levels.iter().map(|l| format!("Level {}", l)).collect();
+        let selected = MultiSelect::new()
+            .with_prompt("Levels (none selected for any)")
+            .items(&level_options)
+            .interact()?;
+        query.levels = selected.into_iter().map(|i| levels[i]).collect();
 
-            println!(
-                "✅ Added {} to {} Semester {}",
-                selected_module.module_code,
-                plan.semesters[sem_index].year,
-                plan.semesters[sem_index].semester
-            );
-        } else if let Some(module) = registry.get_module(&module_code) {
-            let is_available =
-                self.check_module_availability(module, plan.semesters[sem_index].semester);
-
-            if !is_available {
-                let proceed = Confirm::new()
-                    .with_prompt(format!(
-                        "⚠️ {} is not typically offered in Semester {}. Add anyway?",
-                        module.module_code, plan.semesters[sem_index].semester
-                    ))
-                    .default(false)
-                    .interact()?;
+        let department: String = Input::new()
+            .with_prompt("Department (leave empty for any)")
+            .allow_empty(true)
+            .interact()?;
+        query.department = Some(department).filter(|d| !d.trim().is_empty());
 
-                if !proceed {
-                    return Ok(());
-                }
-            }
+        let faculty: String = Input::new()
+            .with_prompt("Faculty (leave empty for any)")
+            .allow_empty(true)
+            .interact()?;
+        query.faculty = Some(faculty).filter(|f| !f.trim().is_empty());
 
-            let planned_module = PlannedModule {
-                module_code: module.module_code.clone(),
-                status: ModuleStatus::Planned,
-                grade: None,
-                s_u_option: false,
-            };
+        let units: String = Input::new()
+            .with_prompt("Units (leave empty for any)")
+            .allow_empty(true)
+            .interact()?;
+        query.units = units.trim().parse::<Units>().ok();
 
-            plan.semesters[sem_index].modules.push(planned_module);
+        let offered_only = Confirm::new()
+            .with_prompt(format!("Only modules offered in Semester {}?", semester))
+            .default(true)
+            .interact()?;
+        query.semester = offered_only.then_some(semester);
 
-            if let Ok(credits) = module.module_credit.parse::<u32>() {
-                plan.semesters[sem_index].total_units += credits;
-            }
+        let exam_options = vec!["Any", "With final exam", "No final exam"];
+        let exam_index = Select::new()
+            .with_prompt("Final exam")
+            .items(&exam_options)
+            .default(0)
+            .interact()?;
+        query.has_exam = match exam_index {
+            1 => Some(true),
+            2 => Some(false),
+            _ => None,
+        };
 
-            println!(
-                "✅ Added {} to {} Semester {}",
-                module.module_code,
-                plan.semesters[sem_index].year,
-                plan.semesters[sem_index].semester
-            );
-        } else {
-            println!("⚠️ Module '{}' not found in registry", module_code);
-        }
+        let workload: String = Input::new()
+            .with_prompt("Maximum weekly workload hours (leave empty for any)")
+            .allow_empty(true)
+            .interact()?;
+        query.max_workload = workload.trim().parse::<f32>().ok();
 
         Ok(())
     }
 
-    fn remove_modules(&self, plan: &mut Plan) -> Result<()> {
+    fn add_module_to_semester(
+        &self,
+        plan: &mut Plan,
+        sem_index: usize,
+        module: &Module,
+        registry: &ModuleRegistry,
+    ) -> Result<Vec<PlanChange>> {
+        let semester = &plan.semesters[sem_index];
+        // Judge availability by the semester's own academic year
+        let module = registry
+            .get_module_for_year(&module.module_code, &semester.year)
+            .unwrap_or(module);
+
+        if !self.check_module_availability(module, semester.semester) {
+            let proceed = Confirm::new()
+                .with_prompt(format!(
+                    "⚠️ {} is not typically offered in Semester {}. Add anyway?",
+                    module.module_code, semester.semester
+                ))
+                .default(false)
+                .interact()?;
+
+            if !proceed {
+                return Ok(Vec::new());
+            }
+        }
+
+        match plan_ops::add_searched_module(plan, registry, sem_index, module) {
+            Ok(change) => {
+                println!(
+                    "✅ Added {} to {} Semester {}",
+                    module.module_code,
+                    plan.semesters[sem_index].year,
+                    plan.semesters[sem_index].semester
+                );
+                Ok(vec![change])
+            }
+            Err(error) => {
+                println!("❌ {}", error);
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    fn remove_modules(
+        &self,
+        plan: &mut Plan,
+        registry: &ModuleRegistry,
+    ) -> Result<Vec<PlanChange>> {
         let semester_options: Vec<String> = plan
             .semesters
             .iter()
@@ -248,7 +339,7 @@ impl EditCommand {
 
         if semester.modules.is_empty() {
             println!("No modules to remove in this semester");
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         let module_options: Vec<String> = semester
@@ -274,34 +365,29 @@ impl EditCommand {
             .interact()?;
 
         if selected_indices.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
 
-        let registry = self.api.get_module_registry()?;
-
-        let mut indices_to_remove: Vec<usize> = selected_indices;
-        indices_to_remove.sort_unstable();
-        indices_to_remove.reverse();
-
-        for index in indices_to_remove {
-            let module_code = plan.semesters[sem_index].modules[index].module_code.clone();
-
-            if let Some(module) = registry.get_module(&module_code) {
-                if let Ok(credits) = module.module_credit.parse::<u32>() {
-                    plan.semesters[sem_index].total_units = plan.semesters[sem_index]
-                        .total_units
-                        .saturating_sub(credits);
-                }
-            }
+        let codes: Vec<String> = selected_indices
+            .into_iter()
+            .map(|i| semester.modules[i].module_code.clone())
+            .collect();
 
-            plan.semesters[sem_index].modules.remove(index);
+        let mut changes = Vec::new();
+        for module_code in codes {
+            changes.push(plan_ops::remove_module(
+                plan,
+                registry,
+                sem_index,
+                &module_code,
+            )?);
             println!("Removed {}", module_code);
         }
 
-        Ok(())
+        Ok(changes)
     }
 
-    fn move_modules(&self, plan: &mut Plan) -> Result<()> {
+    fn move_modules(&self, plan: &mut Plan, registry: &ModuleRegistry) -> Result<Vec<PlanChange>> {
         let semester_options: Vec<String> = plan
             .semesters
             .iter()
@@ -319,7 +405,7 @@ impl EditCommand {
 
         if source_semester.modules.is_empty() {
             println!("No modules to move in this semester");
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         let module_options: Vec<String> = source_semester
@@ -334,7 +420,7 @@ impl EditCommand {
             .interact()?;
 
         if selected_indices.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         let target_index = Select::new()
@@ -345,55 +431,301 @@ impl EditCommand {
 
         if source_index == target_index {
             println!("Source and target semesters are the same");
-            return Ok(());
+            return Ok(Vec::new());
         }
 
-        let mut modules_to_move = Vec::new();
-        let registry = self.api.get_module_registry()?;
+        let codes: Vec<String> = selected_indices
+            .into_iter()
+            .map(|i| module_options[i].clone())
+            .collect();
 
-        for &index in &selected_indices {
-            let module = plan.semesters[source_index].modules[index].clone();
-            modules_to_move.push(module);
-
-            if let Some(registry_module) =
-                registry.get_module(&plan.semesters[source_index].modules[index].module_code)
-            {
-                if let Ok(credits) = registry_module.module_credit.parse::<u32>() {
-                    plan.semesters[source_index].total_units = plan.semesters[source_index]
-                        .total_units
-                        .saturating_sub(credits);
+        let mut changes = Vec::new();
+        for module_code in codes {
+            match plan_ops::move_module(plan, registry, source_index, target_index, &module_code) {
+                Ok(change) => {
+                    println!(
+                        "Moved {} to {} Semester {}",
+                        module_code,
+                        plan.semesters[target_index].year,
+                        plan.semesters[target_index].semester
+                    );
+                    changes.push(change);
                 }
+                Err(error) => println!("❌ {}", error),
             }
         }
 
-        let mut indices_to_remove = selected_indices.clone();
-        indices_to_remove.sort_unstable();
-        indices_to_remove.reverse();
+        Ok(changes)
+    }
+
+    fn mark_module_failed(
+        &self,
+        plan: &mut Plan,
+        registry: &ModuleRegistry,
+    ) -> Result<Vec<PlanChange>> {
+        let semester_options: Vec<String> = plan
+            .semesters
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("[{}] {} Semester {}", i + 1, s.year, s.semester))
+            .collect();
+
+        let sem_index = Select::new()
+            .with_prompt("Select the semester the module was taken in")
+            .items(&semester_options)
+            .default(0)
+            .interact()?;
+
+        let candidates: Vec<usize> = plan.semesters[sem_index]
+            .modules
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.status != ModuleStatus::Failed)
+            .map(|(i, _)| i)
+            .collect();
 
-        for index in indices_to_remove {
-            plan.semesters[source_index].modules.remove(index);
+        if candidates.is_empty() {
+            println!("No modules to mark as failed in this semester");
+            return Ok(Vec::new());
         }
 
-        for module in modules_to_move {
-            if let Some(registry_module) = registry.get_module(&module.module_code) {
-                if let Ok(credits) = registry_module.module_credit.parse::<u32>() {
-                    plan.semesters[target_index].total_units += credits;
-                }
+        let module_options: Vec<String> = candidates
+            .iter()
+            .map(|&i| plan.semesters[sem_index].modules[i].module_code.clone())
+            .collect();
+
+        let selection = Select::new()
+            .with_prompt("Select the failed module")
+            .items(&module_options)
+            .default(0)
+            .interact()?;
+
+        let grade: String = Input::new()
+            .with_prompt("Enter grade received")
+            .with_initial_text("F")
+            .allow_empty(true)
+            .interact()?;
+
+        let failed_code = module_options[selection].clone();
+        let mut changes = vec![
+            plan_ops::set_status(plan, &failed_code, Some(sem_index), ModuleStatus::Failed)?,
+            plan_ops::set_grade(plan, &failed_code, Some(sem_index), Some(grade))?,
+        ];
+
+        println!("❌ Marked {} as failed", failed_code);
+
+        let credited = self
+            .storage
+            .get_student(&plan.student_id)?
+            .map(|student| student.credited_modules())
+            .unwrap_or_default();
+
+        let dependents =
+            repair::find_broken_dependents(plan, registry, &credited, &failed_code, sem_index);
+
+        if dependents.is_empty() {
+            println!("No later modules depend on {}", failed_code);
+        } else {
+            println!("⚠️ The following modules no longer have their prerequisites met:");
+            for dependent in &dependents {
+                let semester = &plan.semesters[dependent.semester_index];
+                println!(
+                    "  - {} ({} Semester {})",
+                    dependent.module_code, semester.year, semester.semester
+                );
             }
+        }
 
-            plan.semesters[target_index].modules.push(module.clone());
+        let Some(retake_index) =
+            repair::propose_retake_semester(plan, registry, &failed_code, sem_index)
+        else {
+            println!(
+                "⚠️ No later semester in this plan offers {}; add a semester to schedule a retake",
+                failed_code
+            );
+            return Ok(changes);
+        };
+
+        let retake = Confirm::new()
+            .with_prompt(format!(
+                "Schedule a retake of {} in {} Semester {}?",
+                failed_code,
+                plan.semesters[retake_index].year,
+                plan.semesters[retake_index].semester
+            ))
+            .default(true)
+            .interact()?;
+
+        if !retake {
+            return Ok(changes);
+        }
+
+        repair::schedule_retake(plan, registry, &failed_code, retake_index);
+        changes.push(PlanChange::Added {
+            module_code: failed_code.clone(),
+            semester_index: retake_index,
+        });
+        println!(
+            "✅ Added retake of {} to {} Semester {}",
+            failed_code, plan.semesters[retake_index].year, plan.semesters[retake_index].semester
+        );
+
+        if dependents.is_empty() {
+            return Ok(changes);
+        }
+
+        let shift = Confirm::new()
+            .with_prompt(format!(
+                "Shift {} dependent module(s) to later semesters?",
+                dependents.len()
+            ))
+            .default(true)
+            .interact()?;
+
+        if !shift {
+            return Ok(changes);
+        }
+
+        let original_len = plan.semesters.len();
+        let shifts =
+            repair::shift_dependents(plan, registry, &failed_code, retake_index, &dependents);
+
+        changes.extend(plan.semesters[original_len..].iter().map(|semester| {
+            PlanChange::SemesterAppended {
+                year: semester.year.clone(),
+                semester: semester.semester,
+            }
+        }));
+        changes.extend(shifts.iter().map(|moved| PlanChange::Moved {
+            module_code: moved.module_code.clone(),
+            from_semester: moved.from_semester,
+            to_semester: moved.to_semester,
+        }));
+
+        for moved in &shifts {
             println!(
                 "Moved {} to {} Semester {}",
-                module.module_code,
-                plan.semesters[target_index].year,
-                plan.semesters[target_index].semester
+                moved.module_code,
+                plan.semesters[moved.to_semester].year,
+                plan.semesters[moved.to_semester].semester
             );
         }
 
-        Ok(())
+        if shifts.len() < dependents.len() {
+            println!("⚠️ Some dependent modules could not be shifted; review them manually");
+        }
+
+        Ok(changes)
+    }
+
+    fn select_classes(
+        &self,
+        plan: &mut Plan,
+        registry: &ModuleRegistry,
+    ) -> Result<Vec<PlanChange>> {
+        let semester_options: Vec<String> = plan
+            .semesters
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("[{}] {} Semester {}", i + 1, s.year, s.semester))
+            .collect();
+
+        let sem_index = Select::new()
+            .with_prompt("Select a semester")
+            .items(&semester_options)
+            .default(0)
+            .interact()?;
+
+        let semester = &plan.semesters[sem_index];
+
+        if semester.modules.is_empty() {
+            println!("No modules planned in this semester");
+            return Ok(Vec::new());
+        }
+
+        let module_options: Vec<String> = semester
+            .modules
+            .iter()
+            .map(|m| m.module_code.clone())
+            .collect();
+
+        let module_index = Select::new()
+            .with_prompt("Select a module")
+            .items(&module_options)
+            .default(0)
+            .interact()?;
+
+        let sem_number = semester.semester;
+        let module_code = semester.modules[module_index].module_code.clone();
+        let mut selections = semester.modules[module_index].class_selections.clone();
+
+        let lessons = registry
+            .get_module_for_year(&module_code, &semester.year)
+            .and_then(|m| m.semester_data.iter().find(|d| d.semester == sem_number))
+            .and_then(|d| d.timetable.as_ref())
+            .filter(|l| !l.is_empty());
+
+        let Some(lessons) = lessons else {
+            println!(
+                "⚠️ No timetable data for {} in Semester {}",
+                module_code, sem_number
+            );
+            return Ok(Vec::new());
+        };
+
+        let mut lesson_types: Vec<&str> = lessons.iter().map(|l| l.lesson_type.as_str()).collect();
+        lesson_types.sort_unstable();
+        lesson_types.dedup();
+
+        for lesson_type in lesson_types {
+            let mut classes: Vec<&str> = lessons
+                .iter()
+                .filter(|l| l.lesson_type == lesson_type)
+                .map(|l| l.class_no.as_str())
+                .collect();
+            classes.sort_unstable();
+            classes.dedup();
+
+            let mut class_options = vec!["Any".to_string()];
+            for class_no in &classes {
+                let slots: Vec<String> = lessons
+                    .iter()
+                    .filter(|l| l.lesson_type == lesson_type && l.class_no == *class_no)
+                    .map(|l| format!("{} {}-{}", l.day, l.start_time, l.end_time))
+                    .collect();
+                class_options.push(format!("{} ({})", class_no, slots.join(", ")));
+            }
+
+            let default = selections
+                .get(lesson_type)
+                .and_then(|selected| classes.iter().position(|c| c == selected))
+                .map(|i| i + 1)
+                .unwrap_or(0);
+
+            let choice = Select::new()
+                .with_prompt(format!("{} {}", module_code, lesson_type))
+                .items(&class_options)
+                .default(default)
+                .interact()?;
+
+            if choice == 0 {
+                selections.remove(lesson_type);
+            } else {
+                selections.insert(lesson_type.to_string(), classes[choice - 1].to_string());
+            }
+        }
+
+        let change = plan_ops::select_classes(plan, sem_index, &module_code, selections)?;
+        println!("✅ Updated class selections for {}", module_code);
+
+        Ok(vec![change])
     }
 
-    fn add_special_activities(&self, plan: &mut Plan) -> Result<()> {
+    fn add_special_activities(
+        &self,
+        plan: &mut Plan,
+        registry: &ModuleRegistry,
+    ) -> Result<Vec<PlanChange>> {
         let semester_options: Vec<String> = plan
             .semesters
             .iter()
@@ -462,21 +794,17 @@ impl EditCommand {
             credits,
         };
 
-        plan.semesters[sem_index].special_activities.push(activity);
-
-        if let Some(credit_val) = credits {
-            plan.semesters[sem_index].total_units += credit_val;
-        }
+        let change = plan_ops::add_activity(plan, registry, sem_index, activity)?;
 
         println!(
             "✅ Added special activity to {} Semester {}",
             plan.semesters[sem_index].year, plan.semesters[sem_index].semester
         );
 
-        Ok(())
+        Ok(vec![change])
     }
 
-    fn edit_metadata(&self, plan: &mut Plan) -> Result<()> {
+    fn edit_metadata(&self, plan: &mut Plan) -> Result<Vec<PlanChange>> {
         let options = vec![
             "Edit plan name",
             "Edit target graduation",
@@ -490,22 +818,32 @@ impl EditCommand {
             .default(0)
             .interact()?;
 
-        match selection {
+        let change = match selection {
             0 => {
                 let new_name: String = Input::new()
                     .with_prompt("Enter new plan name")
                     .with_initial_text(&plan.name)
                     .interact()?;
 
-                plan.name = new_name;
+                let change = plan_ops::set_field(plan, PlanField::Name, Some(new_name))?;
                 println!("✅ Plan name updated");
+                change
             }
             1 => {
-                let years = ["2025/2026", "2026/2027", "2027/2028", "2028/2029"];
+                let years = academic_years_around(
+                    Local::now().date_naive(),
+                    0,
+                    super::create::GRADUATION_YEARS,
+                );
                 let year_index = Select::new()
                     .with_prompt("Select target graduation academic year")
                     .items(&years)
-                    .default(0)
+                    .default(
+                        years
+                            .iter()
+                            .position(|y| plan.target_graduation.starts_with(y.as_str()))
+                            .unwrap_or(0),
+                    )
                     .interact()?;
 
                 let semesters = ["Semester 1", "Semester 2"];
@@ -515,8 +853,13 @@ impl EditCommand {
                     .default(1)
                     .interact()?;
 
-                plan.target_graduation = format!("{} {}", years[year_index], semesters[sem_index]);
+                let change = plan_ops::set_field(
+                    plan,
+                    PlanField::TargetGraduation,
+                    Some(format!("{} {}", years[year_index], semesters[sem_index])),
+                )?;
                 println!("✅ Target graduation updated");
+                change
             }
             2 => {
                 let current_notes = plan.notes.as_deref().unwrap_or("");
@@ -526,24 +869,18 @@ impl EditCommand {
                     .allow_empty(true)
                     .interact()?;
 
-                plan.notes = if new_notes.is_empty() {
-                    None
-                } else {
-                    Some(new_notes)
-                };
+                let change = plan_ops::set_field(plan, PlanField::Notes, Some(new_notes))?;
                 println!("✅ Notes updated");
+                change
             }
-            3 => return Ok(()),
+            3 => return Ok(Vec::new()),
             _ => unreachable!(),
-        }
+        };
 
-        Ok(())
+        Ok(vec![change])
     }
 
     pub fn check_module_availability(&self, module: &crate::models::Module, semester: u8) -> bool {
-        module
-            .semester_data
-            .iter()
-            .any(|sem_data| sem_data.semester == semester)
+        module.is_offered_in(semester)
     }
 }
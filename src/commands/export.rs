@@ -1,4 +1,5 @@
 use crate::api::NusmodsApi;
+use crate::graph::DependencyGraph;
 use crate::models::Plan;
 use crate::storage::Storage;
 use anyhow::{anyhow, Result};
@@ -13,12 +14,26 @@ pub struct ExportCommand {
 
 impl super::Command for ExportCommand {
     fn run(&self) -> Result<()> {
-        let plan = self
+        let mut plan = self
             .storage
             .get_plan(&self.plan_id)?
             .ok_or_else(|| anyhow!("Plan not found"))?;
 
-        let formats = vec!["JSON", "CSV", "Markdown", "Plain Text"];
+        // Lets a copy edited elsewhere be merged back against this version
+        plan.base_version = self
+            .storage
+            .list_plan_versions(&self.plan_id)?
+            .last()
+            .map(|v| v.version);
+
+        let formats = vec![
+            "JSON",
+            "CSV",
+            "Markdown",
+            "Plain Text",
+            "Graphviz DOT (dependency graph)",
+            "Mermaid (dependency graph)",
+        ];
         let format_index = Select::new()
             .with_prompt("Select export format")
             .items(&formats)
@@ -36,6 +51,8 @@ impl super::Command for ExportCommand {
             1 => self.export_csv(&plan, &filename)?,
             2 => self.export_markdown(&plan, &filename)?,
             3 => self.export_text(&plan, &filename)?,
+            4 => self.export_dot(&plan, &filename)?,
+            5 => self.export_mermaid(&plan, &filename)?,
             _ => unreachable!(),
         }
 
@@ -44,6 +61,34 @@ impl super::Command for ExportCommand {
 }
 
 impl ExportCommand {
+    pub fn export_dot(&self, plan: &Plan, filename: &str) -> Result<()> {
+        let path = format!("{}.dot", filename);
+
+        let api = crate::api::DefaultNusmodsApi::new()?;
+        let registry = api.get_module_registry()?;
+        let graph = DependencyGraph::from_plan(plan, &registry);
+
+        let mut file = File::create(&path)?;
+        file.write_all(graph.to_dot(plan).as_bytes())?;
+
+        println!("✅ Exported dependency graph to {}", path);
+        Ok(())
+    }
+
+    pub fn export_mermaid(&self, plan: &Plan, filename: &str) -> Result<()> {
+        let path = format!("{}.mmd", filename);
+
+        let api = crate::api::DefaultNusmodsApi::new()?;
+        let registry = api.get_module_registry()?;
+        let graph = DependencyGraph::from_plan(plan, &registry);
+
+        let mut file = File::create(&path)?;
+        file.write_all(graph.to_mermaid(plan).as_bytes())?;
+
+        println!("✅ Exported dependency graph to {}", path);
+        Ok(())
+    }
+
     pub fn export_json(&self, plan: &Plan, filename: &str) -> Result<()> {
         let path = format!("{}.json", filename);
         let json = serde_json::to_string_pretty(plan)?;
@@ -70,13 +115,13 @@ impl ExportCommand {
         for semester in &plan.semesters {
             for module in &semester.modules {
                 let title = registry
-                    .get_module(&module.module_code)
+                    .get_module_for_year(&module.module_code, &semester.year)
                     .map(|m| m.title.clone())
                     .unwrap_or_else(|| "Unknown".to_string());
 
                 let credits = registry
-                    .get_module(&module.module_code)
-                    .map(|m| m.module_credit.clone())
+                    .get_module_for_year(&module.module_code, &semester.year)
+                    .map(|m| m.module_credit.to_string())
                     .unwrap_or_else(|| "0".to_string());
 
                 let status = match module.status {
@@ -107,7 +152,7 @@ impl ExportCommand {
 
         writeln!(file, "# Academic Plan: {}", plan.name)?;
         writeln!(file, "\nTarget Graduation: {}", plan.target_graduation)?;
-        writeln!(file, "Total Units: {}", plan.total_units())?;
+        writeln!(file, "Total Units: {}", plan.derived_units(&registry))?;
 
         if let Some(ref notes) = plan.notes {
             writeln!(file, "\n## Notes\n\n{}", notes)?;
@@ -122,7 +167,7 @@ impl ExportCommand {
                 i + 1,
                 semester.year,
                 semester.semester,
-                semester.total_units
+                semester.derived_units(&registry)
             )?;
 
             if !semester.modules.is_empty() {
@@ -131,13 +176,13 @@ impl ExportCommand {
 
                 for module in &semester.modules {
                     let title = registry
-                        .get_module(&module.module_code)
+                        .get_module_for_year(&module.module_code, &semester.year)
                         .map(|m| m.title.clone())
                         .unwrap_or_else(|| "Unknown".to_string());
 
                     let credits = registry
-                        .get_module(&module.module_code)
-                        .map(|m| m.module_credit.clone())
+                        .get_module_for_year(&module.module_code, &semester.year)
+                        .map(|m| m.module_credit.to_string())
                         .unwrap_or_else(|| "0".to_string());
 
                     let status = match module.status {
@@ -204,7 +249,7 @@ impl ExportCommand {
 
         writeln!(file, "ACADEMIC PLAN: {}", plan.name)?;
         writeln!(file, "Target Graduation: {}", plan.target_graduation)?;
-        writeln!(file, "Total Units: {}", plan.total_units())?;
+        writeln!(file, "Total Units: {}", plan.derived_units(&registry))?;
 
         if let Some(ref notes) = plan.notes {
             writeln!(file, "\nNOTES:\n{}", notes)?;
@@ -219,19 +264,19 @@ impl ExportCommand {
                 i + 1,
                 semester.year,
                 semester.semester,
-                semester.total_units
+                semester.derived_units(&registry)
             )?;
 
             if !semester.modules.is_empty() {
                 for module in &semester.modules {
                     let title = registry
-                        .get_module(&module.module_code)
+                        .get_module_for_year(&module.module_code, &semester.year)
                         .map(|m| m.title.clone())
                         .unwrap_or_else(|| "Unknown".to_string());
 
                     let credits = registry
-                        .get_module(&module.module_code)
-                        .map(|m| m.module_credit.clone())
+                        .get_module_for_year(&module.module_code, &semester.year)
+                        .map(|m| m.module_credit.to_string())
                         .unwrap_or_else(|| "0".to_string());
 
                     let status = match module.status {
@@ -1,13 +1,19 @@
+use crate::api::NusmodsApi;
+use crate::merge::{three_way_merge, MergeSide, PlanMerge};
 use crate::models::Plan;
 use crate::storage::Storage;
+use crate::transcript;
 use anyhow::{anyhow, Result};
-use dialoguer::{Confirm, Input};
+use chrono::Utc;
+use dialoguer::{Confirm, Input, Select};
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
 pub struct ImportCommand {
     pub storage: Box<dyn Storage>,
+    /// Settles every merge conflict in favour of one side instead of asking.
+    pub on_conflict: Option<MergeSide>,
 }
 
 impl super::Command for ImportCommand {
@@ -25,22 +31,55 @@ impl super::Command for ImportCommand {
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
 
-        let imported_plan: Plan = serde_json::from_str(&contents)?;
+        let mut imported_plan: Plan = serde_json::from_str(&contents)?;
 
-        if self.storage.get_plan(&imported_plan.id)?.is_some() {
-            let overwrite = Confirm::new()
-                .with_prompt(format!(
-                    "A plan with ID {} already exists. Overwrite?",
-                    imported_plan.id
-                ))
-                .default(false)
-                .interact()?;
+        // Totals in older or hand-edited files are often stale
+        let registry = crate::api::DefaultNusmodsApi::new()?.get_module_registry()?;
+        super::reconcile_unit_totals(&mut imported_plan, &registry);
+
+        if let Some(local) = self.storage.get_plan(&imported_plan.id)? {
+            if imported_plan.base_version.is_some() {
+                let choices = [
+                    "Merge with the local copy",
+                    "Overwrite the local copy",
+                    "Cancel",
+                ];
+                let choice = Select::new()
+                    .with_prompt(format!(
+                        "A plan with ID {} already exists",
+                        imported_plan.id
+                    ))
+                    .items(&choices)
+                    .default(0)
+                    .interact()?;
+
+                match choice {
+                    0 => {
+                        let mut merge = self.merge_plan(&local, &imported_plan)?;
+                        self.resolve_conflicts(&mut merge)?;
+                        imported_plan = merge.plan;
+                        imported_plan.repair_unit_totals(&registry);
+                    }
+                    1 => {}
+                    _ => return Err(anyhow!("Import cancelled")),
+                }
+            } else {
+                let overwrite = Confirm::new()
+                    .with_prompt(format!(
+                        "A plan with ID {} already exists. Overwrite?",
+                        imported_plan.id
+                    ))
+                    .default(false)
+                    .interact()?;
 
-            if !overwrite {
-                return Err(anyhow!("Import cancelled"));
+                if !overwrite {
+                    return Err(anyhow!("Import cancelled"));
+                }
             }
         }
 
+        imported_plan.base_version = None;
+        imported_plan.modified_at = Utc::now();
         self.storage.save_plan(&imported_plan)?;
 
         println!("✅ Imported plan: {}", imported_plan.name);
@@ -48,3 +87,119 @@ impl super::Command for ImportCommand {
         Ok(())
     }
 }
+
+impl ImportCommand {
+    /// Merges an imported copy into the local plan, using the saved version
+    /// the copy was exported from as the common ancestor. Conflicts are
+    /// settled here only when `on_conflict` is set.
+    pub fn merge_plan(&self, local: &Plan, imported: &Plan) -> Result<PlanMerge> {
+        let base_version = imported.base_version.ok_or_else(|| {
+            anyhow!("Imported plan does not record the version it was exported from")
+        })?;
+        let base = self
+            .storage
+            .get_plan_version(&local.id, base_version)?
+            .ok_or_else(|| {
+                anyhow!(
+                    "Version {} of plan {} is no longer stored, so the copies cannot be merged",
+                    base_version,
+                    local.id
+                )
+            })?;
+
+        let mut merge = three_way_merge(&base.plan, local, imported);
+        if let Some(side) = self.on_conflict {
+            merge.resolve_all(side);
+        }
+        Ok(merge)
+    }
+
+    fn resolve_conflicts(&self, merge: &mut PlanMerge) -> Result<()> {
+        for change in &merge.merged_from_theirs {
+            println!("🔀 Took imported changes to {}", change);
+        }
+
+        while let Some(conflict) = merge.conflicts.first() {
+            println!("⚠️  Conflict in {}", conflict);
+            let side = Select::new()
+                .with_prompt("Which version should be kept?")
+                .items(&["Keep local", "Take imported"])
+                .default(0)
+                .interact()?;
+
+            let side = if side == 0 {
+                MergeSide::Ours
+            } else {
+                MergeSide::Theirs
+            };
+            merge.resolve(0, side)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct ImportTranscriptCommand {
+    pub storage: Box<dyn Storage>,
+    pub api: Box<dyn NusmodsApi>,
+    pub plan_id: String,
+}
+
+impl super::Command for ImportTranscriptCommand {
+    fn run(&self) -> Result<()> {
+        let mut plan = self
+            .storage
+            .get_plan(&self.plan_id)?
+            .ok_or_else(|| anyhow!("Plan not found"))?;
+
+        let mut student = self
+            .storage
+            .get_student(&plan.student_id)?
+            .ok_or_else(|| anyhow!("Student profile not found"))?;
+
+        let file_path: String = Input::new()
+            .with_prompt("Enter path to transcript file (CSV or text)")
+            .interact()?;
+
+        let path = Path::new(&file_path);
+        if !path.exists() {
+            return Err(anyhow!("File does not exist: {}", file_path));
+        }
+
+        let mut file = File::open(path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        let entries = transcript::parse_transcript(&contents)?;
+        if entries.is_empty() {
+            return Err(anyhow!("No modules found in transcript"));
+        }
+
+        let registry = self.api.get_module_registry()?;
+        super::reconcile_unit_totals(&mut plan, &registry);
+        let report = transcript::apply_transcript(&mut student, &mut plan, &entries, &registry);
+
+        self.storage.save_student(&student)?;
+        self.storage.save_plan(&plan)?;
+
+        println!(
+            "✅ Imported {} completed module(s) into {} ({} new semester(s))",
+            report.imported.len(),
+            plan.name,
+            report.semesters_created
+        );
+
+        if !report.failed.is_empty() {
+            println!("❌ Recorded as failed: {}", report.failed.join(", "));
+        }
+
+        if !report.missing_from_registry.is_empty() {
+            println!(
+                "⚠️ Not found in module registry: {}",
+                report.missing_from_registry.join(", ")
+            );
+        }
+
+        Ok(())
+    }
+}
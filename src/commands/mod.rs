@@ -1,17 +1,46 @@
+use crate::models::{ModuleRegistry, Plan};
+
+mod batch;
+mod branch;
 mod create;
+mod diff;
 mod edit;
 mod export;
 mod import;
+mod rollover;
+mod student;
+mod tui_edit;
 mod validate;
+mod versions;
 mod view;
 
-pub use create::CreateCommand;
+pub use batch::{parse_plan_args, plan_cli, BatchAction, BatchEditCommand, BatchRequest};
+pub use branch::{BranchAction, BranchCommand};
+pub use create::{
+    create_cli, parse_create_args, validate_student, BatchCreateCommand, CreateCommand,
+    CreateRequest, StudentSource,
+};
+pub use diff::{DiffCommand, PlanRef};
 pub use edit::EditCommand;
 pub use export::ExportCommand;
-pub use import::ImportCommand;
+pub use import::{ImportCommand, ImportTranscriptCommand};
+pub use rollover::{RolloverCommand, RolloverSummary};
+pub use student::{
+    parse_student_args, set_student_field, student_cli, StudentAction, StudentCommand, StudentField,
+};
+pub use tui_edit::{EditorAction, EditorFocus, TuiEditCommand, TuiEditorState};
 pub use validate::ValidateCommand;
-pub use view::ViewCommand;
+pub use versions::{VersionAction, VersionsCommand};
+pub use view::{BranchSummary, TimetableGrid, TimetableSlot, ViewCommand, TIMETABLE_DAYS};
 
 pub trait Command {
     fn run(&self) -> anyhow::Result<()>;
 }
+
+/// Repairs stale semester totals on a freshly loaded plan, telling the user
+/// what changed.
+pub(crate) fn reconcile_unit_totals(plan: &mut Plan, registry: &ModuleRegistry) {
+    for mismatch in plan.repair_unit_totals(registry) {
+        println!("⚠️  Corrected unit total for {}", mismatch);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::models::{ModuleStatus, Plan, Student};
+use crate::storage::Storage;
+use crate::transcript::is_passing_grade;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use dialoguer::{Confirm, Input};
+use std::collections::HashMap;
+
+pub struct RolloverCommand {
+    pub storage: Box<dyn Storage>,
+    pub plan_id: String,
+}
+
+#[derive(Debug, Default)]
+pub struct RolloverSummary {
+    pub completed: Vec<String>,
+    pub failed: Vec<String>,
+    pub started: Vec<String>,
+    pub new_semester: u8,
+}
+
+impl super::Command for RolloverCommand {
+    fn run(&self) -> Result<()> {
+        let mut plan = self
+            .storage
+            .get_plan(&self.plan_id)?
+            .ok_or_else(|| anyhow!("Plan not found"))?;
+
+        let mut student = self
+            .storage
+            .get_student(&plan.student_id)?
+            .ok_or_else(|| anyhow!("Student profile not found"))?;
+
+        let current_index = self.find_current_semester(&plan, &student);
+
+        match current_index {
+            Some(index) => println!(
+                "Current semester: {} Semester {}",
+                plan.semesters[index].year, plan.semesters[index].semester
+            ),
+            None => println!(
+                "⚠️ No semester in this plan matches your current semester; only your profile will be advanced"
+            ),
+        }
+
+        let proceed = Confirm::new()
+            .with_prompt(format!(
+                "Advance from semester {} to semester {}?",
+                student.current_semester,
+                student.current_semester.saturating_add(1)
+            ))
+            .default(true)
+            .interact()?;
+
+        if !proceed {
+            println!("Rollover cancelled");
+            return Ok(());
+        }
+
+        let mut grades = HashMap::new();
+        if let Some(index) = current_index {
+            for module in &plan.semesters[index].modules {
+                if module.status != ModuleStatus::Current {
+                    continue;
+                }
+
+                let grade: String = Input::new()
+                    .with_prompt(format!(
+                        "Enter grade for {} (leave empty if not yet released)",
+                        module.module_code
+                    ))
+                    .allow_empty(true)
+                    .interact()?;
+
+                let grade = if grade.is_empty() { None } else { Some(grade) };
+                grades.insert(module.module_code.clone(), grade);
+            }
+        }
+
+        let summary = self.apply_rollover(&mut student, &mut plan, current_index, &grades);
+
+        self.storage.save_student(&student)?;
+        self.storage.save_plan(&plan)?;
+
+        for code in &summary.completed {
+            println!("✅ Completed {}", code);
+        }
+        for code in &summary.failed {
+            println!(
+                "❌ Failed {} (use the edit command to schedule a retake)",
+                code
+            );
+        }
+        for code in &summary.started {
+            println!("🔄 Now taking {}", code);
+        }
+        println!("✅ Advanced to semester {}", summary.new_semester);
+
+        Ok(())
+    }
+}
+
+impl RolloverCommand {
+    /// Locates the plan semester the student is currently in, falling back to
+    /// the first semester that still holds modules marked as current.
+    pub fn find_current_semester(&self, plan: &Plan, student: &Student) -> Option<usize> {
+        student
+            .academic_term(student.current_semester)
+            .and_then(|(year, semester)| {
+                plan.semesters
+                    .iter()
+                    .position(|s| s.year == year && s.semester == semester)
+            })
+            .or_else(|| {
+                plan.semesters
+                    .iter()
+                    .position(|s| s.modules.iter().any(|m| m.status == ModuleStatus::Current))
+            })
+    }
+
+    /// Promotes current modules to completed (or failed, for an F or U
+    /// grade), starts the following semester's planned modules, advances the
+    /// student and syncs `completed_modules` with the plan.
+    pub fn apply_rollover(
+        &self,
+        student: &mut Student,
+        plan: &mut Plan,
+        current_index: Option<usize>,
+        grades: &HashMap<String, Option<String>>,
+    ) -> RolloverSummary {
+        let mut summary = RolloverSummary::default();
+
+        if let Some(index) = current_index {
+            for module in &mut plan.semesters[index].modules {
+                if module.status != ModuleStatus::Current {
+                    continue;
+                }
+
+                module.grade = grades.get(&module.module_code).cloned().flatten();
+
+                if module
+                    .grade
+                    .as_deref()
+                    .is_some_and(|g| !is_passing_grade(g))
+                {
+                    module.status = ModuleStatus::Failed;
+                    summary.failed.push(module.module_code.clone());
+                } else {
+                    module.status = ModuleStatus::Completed;
+                    summary.completed.push(module.module_code.clone());
+                }
+            }
+
+            if let Some(next) = plan.semesters.get_mut(index + 1) {
+                for module in &mut next.modules {
+                    if module.status == ModuleStatus::Planned {
+                        module.status = ModuleStatus::Current;
+                        summary.started.push(module.module_code.clone());
+                    }
+                }
+            }
+        }
+
+        student.current_semester = student.current_semester.saturating_add(1);
+        summary.new_semester = student.current_semester;
+
+        let completed = plan.completed_modules();
+        for semester in &plan.semesters {
+            for module in &semester.modules {
+                if module.status == ModuleStatus::Failed && !completed.contains(&module.module_code)
+                {
+                    student.completed_modules.remove(&module.module_code);
+                }
+            }
+        }
+        student.completed_modules.extend(completed);
+
+        plan.modified_at = Utc::now();
+
+        summary
+    }
+}
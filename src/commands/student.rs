@@ -0,0 +1,503 @@
+use crate::migrate::{assign_student_ids, StudentIdMigration};
+use crate::models::{validate_matriculation_year, CandidatureType, Plan, Student};
+use crate::storage::Storage;
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, ArgMatches};
+use dialoguer::{Input, Select};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// An editable `Student` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StudentField {
+    Name,
+    MatriculationYear,
+    Faculty,
+    Major,
+    SecondMajor,
+    Minors,
+    CompletedModules,
+    ExemptedModules,
+    AdvancedPlacementCredits,
+    CurrentSemester,
+    CandidatureType,
+}
+
+impl StudentField {
+    pub const ALL: [StudentField; 11] = [
+        StudentField::Name,
+        StudentField::MatriculationYear,
+        StudentField::Faculty,
+        StudentField::Major,
+        StudentField::SecondMajor,
+        StudentField::Minors,
+        StudentField::CompletedModules,
+        StudentField::ExemptedModules,
+        StudentField::AdvancedPlacementCredits,
+        StudentField::CurrentSemester,
+        StudentField::CandidatureType,
+    ];
+
+    /// Name used on the command line, e.g. `second-major`.
+    pub fn key(self) -> &'static str {
+        match self {
+            StudentField::Name => "name",
+            StudentField::MatriculationYear => "matriculation-year",
+            StudentField::Faculty => "faculty",
+            StudentField::Major => "major",
+            StudentField::SecondMajor => "second-major",
+            StudentField::Minors => "minors",
+            StudentField::CompletedModules => "completed",
+            StudentField::ExemptedModules => "exempted",
+            StudentField::AdvancedPlacementCredits => "ap-credits",
+            StudentField::CurrentSemester => "current-semester",
+            StudentField::CandidatureType => "candidature",
+        }
+    }
+
+    /// The field's current value, in the form `set_student_field` accepts.
+    pub fn value(self, student: &Student) -> String {
+        let sorted = |set: &HashSet<String>| {
+            let mut items: Vec<&str> = set.iter().map(String::as_str).collect();
+            items.sort_unstable();
+            items.join(", ")
+        };
+
+        match self {
+            StudentField::Name => student.name.clone(),
+            StudentField::MatriculationYear => student.matriculation_year.clone(),
+            StudentField::Faculty => student.faculty.clone(),
+            StudentField::Major => student.major.clone(),
+            StudentField::SecondMajor => student.second_major.clone().unwrap_or_default(),
+            StudentField::Minors => student.minors.join(", "),
+            StudentField::CompletedModules => sorted(&student.completed_modules),
+            StudentField::ExemptedModules => sorted(&student.exempted_modules),
+            StudentField::AdvancedPlacementCredits => {
+                student.advanced_placement_credits.to_string()
+            }
+            StudentField::CurrentSemester => student.current_semester.to_string(),
+            StudentField::CandidatureType => student.candidature_type.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for StudentField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+impl FromStr for StudentField {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let key = s.trim().to_lowercase().replace('_', "-");
+        StudentField::ALL
+            .into_iter()
+            .find(|field| field.key() == key)
+            .ok_or_else(|| {
+                let keys: Vec<&str> = StudentField::ALL.iter().map(|f| f.key()).collect();
+                anyhow!(
+                    "Unknown student field '{}' (expected one of: {})",
+                    s.trim(),
+                    keys.join(", ")
+                )
+            })
+    }
+}
+
+/// Sets one field from text. Lists are comma separated; an empty value
+/// clears optional fields and lists.
+pub fn set_student_field(student: &mut Student, field: StudentField, value: &str) -> Result<()> {
+    let value = value.trim();
+    let list = || -> Vec<String> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let codes = || -> HashSet<String> { list().iter().map(|c| c.to_uppercase()).collect() };
+    let required = || {
+        if value.is_empty() {
+            Err(anyhow!("{} must not be empty", field))
+        } else {
+            Ok(value.to_string())
+        }
+    };
+
+    match field {
+        StudentField::Name => student.name = required()?,
+        StudentField::MatriculationYear => {
+            validate_matriculation_year(value)?;
+            student.matriculation_year = value.to_string();
+        }
+        StudentField::Faculty => student.faculty = required()?,
+        StudentField::Major => student.major = required()?,
+        StudentField::SecondMajor => {
+            student.second_major = Some(value.to_string()).filter(|v| !v.is_empty())
+        }
+        StudentField::Minors => student.minors = list(),
+        StudentField::CompletedModules => student.completed_modules = codes(),
+        StudentField::ExemptedModules => student.exempted_modules = codes(),
+        StudentField::AdvancedPlacementCredits => {
+            student.advanced_placement_credits = value
+                .parse()
+                .map_err(|_| anyhow!("Invalid unit count '{}'", value))?
+        }
+        StudentField::CurrentSemester => {
+            let semester: u8 = value
+                .parse()
+                .map_err(|_| anyhow!("Invalid semester '{}'", value))?;
+            let max_semesters = student.candidature_type.rules().max_semesters;
+            if semester == 0 || semester > max_semesters {
+                return Err(anyhow!(
+                    "Current semester must be between 1 and {}",
+                    max_semesters
+                ));
+            }
+            student.current_semester = semester;
+        }
+        StudentField::CandidatureType => {
+            student.candidature_type = value.parse::<CandidatureType>()?
+        }
+    }
+
+    Ok(())
+}
+
+/// Looks a profile up by ID, falling back to its name. Names need not be
+/// unique, so a name shared by several profiles is an error.
+pub(crate) fn find_student(storage: &dyn Storage, key: &str) -> Result<Student> {
+    if let Some(student) = storage.get_student(key)? {
+        return Ok(student);
+    }
+
+    let mut matches: Vec<Student> = storage
+        .list_students()?
+        .into_iter()
+        .filter(|s| s.name == key)
+        .collect();
+    match matches.len() {
+        0 => Err(anyhow!("Student profile not found: {}", key)),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            let ids: Vec<&str> = matches.iter().map(|s| s.id.as_str()).collect();
+            Err(anyhow!(
+                "Several student profiles are named {}; use an ID instead: {}",
+                key,
+                ids.join(", ")
+            ))
+        }
+    }
+}
+
+/// Runs `assign_student_ids` over storage. Legacy profiles are re-saved
+/// under their ID and removed from under their name.
+pub(crate) fn migrate_student_ids(storage: &dyn Storage) -> Result<StudentIdMigration> {
+    let mut students = storage.list_students()?;
+    let mut plans = storage.list_plans()?;
+    let migration = assign_student_ids(&mut students, &mut plans);
+
+    for (name, id) in &migration.assigned {
+        if let Some(student) = students.iter().find(|s| s.id == *id) {
+            storage.save_student(student)?;
+            storage.delete_student(name)?;
+        }
+    }
+    for plan in plans.iter().filter(|p| migration.relinked.contains(&p.id)) {
+        storage.save_plan(plan)?;
+    }
+
+    Ok(migration)
+}
+
+/// Returns the profile as stored after giving it an ID, migrating storage
+/// first if it predates IDs, so plans are never linked to an empty ID.
+pub(crate) fn ensure_student_id(storage: &dyn Storage, student: Student) -> Result<Student> {
+    if !student.id.is_empty() {
+        return Ok(student);
+    }
+
+    // Legacy profiles are keyed by name, so the name picks out one of them
+    let migration = migrate_student_ids(storage)?;
+    let (_, id) = migration
+        .assigned
+        .iter()
+        .find(|(name, _)| *name == student.name)
+        .ok_or_else(|| anyhow!("Student profile not found: {}", student.name))?;
+    println!(
+        "⚠️  Gave legacy student profiles IDs; {} is now {}",
+        student.name, id
+    );
+
+    storage
+        .get_student(id)?
+        .ok_or_else(|| anyhow!("Student profile not found: {}", id))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StudentAction {
+    List,
+    Show(String),
+    /// Applies the given field changes, or opens an interactive editor when
+    /// there are none.
+    Edit {
+        /// Name or ID of the profile.
+        student: String,
+        changes: Vec<(StudentField, String)>,
+    },
+    /// Deletes a profile. Plans referring to it block the delete unless
+    /// `cascade` is set, in which case they are deleted too.
+    Delete {
+        student: String,
+        cascade: bool,
+    },
+    /// Runs `assign_student_ids` over storage.
+    MigrateIds,
+}
+
+/// Lists, shows, edits and deletes student profiles.
+pub struct StudentCommand {
+    pub storage: Box<dyn Storage>,
+    pub action: StudentAction,
+}
+
+impl super::Command for StudentCommand {
+    fn run(&self) -> Result<()> {
+        match &self.action {
+            StudentAction::List => {
+                let mut students = self.storage.list_students()?;
+                if students.is_empty() {
+                    println!("No student profiles found.");
+                }
+                students.sort_by(|a, b| a.name.cmp(&b.name));
+                for student in students {
+                    println!(
+                        "{} — {} ({}), semester {} [{}]",
+                        student.name,
+                        student.major,
+                        student.matriculation_year,
+                        student.current_semester,
+                        student.id
+                    );
+                }
+            }
+            StudentAction::Show(key) => {
+                let student = find_student(self.storage.as_ref(), key)?;
+                println!("{:<20} {}", "id:", student.id);
+                for field in StudentField::ALL {
+                    println!("{:<20} {}", format!("{}:", field), field.value(&student));
+                }
+            }
+            StudentAction::Edit { student, changes } => {
+                let changes = if changes.is_empty() {
+                    self.prompt_changes(&find_student(self.storage.as_ref(), student)?)?
+                } else {
+                    changes.clone()
+                };
+                let student = self.edit(student, &changes)?;
+                println!("✅ Updated student profile: {}", student.name);
+            }
+            StudentAction::Delete { student, cascade } => {
+                let deleted_plans = self.delete(student, *cascade)?;
+                println!("✅ Deleted student profile: {}", student);
+                if !deleted_plans.is_empty() {
+                    println!("🗑️  Deleted {} plan(s)", deleted_plans.len());
+                }
+            }
+            StudentAction::MigrateIds => {
+                let migration = self.migrate_ids()?;
+                if migration.is_empty() {
+                    println!("✅ Every student profile already has an ID");
+                }
+                for (name, id) in &migration.assigned {
+                    println!("✅ {} is now {}", name, id);
+                }
+                if !migration.relinked.is_empty() {
+                    println!("✅ Relinked {} plan(s)", migration.relinked.len());
+                }
+                for plan_id in &migration.ambiguous {
+                    println!(
+                        "⚠️  Plan {} names a student shared by several profiles; set its student manually",
+                        plan_id
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl StudentCommand {
+    /// Applies field changes and saves the profile. Plans refer to the
+    /// student by ID, so renaming needs no further updates.
+    pub fn edit(&self, key: &str, changes: &[(StudentField, String)]) -> Result<Student> {
+        let mut student = find_student(self.storage.as_ref(), key)?;
+        for (field, value) in changes {
+            set_student_field(&mut student, *field, value)?;
+        }
+        self.storage.save_student(&student)?;
+
+        Ok(student)
+    }
+
+    /// Deletes a profile, returning the IDs of any plans deleted with it.
+    pub fn delete(&self, key: &str, cascade: bool) -> Result<Vec<String>> {
+        let student = find_student(self.storage.as_ref(), key)?;
+
+        let plans: Vec<Plan> = self
+            .storage
+            .list_plans()?
+            .into_iter()
+            .filter(|p| p.student_id == student.id)
+            .collect();
+        if !plans.is_empty() && !cascade {
+            let names: Vec<&str> = plans.iter().map(|p| p.name.as_str()).collect();
+            return Err(anyhow!(
+                "Student {} has {} plan(s): {}. Delete them too with --cascade",
+                student.name,
+                plans.len(),
+                names.join(", ")
+            ));
+        }
+
+        let mut deleted = Vec::new();
+        for plan in plans {
+            self.storage.delete_plan(&plan.id)?;
+            deleted.push(plan.id);
+        }
+        self.storage.delete_student(&student.id)?;
+
+        Ok(deleted)
+    }
+
+    /// Gives legacy profiles IDs and points plans that name them at those
+    /// IDs.
+    pub fn migrate_ids(&self) -> Result<StudentIdMigration> {
+        migrate_student_ids(self.storage.as_ref())
+    }
+
+    fn prompt_changes(&self, student: &Student) -> Result<Vec<(StudentField, String)>> {
+        let mut working = student.clone();
+        let mut changes = Vec::new();
+
+        loop {
+            let mut options: Vec<String> = StudentField::ALL
+                .iter()
+                .map(|field| format!("{}: {}", field, field.value(&working)))
+                .collect();
+            options.push("Save and exit".to_string());
+
+            let selection = Select::new()
+                .with_prompt("Choose a field to edit")
+                .items(&options)
+                .default(0)
+                .interact()?;
+            let Some(&field) = StudentField::ALL.get(selection) else {
+                return Ok(changes);
+            };
+
+            let value: String = Input::new()
+                .with_prompt(format!("New {}", field))
+                .with_initial_text(field.value(&working))
+                .allow_empty(true)
+                .interact()?;
+
+            match set_student_field(&mut working, field, &value) {
+                Ok(()) => changes.push((field, value)),
+                Err(error) => println!("❌ {}", error),
+            }
+        }
+    }
+}
+
+pub fn student_cli() -> clap::Command {
+    let student = || {
+        Arg::new("student")
+            .required(true)
+            .help("Student name or ID")
+    };
+
+    clap::Command::new("student")
+        .about("Manage student profiles")
+        .subcommand_required(true)
+        .subcommand(clap::Command::new("list").about("List student profiles"))
+        .subcommand(
+            clap::Command::new("show")
+                .about("Show a student profile")
+                .arg(student()),
+        )
+        .subcommand(
+            clap::Command::new("edit")
+                .about("Edit a student profile (interactive without --set)")
+                .arg(student())
+                .arg(
+                    Arg::new("set")
+                        .long("set")
+                        .action(ArgAction::Append)
+                        .help("field=value, e.g. --set current-semester=5"),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("delete")
+                .about("Delete a student profile")
+                .arg(student())
+                .arg(
+                    Arg::new("cascade")
+                        .long("cascade")
+                        .action(ArgAction::SetTrue)
+                        .help("Also delete the student's plans"),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("migrate-ids")
+                .about("Give legacy profiles IDs and relink their plans"),
+        )
+}
+
+/// Parses `student <subcommand> ...` arguments, including the leading
+/// `student`.
+pub fn parse_student_args<I, T>(args: I) -> Result<StudentAction>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let matches = student_cli().try_get_matches_from(args)?;
+    let (subcommand, sub) = matches
+        .subcommand()
+        .ok_or_else(|| anyhow!("Missing student subcommand"))?;
+    let student = |sub: &ArgMatches| {
+        sub.get_one::<String>("student")
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    Ok(match subcommand {
+        "list" => StudentAction::List,
+        "show" => StudentAction::Show(student(sub)),
+        "edit" => StudentAction::Edit {
+            student: student(sub),
+            changes: sub
+                .get_many::<String>("set")
+                .into_iter()
+                .flatten()
+                .map(|assignment| {
+                    let (field, value) = assignment
+                        .split_once('=')
+                        .ok_or_else(|| anyhow!("Expected field=value, got '{}'", assignment))?;
+                    Ok((field.parse()?, value.to_string()))
+                })
+                .collect::<Result<_>>()?,
+        },
+        "delete" => StudentAction::Delete {
+            student: student(sub),
+            cascade: sub.get_flag("cascade"),
+        },
+        "migrate-ids" => StudentAction::MigrateIds,
+        other => return Err(anyhow!("Unknown student subcommand '{}'", other)),
+    })
+}
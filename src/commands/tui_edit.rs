@@ -0,0 +1,574 @@
+use crate::api::NusmodsApi;
+use crate::history;
+use crate::models::{Module, ModuleRegistry, ModuleStatus, Plan, Student};
+use crate::plan_ops::{self, PlanOperation};
+use crate::storage::Storage;
+use crate::validation::{
+    availability, candidature, prerequisites, workload, ValidationLevel, ValidationResult,
+};
+use anyhow::{anyhow, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use std::io;
+use tui::backend::{Backend, CrosstermBackend};
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use tui::{Frame, Terminal};
+
+pub struct TuiEditCommand {
+    pub storage: Box<dyn Storage>,
+    pub api: Box<dyn NusmodsApi>,
+    pub plan_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorFocus {
+    Semesters,
+    Search,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorAction {
+    Continue,
+    Save,
+    Quit,
+}
+
+/// Editor state, kept separate from rendering so key handling can be tested
+/// without a terminal.
+pub struct TuiEditorState {
+    pub plan: Plan,
+    pub student: Option<Student>,
+    pub focus: EditorFocus,
+    pub selected_semester: usize,
+    pub selected_module: usize,
+    /// Module being carried between semesters: (semester index, module index).
+    pub carrying: Option<(usize, usize)>,
+    pub search_query: String,
+    pub search_results: Vec<Module>,
+    pub selected_result: usize,
+    pub validation: ValidationResult,
+    pub status: String,
+    pub dirty: bool,
+    confirm_quit: bool,
+}
+
+impl super::Command for TuiEditCommand {
+    fn run(&self) -> Result<()> {
+        let mut plan = self
+            .storage
+            .get_plan(&self.plan_id)?
+            .ok_or_else(|| anyhow!("Plan not found"))?;
+
+        if plan.semesters.is_empty() {
+            return Err(anyhow!("Plan has no semesters to edit"));
+        }
+
+        let student = self.storage.get_student(&plan.student_id)?;
+        let registry = self.api.get_module_registry()?;
+        super::reconcile_unit_totals(&mut plan, &registry);
+
+        let mut state = TuiEditorState::new(plan, student);
+        state.revalidate(&registry);
+
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+        let outcome = self.event_loop(&mut terminal, &mut state, &registry);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        outcome?;
+
+        if state.dirty {
+            println!("⚠️ Exited without saving the latest changes");
+        } else {
+            println!("✅ Plan updated successfully");
+        }
+
+        Ok(())
+    }
+}
+
+impl TuiEditCommand {
+    fn event_loop<B: Backend>(
+        &self,
+        terminal: &mut Terminal<B>,
+        state: &mut TuiEditorState,
+        registry: &ModuleRegistry,
+    ) -> Result<()> {
+        loop {
+            terminal.draw(|f| draw(f, state))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+
+            match state.handle_key(key, self.api.as_ref(), registry)? {
+                EditorAction::Continue => {}
+                EditorAction::Save => {
+                    state.plan.modified_at = chrono::Utc::now();
+                    self.storage.save_plan(&state.plan)?;
+                    state.dirty = false;
+                    state.status = "Plan saved".to_string();
+                }
+                EditorAction::Quit => return Ok(()),
+            }
+        }
+    }
+}
+
+impl TuiEditorState {
+    pub fn new(plan: Plan, student: Option<Student>) -> Self {
+        Self {
+            plan,
+            student,
+            focus: EditorFocus::Semesters,
+            selected_semester: 0,
+            selected_module: 0,
+            carrying: None,
+            search_query: String::new(),
+            search_results: Vec::new(),
+            selected_result: 0,
+            validation: ValidationResult::new(),
+            status: "Tab: switch pane  Space: pick up/drop  d: remove  s: save  q: quit"
+                .to_string(),
+            dirty: false,
+            confirm_quit: false,
+        }
+    }
+
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        api: &dyn NusmodsApi,
+        registry: &ModuleRegistry,
+    ) -> Result<EditorAction> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Ok(match key.code {
+                KeyCode::Char('s') => EditorAction::Save,
+                KeyCode::Char('q') | KeyCode::Char('c') => self.request_quit(),
+                _ => EditorAction::Continue,
+            });
+        }
+
+        if key.code == KeyCode::Tab {
+            self.focus = match self.focus {
+                EditorFocus::Semesters => EditorFocus::Search,
+                EditorFocus::Search => EditorFocus::Semesters,
+            };
+            return Ok(EditorAction::Continue);
+        }
+
+        match self.focus {
+            EditorFocus::Semesters => Ok(self.handle_semester_key(key.code, registry)),
+            EditorFocus::Search => {
+                self.handle_search_key(key.code, api, registry)?;
+                Ok(EditorAction::Continue)
+            }
+        }
+    }
+
+    fn handle_semester_key(&mut self, code: KeyCode, registry: &ModuleRegistry) -> EditorAction {
+        if code != KeyCode::Char('q') {
+            self.confirm_quit = false;
+        }
+
+        match code {
+            KeyCode::Left => self.step_semester(-1, registry),
+            KeyCode::Right => self.step_semester(1, registry),
+            KeyCode::Up if self.carrying.is_none() => {
+                self.selected_module = self.selected_module.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                let count = self.plan.semesters[self.selected_semester].modules.len();
+                if self.carrying.is_none() && self.selected_module + 1 < count {
+                    self.selected_module += 1;
+                }
+            }
+            KeyCode::Char(' ') => self.toggle_carry(),
+            KeyCode::Esc if self.carrying.is_some() => {
+                self.carrying = None;
+                self.status = "Move finished".to_string();
+            }
+            KeyCode::Char('d') | KeyCode::Delete => self.remove_selected(registry),
+            KeyCode::Char('s') => return EditorAction::Save,
+            KeyCode::Char('q') => return self.request_quit(),
+            _ => {}
+        }
+
+        EditorAction::Continue
+    }
+
+    fn handle_search_key(
+        &mut self,
+        code: KeyCode,
+        api: &dyn NusmodsApi,
+        registry: &ModuleRegistry,
+    ) -> Result<()> {
+        match code {
+            KeyCode::Char(c) => {
+                self.search_query.push(c);
+                self.refresh_search(api)?;
+            }
+            KeyCode::Backspace => {
+                self.search_query.pop();
+                self.refresh_search(api)?;
+            }
+            KeyCode::Up => self.selected_result = self.selected_result.saturating_sub(1),
+            KeyCode::Down if self.selected_result + 1 < self.search_results.len() => {
+                self.selected_result += 1;
+            }
+            KeyCode::Enter => self.add_selected_result(registry),
+            KeyCode::Esc => self.focus = EditorFocus::Semesters,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn refresh_search(&mut self, api: &dyn NusmodsApi) -> Result<()> {
+        self.search_results = if self.search_query.is_empty() {
+            Vec::new()
+        } else {
+            api.search_modules(&self.search_query.to_uppercase())?
+        };
+        self.selected_result = 0;
+        Ok(())
+    }
+
+    fn request_quit(&mut self) -> EditorAction {
+        if self.dirty && !self.confirm_quit {
+            self.confirm_quit = true;
+            self.status = "Unsaved changes: press q again to discard, s to save".to_string();
+            return EditorAction::Continue;
+        }
+        EditorAction::Quit
+    }
+
+    fn step_semester(&mut self, delta: isize, registry: &ModuleRegistry) {
+        let target = self.selected_semester as isize + delta;
+        if target < 0 || target as usize >= self.plan.semesters.len() {
+            return;
+        }
+        let target = target as usize;
+
+        if let Some((source, index)) = self.carrying {
+            let module_code = self.plan.semesters[source].modules[index]
+                .module_code
+                .clone();
+            let operation = PlanOperation::Move {
+                from: source,
+                to: target,
+                module_codes: vec![module_code],
+            };
+            if !self.apply(&operation, registry) {
+                return;
+            }
+
+            let new_index = self.plan.semesters[target].modules.len() - 1;
+            self.carrying = Some((target, new_index));
+            self.selected_module = new_index;
+        } else {
+            self.selected_module = 0;
+        }
+
+        self.selected_semester = target;
+    }
+
+    fn toggle_carry(&mut self) {
+        if self.carrying.take().is_some() {
+            self.status = "Module dropped".to_string();
+            return;
+        }
+
+        let semester = &self.plan.semesters[self.selected_semester];
+        if let Some(module) = semester.modules.get(self.selected_module) {
+            self.status = format!(
+                "Moving {}: use ←/→ to change semester, Space to drop",
+                module.module_code
+            );
+            self.carrying = Some((self.selected_semester, self.selected_module));
+        }
+    }
+
+    fn remove_selected(&mut self, registry: &ModuleRegistry) {
+        if self.carrying.is_some() {
+            return;
+        }
+
+        let semester = &self.plan.semesters[self.selected_semester];
+        let Some(module) = semester.modules.get(self.selected_module) else {
+            return;
+        };
+        let module_code = module.module_code.clone();
+
+        let operation = PlanOperation::Remove {
+            semester: self.selected_semester,
+            module_codes: vec![module_code.clone()],
+        };
+        if !self.apply(&operation, registry) {
+            return;
+        }
+
+        self.selected_module = self.selected_module.min(
+            self.plan.semesters[self.selected_semester]
+                .modules
+                .len()
+                .saturating_sub(1),
+        );
+        self.status = format!("Removed {}", module_code);
+    }
+
+    fn add_selected_result(&mut self, registry: &ModuleRegistry) {
+        let Some(module) = self.search_results.get(self.selected_result) else {
+            return;
+        };
+        let module_code = module.module_code.clone();
+
+        let operation = PlanOperation::Add {
+            semester: self.selected_semester,
+            module_codes: vec![module_code.clone()],
+        };
+        if !self.apply(&operation, registry) {
+            return;
+        }
+
+        let semester = &self.plan.semesters[self.selected_semester];
+        self.status = format!(
+            "Added {} to {} Semester {}",
+            module_code, semester.year, semester.semester
+        );
+    }
+
+    /// Applies an edit through `plan_ops` and logs it in the plan's edit
+    /// history, so `plan undo` can revert it. Errors go to the status line.
+    fn apply(&mut self, operation: &PlanOperation, registry: &ModuleRegistry) -> bool {
+        match plan_ops::apply(&mut self.plan, registry, operation) {
+            Ok(result) => {
+                history::record(&mut self.plan, result.changes);
+                self.mark_changed(registry);
+                true
+            }
+            Err(error) => {
+                self.status = error.to_string();
+                false
+            }
+        }
+    }
+
+    fn mark_changed(&mut self, registry: &ModuleRegistry) {
+        self.dirty = true;
+        self.confirm_quit = false;
+        self.revalidate(registry);
+    }
+
+    /// Re-runs the plan checks that feed the validation panel.
+    pub fn revalidate(&mut self, registry: &ModuleRegistry) {
+        let mut result = ValidationResult::new();
+
+        if let Err(error) = self.run_checks(registry, &mut result) {
+            self.status = format!("Validation failed: {}", error);
+        }
+
+        self.validation = result;
+    }
+
+    fn run_checks(&self, registry: &ModuleRegistry, result: &mut ValidationResult) -> Result<()> {
+        if let Some(student) = &self.student {
+            prerequisites::validate_prerequisites(&self.plan, registry, student, result)?;
+            candidature::validate_candidature(&self.plan, student, result)?;
+        }
+        workload::validate_workload(&self.plan, registry, result)?;
+        availability::validate_availability(&self.plan, registry, result)?;
+
+        Ok(())
+    }
+}
+
+fn draw<B: Backend>(f: &mut Frame<B>, state: &TuiEditorState) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(60),
+            Constraint::Min(6),
+            Constraint::Length(1),
+        ])
+        .split(f.size());
+
+    draw_semesters(f, state, rows[0]);
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[1]);
+
+    draw_search(f, state, bottom[0]);
+    draw_validation(f, state, bottom[1]);
+
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            state.status.clone(),
+            Style::default().fg(Color::Gray),
+        )),
+        rows[2],
+    );
+}
+
+fn draw_semesters<B: Backend>(f: &mut Frame<B>, state: &TuiEditorState, area: Rect) {
+    let count = state.plan.semesters.len() as u32;
+    let constraints: Vec<Constraint> = (0..count).map(|_| Constraint::Ratio(1, count)).collect();
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(area);
+
+    let focused = state.focus == EditorFocus::Semesters;
+
+    for (i, semester) in state.plan.semesters.iter().enumerate() {
+        let has_errors =
+            state.validation.issues.iter().any(|issue| {
+                issue.semester_index == Some(i) && issue.level == ValidationLevel::Error
+            });
+
+        let items: Vec<ListItem> = semester
+            .modules
+            .iter()
+            .enumerate()
+            .map(|(j, m)| {
+                let style = match m.status {
+                    ModuleStatus::Planned => Style::default(),
+                    ModuleStatus::Current => Style::default().fg(Color::Blue),
+                    ModuleStatus::Completed => Style::default().fg(Color::Green),
+                    ModuleStatus::Failed => Style::default().fg(Color::Red),
+                };
+                let marker = if state.carrying == Some((i, j)) {
+                    "» "
+                } else {
+                    ""
+                };
+                ListItem::new(Spans::from(Span::styled(
+                    format!("{}{}", marker, m.module_code),
+                    style,
+                )))
+            })
+            .collect();
+
+        let border_style = if focused && i == state.selected_semester {
+            Style::default().fg(Color::Yellow)
+        } else if has_errors {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        };
+
+        let title = format!(
+            "{} S{} ({}u)",
+            semester.year, semester.semester, semester.total_units
+        );
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title(title),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        let mut list_state = ListState::default();
+        if focused && i == state.selected_semester && !semester.modules.is_empty() {
+            list_state.select(Some(state.selected_module));
+        }
+
+        f.render_stateful_widget(list, columns[i], &mut list_state);
+    }
+}
+
+fn draw_search<B: Backend>(f: &mut Frame<B>, state: &TuiEditorState, area: Rect) {
+    let focused = state.focus == EditorFocus::Search;
+
+    let items: Vec<ListItem> = state
+        .search_results
+        .iter()
+        .map(|m| {
+            ListItem::new(format!(
+                "{}: {} ({}u)",
+                m.module_code, m.title, m.module_credit
+            ))
+        })
+        .collect();
+
+    let title = format!(
+        "Search: {}{}",
+        state.search_query,
+        if focused { "_" } else { "" }
+    );
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(if focused {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                })
+                .title(title),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut list_state = ListState::default();
+    if focused && !state.search_results.is_empty() {
+        list_state.select(Some(state.selected_result));
+    }
+
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn draw_validation<B: Backend>(f: &mut Frame<B>, state: &TuiEditorState, area: Rect) {
+    let items: Vec<ListItem> = if state.validation.issues.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No issues found",
+            Style::default().fg(Color::Green),
+        ))]
+    } else {
+        state
+            .validation
+            .issues
+            .iter()
+            .map(|issue| {
+                let (label, color) = match issue.level {
+                    ValidationLevel::Error => ("ERROR", Color::Red),
+                    ValidationLevel::Warning => ("WARN ", Color::Yellow),
+                    ValidationLevel::Info => ("INFO ", Color::Blue),
+                };
+                let location = issue
+                    .semester_index
+                    .and_then(|i| state.plan.semesters.get(i))
+                    .map(|s| format!(" [{} S{}]", s.year, s.semester))
+                    .unwrap_or_default();
+
+                ListItem::new(Spans::from(vec![
+                    Span::styled(label, Style::default().fg(color)),
+                    Span::raw(format!(" {}{}", issue.message, location)),
+                ]))
+            })
+            .collect()
+    };
+
+    f.render_widget(
+        List::new(items).block(Block::default().borders(Borders::ALL).title("Validation")),
+        area,
+    );
+}
@@ -2,7 +2,10 @@
 use crate::api::NusmodsApi;
 use crate::models::Plan;
 use crate::storage::Storage;
-use crate::validation::{availability, prerequisites, workload, ValidationLevel, ValidationResult};
+use crate::validation::{
+    availability, candidature, critical_path, graduation, prerequisites, workload, ValidationLevel,
+    ValidationResult,
+};
 use anyhow::{anyhow, Result};
 use colored::Colorize;
 
@@ -15,7 +18,7 @@ pub struct ValidateCommand {
 impl super::Command for ValidateCommand {
     fn run(&self) -> Result<()> {
         // Load the plan
-        let plan = self
+        let mut plan = self
             .storage
             .get_plan(&self.plan_id)?
             .ok_or_else(|| anyhow!("Plan not found"))?;
@@ -28,9 +31,10 @@ impl super::Command for ValidateCommand {
 
         // Load module registry
         let registry = self.api.get_module_registry()?;
+        super::reconcile_unit_totals(&mut plan, &registry);
 
         // Load curriculum
-        let _curriculum = self
+        let curriculum = self
             .storage
             .get_curriculum(&student.major)?
             .ok_or_else(|| anyhow!("Curriculum not found"))?;
@@ -49,8 +53,14 @@ impl super::Command for ValidateCommand {
         // Module availability validation
         availability::validate_availability(&plan, &registry, &mut result)?;
 
+        // Candidature validation
+        candidature::validate_candidature(&plan, &student, &mut result)?;
+
         // Graduation requirements validation
-        // (Will be more complex in a full implementation)
+        graduation::validate_units(&plan, &curriculum, &student, &registry, &mut result)?;
+
+        // Critical path validation
+        critical_path::validate_critical_path(&plan, &registry, &student, &mut result)?;
 
         // Display validation results
         self.display_validation_results(&result, &plan);
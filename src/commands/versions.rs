@@ -0,0 +1,101 @@
+use crate::models::{Plan, PlanVersion, RetentionPolicy};
+use crate::storage::Storage;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionAction {
+    List,
+    View(u32),
+    /// Makes an old version current again. The restore is saved as a new
+    /// version, so nothing in the history is lost.
+    Restore(u32),
+    Prune(RetentionPolicy),
+}
+
+/// Lists, shows, restores and prunes the saved versions of a plan.
+pub struct VersionsCommand {
+    pub storage: Box<dyn Storage>,
+    pub plan_id: String,
+    pub action: VersionAction,
+}
+
+impl super::Command for VersionsCommand {
+    fn run(&self) -> Result<()> {
+        match &self.action {
+            VersionAction::List => {
+                let versions = self.versions()?;
+                if versions.is_empty() {
+                    println!("No saved versions for plan {}", self.plan_id);
+                }
+                for version in versions {
+                    println!("{}", version);
+                }
+            }
+            VersionAction::View(number) => {
+                let version = self.version(*number)?;
+                println!("📜 {}", version);
+                display_plan(&version.plan);
+            }
+            VersionAction::Restore(number) => {
+                let plan = self.restore(*number)?;
+                println!("✅ Restored plan '{}' to version {}", plan.name, number);
+            }
+            VersionAction::Prune(policy) => {
+                let pruned = self.storage.prune_plan_versions(&self.plan_id, policy)?;
+                println!("🗑️  Removed {} old version(s)", pruned.len());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl VersionsCommand {
+    /// Saved versions of the plan, newest first.
+    pub fn versions(&self) -> Result<Vec<PlanVersion>> {
+        let mut versions = self.storage.list_plan_versions(&self.plan_id)?;
+        versions.reverse();
+        Ok(versions)
+    }
+
+    pub fn version(&self, number: u32) -> Result<PlanVersion> {
+        self.storage
+            .get_plan_version(&self.plan_id, number)?
+            .ok_or_else(|| anyhow!("Version {} of plan {} not found", number, self.plan_id))
+    }
+
+    /// Saves the given version as the current plan and returns it.
+    pub fn restore(&self, number: u32) -> Result<Plan> {
+        let mut plan = self.version(number)?.plan;
+        plan.modified_at = Utc::now();
+        self.storage.save_plan(&plan)?;
+        Ok(plan)
+    }
+}
+
+fn display_plan(plan: &Plan) {
+    println!(
+        "{} (Target graduation: {})",
+        plan.name, plan.target_graduation
+    );
+
+    for semester in &plan.semesters {
+        let modules: Vec<&str> = semester
+            .modules
+            .iter()
+            .map(|m| m.module_code.as_str())
+            .collect();
+        println!(
+            "  {} Semester {} ({} units): {}",
+            semester.year,
+            semester.semester,
+            semester.total_units,
+            if modules.is_empty() {
+                "-".to_string()
+            } else {
+                modules.join(", ")
+            }
+        );
+    }
+}
@@ -1,9 +1,14 @@
 use crate::api::NusmodsApi;
-use crate::models::{ModuleRegistry, ModuleStatus, Plan};
+use crate::critical_path::analyse_critical_path;
+use crate::graph;
+use crate::models::{
+    ExemptionPolicy, Module, ModuleRegistry, ModuleStatus, Plan, SemesterPlan, Student, Units,
+};
 use crate::storage::Storage;
 use anyhow::{anyhow, Result};
-use colored::Colorize;
-use dialoguer::Select;
+use colored::{Color, Colorize};
+use dialoguer::{Input, Select};
+use std::collections::{HashMap, HashSet};
 
 pub struct ViewCommand {
     pub storage: Box<dyn Storage>,
@@ -11,9 +16,84 @@ pub struct ViewCommand {
     pub plan_id: Option<String>,
 }
 
+pub const TIMETABLE_DAYS: [&str; 6] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const TIMETABLE_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Blue,
+    Color::BrightWhite,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimetableSlot {
+    pub module_code: String,
+    pub lesson_type: String,
+    pub class_no: String,
+    pub venue: String,
+}
+
+/// Side-by-side figures for one branch in the branch comparison view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchSummary {
+    pub branch_name: String,
+    pub primary: bool,
+    /// Units per semester, keyed by "year Semester n".
+    pub semester_units: Vec<(String, Units)>,
+    pub total_units: Units,
+    /// Units counted towards the degree (including exemptions) and the
+    /// units required, when the student's curriculum is known.
+    pub requirement_progress: Option<(Units, Units)>,
+    pub projected_cap: Option<f32>,
+}
+
+/// Hour-by-hour lesson layout for one semester, keyed by (day index, hour).
+#[derive(Debug, Default)]
+pub struct TimetableGrid {
+    pub start_hour: u8,
+    pub end_hour: u8,
+    pub cells: HashMap<(usize, u8), Vec<TimetableSlot>>,
+    /// Modules in the semester with no timetable data for it.
+    pub unscheduled: Vec<String>,
+}
+
+impl TimetableGrid {
+    pub fn slots_at(&self, day: usize, hour: u8) -> &[TimetableSlot] {
+        self.cells
+            .get(&(day, hour))
+            .map(|slots| slots.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Cells where lessons from more than one module overlap, in time order.
+    pub fn clashes(&self) -> Vec<(usize, u8)> {
+        let mut clashes: Vec<(usize, u8)> = self
+            .cells
+            .iter()
+            .filter(|(_, slots)| {
+                slots
+                    .iter()
+                    .any(|slot| slot.module_code != slots[0].module_code)
+            })
+            .map(|(&key, _)| key)
+            .collect();
+        clashes.sort_unstable();
+        clashes
+    }
+}
+
 impl super::Command for ViewCommand {
     fn run(&self) -> Result<()> {
-        let plan = if let Some(id) = &self.plan_id {
+        let mut plan = if let Some(id) = &self.plan_id {
             self.storage
                 .get_plan(id)?
                 .ok_or_else(|| anyhow!("Plan not found"))?
@@ -39,11 +119,18 @@ impl super::Command for ViewCommand {
             plans[plan_index].clone()
         };
 
+        let registry = self.api.get_module_registry()?;
+        super::reconcile_unit_totals(&mut plan, &registry);
+
         let view_modes = vec![
             "Summary View",
             "Detailed View",
             "Curriculum Progress View",
             "Module List View",
+            "Timetable View",
+            "Prerequisite Graph View",
+            "Module Unlocks View",
+            "Branch Comparison View",
         ];
 
         let mode_index = Select::new()
@@ -57,6 +144,10 @@ impl super::Command for ViewCommand {
             1 => self.display_detailed_view(&plan)?,
             2 => self.display_curriculum_view(&plan)?,
             3 => self.display_module_list_view(&plan)?,
+            4 => self.display_timetable_view(&plan)?,
+            5 => self.display_prereq_graph_view(&plan)?,
+            6 => self.display_unlocks_view(&plan)?,
+            7 => self.display_branch_comparison_view(&plan, &registry)?,
             _ => unreachable!(),
         }
 
@@ -107,7 +198,7 @@ impl ViewCommand {
 
         println!("\n{}", "Semester Overview:".bold());
 
-        let mut most_units_sem = (0, 0);
+        let mut most_units_sem = (0, Units::ZERO);
         let mut total_modules = 0;
 
         for (i, semester) in plan.semesters.iter().enumerate() {
@@ -142,7 +233,7 @@ impl ViewCommand {
             if plan.semesters.is_empty() {
                 0.0
             } else {
-                plan.total_units() as f64 / plan.semesters.len() as f64
+                plan.total_units().as_f32() as f64 / plan.semesters.len() as f64
             }
         );
         println!(
@@ -171,6 +262,23 @@ impl ViewCommand {
 
         let registry = self.api.get_module_registry()?;
 
+        let credited = self
+            .storage
+            .get_student(&plan.student_id)?
+            .map(|s| s.credited_modules())
+            .unwrap_or_default();
+        let critical_path = analyse_critical_path(plan, &registry, &credited);
+
+        if let Some(ref report) = critical_path {
+            if report.longest_chain.len() > 1 {
+                println!(
+                    "{}:     {}",
+                    "Critical Path".bold(),
+                    report.longest_chain.join(" → ")
+                );
+            }
+        }
+
         for (i, semester) in plan.semesters.iter().enumerate() {
             println!(
                 "\n{} {} {}",
@@ -218,13 +326,13 @@ impl ViewCommand {
                             println!("  {} Modules:", level.bold());
                             for module in modules {
                                 let title = registry
-                                    .get_module(&module.module_code)
+                                    .get_module_for_year(&module.module_code, &semester.year)
                                     .map(|m| m.title.clone())
                                     .unwrap_or_else(|| "Unknown Module".to_string());
 
                                 let credits = registry
-                                    .get_module(&module.module_code)
-                                    .map(|m| m.module_credit.clone())
+                                    .get_module_for_year(&module.module_code, &semester.year)
+                                    .map(|m| m.module_credit.to_string())
                                     .unwrap_or_else(|| "?".to_string());
 
                                 let status = match module.status {
@@ -240,14 +348,26 @@ impl ViewCommand {
                                     "".normal()
                                 };
 
+                                let slack = critical_path
+                                    .as_ref()
+                                    .and_then(|report| report.get(&module.module_code));
+                                let slack_info = match slack {
+                                    Some(s) if s.delays_graduation() => {
+                                        " ⏰ delays graduation".red().bold()
+                                    }
+                                    Some(s) if s.is_critical() => " ⏱ no slack".red().bold(),
+                                    _ => "".normal(),
+                                };
+
                                 println!(
-                                    "    {} {} - {} ({} units) {} {}",
+                                    "    {} {} - {} ({} units) {} {}{}",
                                     "•".bold(),
                                     module.module_code.bold(),
                                     title,
                                     credits,
                                     status,
-                                    su_info
+                                    su_info,
+                                    slack_info
                                 );
                             }
                         }
@@ -321,20 +441,37 @@ impl ViewCommand {
 
         let all_modules: std::collections::HashSet<String> = plan.all_modules();
 
+        let registry = self.api.get_module_registry()?;
+        let credited_units = curriculum.credited_units_for(&student, &registry);
+        let earned_units = plan.total_units() + credited_units;
+        let required_units = Units::from(curriculum.units_required_for(&student));
+
         println!("\n{}", "Degree Requirements:".bold());
-        println!(
-            "Total units required: {}/{}",
-            plan.total_units(),
-            curriculum.total_units_required
-        );
+        println!("Total units required: {}/{}", earned_units, required_units);
+
+        if credited_units > Units::ZERO {
+            println!(
+                "  Includes {} units from exemptions and advanced placement",
+                credited_units
+            );
+        }
 
-        if plan.total_units() >= curriculum.total_units_required {
+        if earned_units >= required_units {
             println!("  {} Unit requirement met", "✅".green());
         } else {
             println!(
                 "  {} Need {} more units",
                 "❌".red(),
-                curriculum.total_units_required - plan.total_units()
+                required_units.saturating_sub(earned_units)
+            );
+        }
+
+        let replacement_units = curriculum.replacement_units_for(&student);
+        if replacement_units > 0 {
+            println!(
+                "  {} {} units of exempted requirements must be replaced with unrestricted electives",
+                "ℹ️".blue(),
+                replacement_units
             );
         }
 
@@ -344,13 +481,13 @@ impl ViewCommand {
             level_1000_units, curriculum.max_level1000_units
         );
 
-        if level_1000_units <= curriculum.max_level1000_units {
+        if level_1000_units <= Units::from(curriculum.max_level1000_units) {
             println!("  {} Within Level 1000 limit", "✅".green());
         } else {
             println!(
                 "  {} Exceeded Level 1000 limit by {} units",
                 "❌".red(),
-                level_1000_units - curriculum.max_level1000_units
+                level_1000_units.saturating_sub(Units::from(curriculum.max_level1000_units))
             );
         }
 
@@ -358,7 +495,7 @@ impl ViewCommand {
             println!(
                 "\n{} ({}/{} units):",
                 component.name.bold(),
-                self.calculate_component_units(plan, component)?,
+                self.calculate_component_units(plan, &student, component)?,
                 component.min_units
             );
 
@@ -368,20 +505,38 @@ impl ViewCommand {
                         module_code,
                         name,
                         units,
+                        on_exemption,
                     } => {
+                        let exempted = student.exempted_modules.contains(module_code);
                         let fulfilled = all_modules.contains(module_code);
-                        let status = if fulfilled {
+                        let status = if exempted {
+                            "🎓".green()
+                        } else if fulfilled {
                             "✅".green()
                         } else {
                             "⬜".normal()
                         };
 
+                        let exemption_info = if exempted {
+                            match on_exemption {
+                                ExemptionPolicy::Credited => " (exempted, credited)",
+                                ExemptionPolicy::Waived => " (exempted, waived)",
+                                ExemptionPolicy::ReplacedByElectives => {
+                                    " (exempted, replace with unrestricted electives)"
+                                }
+                            }
+                            .dimmed()
+                        } else {
+                            "".normal()
+                        };
+
                         println!(
-                            "  {} {} {} ({} units)",
+                            "  {} {} {} ({} units){}",
                             status,
                             module_code.bold(),
                             name,
-                            units
+                            units,
+                            exemption_info
                         );
                     }
                     crate::models::Requirement::ModuleGroup {
@@ -392,14 +547,16 @@ impl ViewCommand {
                     } => {
                         let fulfilled_modules: Vec<&String> = possible_modules
                             .iter()
-                            .filter(|m| plan.all_modules().contains(*m))
+                            .filter(|m| {
+                                all_modules.contains(*m) || student.exempted_modules.contains(*m)
+                            })
                             .collect();
 
                         let _fulfilled_count = fulfilled_modules.len();
                         let fulfilled_units =
-                            self.calculate_group_units(fulfilled_modules.clone())?;
+                            self.calculate_group_units(plan, fulfilled_modules.clone())?;
 
-                        let status = if fulfilled_units >= *min_units {
+                        let status = if fulfilled_units >= Units::from(*min_units) {
                             "✅".green()
                         } else {
                             "⬜".normal()
@@ -455,7 +612,7 @@ impl ViewCommand {
 
         let mut modules = Vec::new();
         for code in all_module_codes {
-            if let Some(module) = registry.get_module(&code) {
+            if let Some(module) = planned_module_data(&registry, plan, &code) {
                 modules.push(module);
             }
         }
@@ -486,23 +643,442 @@ impl ViewCommand {
         Ok(())
     }
 
-    pub fn calculate_level_units(&self, plan: &Plan, level: u32) -> Result<u32> {
-        let level_digit = (level / 1000).to_string();
-        let mut total = 0;
+    fn select_module_code(&self, plan: &Plan) -> Result<String> {
+        let mut module_options: Vec<String> = plan.all_modules().into_iter().collect();
+        module_options.sort();
+        module_options.push("Another module...".to_string());
+
+        let module_index = Select::new()
+            .with_prompt("Select a module")
+            .items(&module_options)
+            .default(0)
+            .interact()?;
+
+        if module_index + 1 == module_options.len() {
+            let code: String = Input::new()
+                .with_prompt("Enter module code")
+                .interact_text()?;
+            Ok(code.trim().to_uppercase())
+        } else {
+            Ok(module_options[module_index].clone())
+        }
+    }
+
+    fn display_prereq_graph_view(&self, plan: &Plan) -> Result<()> {
+        let module_code = self.select_module_code(plan)?;
+
+        let registry = self.api.get_module_registry()?;
+        if !registry.contains_module(&module_code) {
+            println!("⚠️ Module {} not found in the registry", module_code);
+            return Ok(());
+        }
+
+        let mut passed = plan.completed_modules();
+        if let Some(student) = self.storage.get_student(&plan.student_id)? {
+            passed.extend(student.credited_modules());
+        }
+
+        println!("\n{}", "🔗 PREREQUISITE GRAPH".bold());
+        print!(
+            "{}",
+            graph::render_prereq_tree(&registry, &module_code, &passed)
+        );
+        println!("\n{} passed or credited", "✓".green());
+
+        Ok(())
+    }
+
+    fn display_unlocks_view(&self, plan: &Plan) -> Result<()> {
+        let module_code = self.select_module_code(plan)?;
+
+        let registry = self.api.get_module_registry()?;
+        if !registry.contains_module(&module_code) {
+            println!("⚠️ Module {} not found in the registry", module_code);
+            return Ok(());
+        }
+
+        let student = self.storage.get_student(&plan.student_id)?;
+
+        let mut passed = plan.completed_modules();
+        let mut taken = plan.all_modules();
+        if let Some(ref student) = student {
+            passed.extend(student.credited_modules());
+            taken.extend(student.credited_modules());
+        }
+
+        let unlocks = registry.unlocked_by(&module_code, &passed);
+
+        println!("\n{} {}", "🔓 UNLOCKED BY".bold(), module_code);
+
+        if unlocks.direct.is_empty() && unlocks.transitive.is_empty() {
+            println!("No further modules are unlocked by {}", module_code);
+            return Ok(());
+        }
+
+        let describe = |code: &String| {
+            let title = registry
+                .get_module(code)
+                .map(|m| m.title.as_str())
+                .unwrap_or("");
+            let marker = if taken.contains(code) {
+                " (in plan)"
+            } else {
+                ""
+            };
+            format!("  - {} {}{}", code, truncate_string(title, 40), marker)
+        };
+
+        println!("\n{} ({})", "Directly".bold(), unlocks.direct.len());
+        for code in &unlocks.direct {
+            println!("{}", describe(code));
+        }
+
+        if !unlocks.transitive.is_empty() {
+            println!("\n{} ({})", "Later on".bold(), unlocks.transitive.len());
+            for code in &unlocks.transitive {
+                println!("{}", describe(code));
+            }
+        }
+
+        if let Some(curriculum) = student
+            .as_ref()
+            .map(|s| self.storage.get_curriculum(&s.major))
+            .transpose()?
+            .flatten()
+        {
+            let unlocked: HashSet<String> = unlocks.all().cloned().collect();
+            let requirements = curriculum.requirements_unlocked_by(&unlocked, &taken, &registry);
+
+            if !requirements.is_empty() {
+                println!("\n{}", "Unmet requirements that become reachable:".bold());
+                for name in requirements {
+                    println!("  - {}", name);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn display_timetable_view(&self, plan: &Plan) -> Result<()> {
+        if plan.semesters.is_empty() {
+            println!("No semesters in this plan.");
+            return Ok(());
+        }
+
+        let semester_options: Vec<String> = plan
+            .semesters
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("[{}] {} Semester {}", i + 1, s.year, s.semester))
+            .collect();
+
+        let sem_index = Select::new()
+            .with_prompt("Select a semester")
+            .items(&semester_options)
+            .default(0)
+            .interact()?;
+
+        let mut week_options = vec!["All weeks".to_string()];
+        week_options.extend((1..=13).map(|week| format!("Week {}", week)));
+
+        let week_index = Select::new()
+            .with_prompt("Select a teaching week")
+            .items(&week_options)
+            .default(0)
+            .interact()?;
+        let week = (week_index > 0).then_some(week_index as u8);
+
+        let semester = &plan.semesters[sem_index];
+        let registry = self.api.get_module_registry()?;
+        let grid = self.build_timetable_grid(semester, &registry, week);
+
+        println!(
+            "\n{} {} Semester {} ({})",
+            "📅 TIMETABLE:".bold(),
+            semester.year,
+            semester.semester,
+            week_options[week_index]
+        );
+
+        let colors: HashMap<&str, Color> = semester
+            .modules
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                (
+                    m.module_code.as_str(),
+                    TIMETABLE_COLORS[i % TIMETABLE_COLORS.len()],
+                )
+            })
+            .collect();
+
+        const CELL_WIDTH: usize = 14;
+
+        print!("{:<6}", "");
+        for day in TIMETABLE_DAYS {
+            print!("{:<width$}", &day[..3], width = CELL_WIDTH);
+        }
+        println!();
+
+        for hour in grid.start_hour..grid.end_hour {
+            print!("{:<6}", format!("{:02}00", hour));
+
+            for day in 0..TIMETABLE_DAYS.len() {
+                let slots = grid.slots_at(day, hour);
+                let mut codes: Vec<&str> = slots.iter().map(|s| s.module_code.as_str()).collect();
+                codes.dedup();
+
+                let text = format!(
+                    "{:<width$}",
+                    truncate_string(&codes.join("/"), CELL_WIDTH - 1),
+                    width = CELL_WIDTH
+                );
+
+                let cell = if codes.len() > 1 {
+                    text.white().on_red().bold()
+                } else if let Some(code) = codes.first() {
+                    text.color(colors.get(code).copied().unwrap_or(Color::White))
+                } else {
+                    text.dimmed()
+                };
+                print!("{}", cell);
+            }
+            println!();
+        }
+
+        let clashes = grid.clashes();
+        if clashes.is_empty() {
+            println!("\n{} No timetable clashes", "✅".green());
+        } else {
+            println!("\n{}", "Clashes:".red().bold());
+            for (day, hour) in clashes {
+                let lessons: Vec<String> = grid
+                    .slots_at(day, hour)
+                    .iter()
+                    .map(|s| format!("{} {} [{}]", s.module_code, s.lesson_type, s.class_no))
+                    .collect();
+                println!(
+                    "  {} {:02}00: {}",
+                    TIMETABLE_DAYS[day],
+                    hour,
+                    lessons.join(", ")
+                );
+            }
+        }
+
+        if !grid.unscheduled.is_empty() {
+            println!(
+                "\n{} {}",
+                "No timetable data:".dimmed(),
+                grid.unscheduled.join(", ")
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Lays out each module's selected classes, or every class if none has
+    /// been chosen for a lesson type, keeping only lessons that run in `week`.
+    pub fn build_timetable_grid(
+        &self,
+        semester: &SemesterPlan,
+        registry: &ModuleRegistry,
+        week: Option<u8>,
+    ) -> TimetableGrid {
+        let mut grid = TimetableGrid {
+            start_hour: 8,
+            end_hour: 18,
+            ..TimetableGrid::default()
+        };
+
+        for planned in &semester.modules {
+            let lessons = registry
+                .get_module_for_year(&planned.module_code, &semester.year)
+                .and_then(|m| {
+                    m.semester_data
+                        .iter()
+                        .find(|d| d.semester == semester.semester)
+                })
+                .and_then(|d| d.timetable.as_ref());
+
+            let Some(lessons) = lessons.filter(|l| !l.is_empty()) else {
+                grid.unscheduled.push(planned.module_code.clone());
+                continue;
+            };
+
+            for lesson in lessons {
+                if let Some(selected) = planned.class_selections.get(&lesson.lesson_type) {
+                    if *selected != lesson.class_no {
+                        continue;
+                    }
+                }
+
+                if week.is_some_and(|w| !lesson.week_type.occurs_in_week(w)) {
+                    continue;
+                }
+
+                let Some(day) = TIMETABLE_DAYS
+                    .iter()
+                    .position(|d| d.eq_ignore_ascii_case(&lesson.day))
+                else {
+                    continue;
+                };
+
+                let Some((start, end)) = lesson.hour_span() else {
+                    continue;
+                };
+
+                grid.start_hour = grid.start_hour.min(start);
+                grid.end_hour = grid.end_hour.max(end);
+
+                for hour in start..end {
+                    grid.cells
+                        .entry((day, hour))
+                        .or_default()
+                        .push(TimetableSlot {
+                            module_code: planned.module_code.clone(),
+                            lesson_type: lesson.lesson_type.clone(),
+                            class_no: lesson.class_no.clone(),
+                            venue: lesson.venue.clone(),
+                        });
+                }
+            }
+        }
+
+        grid
+    }
+
+    fn display_branch_comparison_view(&self, plan: &Plan, registry: &ModuleRegistry) -> Result<()> {
+        let family = super::branch::plan_family(self.storage.as_ref(), plan.family_id())?;
+        if family.len() < 2 {
+            println!("This plan has no other branches to compare with.");
+            return Ok(());
+        }
+
+        let summaries = family
+            .iter()
+            .map(|branch| self.summarize_branch(branch, registry))
+            .collect::<Result<Vec<_>>>()?;
+
+        println!("\n{}", "🔀 BRANCH COMPARISON".bold());
+
+        let mut terms: Vec<&String> = Vec::new();
+        for summary in &summaries {
+            for (term, _) in &summary.semester_units {
+                if !terms.contains(&term) {
+                    terms.push(term);
+                }
+            }
+        }
+        terms.sort();
+
+        print!("{:<24}", "");
+        for summary in &summaries {
+            let header = if summary.primary {
+                format!("{} ★", summary.branch_name)
+            } else {
+                summary.branch_name.clone()
+            };
+            print!("{:>16}", truncate_string(&header, 16).bold());
+        }
+        println!();
+
+        for term in terms {
+            print!("{:<24}", term);
+            for summary in &summaries {
+                let units = summary
+                    .semester_units
+                    .iter()
+                    .find(|(t, _)| t == term)
+                    .map_or("-".to_string(), |(_, units)| units.to_string());
+                print!("{:>16}", units);
+            }
+            println!();
+        }
+
+        print!("{:<24}", "Total units".bold());
+        for summary in &summaries {
+            print!("{:>16}", summary.total_units.to_string());
+        }
+        println!();
+
+        print!("{:<24}", "Requirement progress".bold());
+        for summary in &summaries {
+            let progress = summary
+                .requirement_progress
+                .map_or("-".to_string(), |(earned, required)| {
+                    format!("{}/{}", earned, required)
+                });
+            print!("{:>16}", progress);
+        }
+        println!();
+
+        print!("{:<24}", "Projected CAP".bold());
+        for summary in &summaries {
+            let cap = summary
+                .projected_cap
+                .map_or("-".to_string(), |cap| format!("{:.2}", cap));
+            print!("{:>16}", cap);
+        }
+        println!();
+
+        Ok(())
+    }
+
+    /// Gathers the figures the branch comparison view shows for one branch.
+    pub fn summarize_branch(
+        &self,
+        plan: &Plan,
+        registry: &ModuleRegistry,
+    ) -> Result<BranchSummary> {
+        let semester_units = plan
+            .semesters
+            .iter()
+            .map(|s| {
+                (
+                    format!("{} Semester {}", s.year, s.semester),
+                    s.derived_units(registry),
+                )
+            })
+            .collect();
+        let total_units = plan.derived_units(registry);
+
+        let student = self.storage.get_student(&plan.student_id)?;
+        let curriculum = match &student {
+            Some(student) => self.storage.get_curriculum(&student.major)?,
+            None => None,
+        };
+        let requirement_progress = student.zip(curriculum).map(|(student, curriculum)| {
+            (
+                total_units + curriculum.credited_units_for(&student, registry),
+                Units::from(curriculum.units_required_for(&student)),
+            )
+        });
+
+        Ok(BranchSummary {
+            branch_name: plan.branch_name().to_string(),
+            primary: plan.is_primary(),
+            semester_units,
+            total_units,
+            requirement_progress,
+            projected_cap: plan.projected_cap(registry),
+        })
+    }
+
+    /// Units planned at the given level, using each module's credit for the
+    /// semester's academic year. Modules missing from the registry count as 0.
+    pub fn calculate_level_units(&self, plan: &Plan, level: u32) -> Result<Units> {
+        let registry = self.api.get_module_registry()?;
+        let mut total = Units::ZERO;
 
         for semester in &plan.semesters {
             for module in &semester.modules {
-                // Find the first digit in the module code
-                if let Some(pos) = module.module_code.chars().position(|c| c.is_ascii_digit()) {
-                    // Get the numeric part (e.g., "1111" from "IE1111R")
-                    let numeric_part: String = module.module_code[pos..]
-                        .chars()
-                        .take_while(|c| c.is_ascii_digit())
-                        .collect();
-
-                    // Check if it's a module of the specified level
-                    if numeric_part.starts_with(&level_digit) {
-                        total += 4; // Each module is 4 units
+                if let Some(registry_module) =
+                    registry.get_module_for_year(&module.module_code, &semester.year)
+                {
+                    if registry_module.level() == Some(level) {
+                        total += registry_module.units();
                     }
                 }
             }
@@ -511,31 +1087,32 @@ impl ViewCommand {
         Ok(total)
     }
 
+    /// Units counted toward a component, including exempted modules.
     pub fn calculate_component_units(
         &self,
         plan: &Plan,
+        student: &Student,
         component: &crate::models::CurriculumComponent,
-    ) -> Result<u32> {
-        let mut total = 0;
+    ) -> Result<Units> {
+        let mut total = Units::ZERO;
+        let taken = plan.all_modules();
+        let counts =
+            |code: &String| taken.contains(code) || student.exempted_modules.contains(code);
 
         for requirement in &component.requirements {
             match requirement {
                 crate::models::Requirement::FixedModule {
                     module_code, units, ..
-                } => {
-                    if plan.all_modules().contains(module_code) {
-                        total += units;
-                    }
+                } if counts(module_code) => {
+                    total += Units::from(*units);
                 }
                 crate::models::Requirement::ModuleGroup {
                     possible_modules, ..
                 } => {
-                    let fulfilled_modules: Vec<&String> = possible_modules
-                        .iter()
-                        .filter(|m| plan.all_modules().contains(*m))
-                        .collect();
+                    let fulfilled_modules: Vec<&String> =
+                        possible_modules.iter().filter(|m| counts(m)).collect();
 
-                    total += self.calculate_group_units(fulfilled_modules.clone())?;
+                    total += self.calculate_group_units(plan, fulfilled_modules.clone())?;
                 }
                 _ => {}
             }
@@ -544,27 +1121,40 @@ impl ViewCommand {
         Ok(total)
     }
 
-    pub fn calculate_group_units(&self, modules: Vec<&String>) -> Result<u32> {
+    pub fn calculate_group_units(&self, plan: &Plan, modules: Vec<&String>) -> Result<Units> {
         let registry = self.api.get_module_registry()?;
-        let mut total = 0;
-
-        for &module_code in &modules {
-            if let Some(module) = registry.get_module(module_code) {
-                if let Ok(credits) = module.module_credit.parse::<u32>() {
-                    total += credits;
-                }
-            }
-        }
 
-        Ok(total)
+        Ok(modules
+            .iter()
+            .filter_map(|code| planned_module_data(&registry, plan, code))
+            .map(|m| m.units())
+            .sum())
     }
 }
 
+/// A module's data for the academic year of its latest semester in the
+/// plan, or the latest known data for modules not in the plan (such as
+/// exemptions).
+fn planned_module_data<'a>(
+    registry: &'a ModuleRegistry,
+    plan: &Plan,
+    module_code: &str,
+) -> Option<&'a Module> {
+    plan.semesters
+        .iter()
+        .rev()
+        .find(|s| s.modules.iter().any(|m| m.module_code == module_code))
+        .map_or_else(
+            || registry.get_module(module_code),
+            |semester| registry.get_module_for_year(module_code, &semester.year),
+        )
+}
+
 // Helper function to truncate long strings
 fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[0..max_len - 3])
+        format!("{}...", s.chars().take(max_len - 3).collect::<String>())
     }
 }
@@ -0,0 +1,289 @@
+use crate::models::{term_key, ModuleRegistry, ModuleStatus, Plan, SemesterPlan};
+use crate::transcript::parse_term;
+use std::collections::{HashMap, HashSet};
+
+/// How many semesters past the end of the plan the target graduation term
+/// may lie before the analysis gives up on finding it.
+const MAX_TERMS_AHEAD: usize = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleSlack {
+    pub module_code: String,
+    pub scheduled_semester: usize,
+    /// First semester the module could be taken once its planned
+    /// prerequisites are done, or `None` if it is never offered in time.
+    pub earliest_semester: Option<usize>,
+    /// Last semester the module can be taken without delaying graduation,
+    /// or `None` if no such semester exists.
+    pub latest_semester: Option<usize>,
+    /// Number of modules in the longest prerequisite chain starting here.
+    pub chain_length: usize,
+}
+
+impl ModuleSlack {
+    /// Semesters the module can slip before it delays graduation. Negative
+    /// when it is already scheduled too late.
+    pub fn slack(&self) -> Option<isize> {
+        self.latest_semester
+            .map(|latest| latest as isize - self.scheduled_semester as isize)
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.slack() == Some(0)
+    }
+
+    pub fn delays_graduation(&self) -> bool {
+        self.slack().is_none_or(|slack| slack < 0)
+    }
+}
+
+#[derive(Debug)]
+pub struct CriticalPathReport {
+    /// Index of the target graduation semester in `terms`.
+    pub deadline_index: usize,
+    /// The plan's semesters, extended with regular semesters up to the
+    /// target graduation term if the plan ends earlier.
+    pub terms: Vec<(String, u8)>,
+    pub modules: Vec<ModuleSlack>,
+    pub longest_chain: Vec<String>,
+}
+
+impl CriticalPathReport {
+    pub fn get(&self, module_code: &str) -> Option<&ModuleSlack> {
+        self.modules.iter().find(|m| m.module_code == module_code)
+    }
+
+    pub fn critical_modules(&self) -> impl Iterator<Item = &ModuleSlack> {
+        self.modules.iter().filter(|m| m.is_critical())
+    }
+
+    pub fn term_label(&self, index: usize) -> String {
+        self.terms
+            .get(index)
+            .map(|(year, semester)| format!("{} Semester {}", year, semester))
+            .unwrap_or_else(|| format!("semester {}", index + 1))
+    }
+}
+
+/// Works out how late each remaining planned module can be taken without
+/// pushing graduation past `plan.target_graduation`.
+///
+/// Only prerequisites that cannot be met any other way create ordering
+/// constraints, and a module can only sit in a semester it is offered in.
+/// `credited` holds modules passed or exempted outside the plan. Returns
+/// `None` if the target graduation term cannot be parsed or located.
+pub fn analyse_critical_path(
+    plan: &Plan,
+    registry: &ModuleRegistry,
+    credited: &HashSet<String>,
+) -> Option<CriticalPathReport> {
+    let (target_year, target_semester) = parse_term(&plan.target_graduation)?;
+    let terms = plan_terms(plan, &target_year, target_semester);
+    let deadline_index = terms
+        .iter()
+        .position(|(year, semester)| *year == target_year && *semester == target_semester)?;
+
+    let mut passed = credited.clone();
+    let mut first_open = 0;
+    let mut scheduled: HashMap<String, usize> = HashMap::new();
+
+    for (index, semester) in plan.semesters.iter().enumerate() {
+        for module in &semester.modules {
+            match module.status {
+                ModuleStatus::Completed | ModuleStatus::Current => {
+                    passed.insert(module.module_code.clone());
+                    first_open = index + 1;
+                }
+                ModuleStatus::Planned => {
+                    scheduled.insert(module.module_code.clone(), index);
+                }
+                ModuleStatus::Failed => {}
+            }
+        }
+    }
+    scheduled.retain(|code, _| !passed.contains(code));
+
+    let mut remaining: Vec<String> = scheduled.keys().cloned().collect();
+    remaining.sort_by_key(|code| (scheduled[code], code.clone()));
+
+    let everything: HashSet<String> = passed.iter().chain(remaining.iter()).cloned().collect();
+    let mut predecessors: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for code in &remaining {
+        let Some(tree) = registry
+            .get_module_for_year(code, &plan.semesters[scheduled[code]].year)
+            .and_then(|m| m.prereq_tree.as_ref())
+        else {
+            continue;
+        };
+
+        let mentioned = tree.module_codes();
+        for prereq in &remaining {
+            if prereq == code || !mentioned.iter().any(|m| code_matches(m, prereq)) {
+                continue;
+            }
+
+            let mut without = everything.clone();
+            without.remove(prereq);
+            if tree.is_satisfied_by(&everything) && !tree.is_satisfied_by(&without) {
+                predecessors.entry(code).or_default().push(prereq);
+                successors.entry(prereq).or_default().push(code);
+            }
+        }
+    }
+
+    let order = topological_order(&remaining, &predecessors);
+
+    let mut earliest: HashMap<&str, Option<usize>> = HashMap::new();
+    for &code in &order {
+        let start = predecessors
+            .get(code)
+            .into_iter()
+            .flatten()
+            .map(|p| match earliest.get(p) {
+                Some(index) => index.map(|index| index + 1),
+                None => Some(first_open),
+            })
+            .try_fold(first_open, |acc, index| index.map(|i| acc.max(i)));
+
+        let index = start.and_then(|start| {
+            (start..terms.len())
+                .find(|&index| registry.is_offered(code, &terms[index].0, terms[index].1))
+        });
+        earliest.insert(code, index);
+    }
+
+    let mut latest: HashMap<&str, Option<usize>> = HashMap::new();
+    let mut chain_length: HashMap<&str, usize> = HashMap::new();
+    for &code in order.iter().rev() {
+        let next = successors.get(code).map(|s| s.as_slice()).unwrap_or(&[]);
+
+        let bound = next
+            .iter()
+            .map(|s| match latest.get(s) {
+                Some(index) => index.and_then(|index| index.checked_sub(1)),
+                None => Some(deadline_index),
+            })
+            .try_fold(deadline_index, |acc, index| index.map(|i| acc.min(i)));
+
+        let index = bound.and_then(|bound| {
+            (first_open..=bound)
+                .rev()
+                .find(|&index| registry.is_offered(code, &terms[index].0, terms[index].1))
+        });
+        latest.insert(code, index);
+
+        let length = 1 + next
+            .iter()
+            .filter_map(|s| chain_length.get(s))
+            .max()
+            .unwrap_or(&0);
+        chain_length.insert(code, length);
+    }
+
+    let mut longest_chain = Vec::new();
+    let mut current = remaining
+        .iter()
+        .map(|code| code.as_str())
+        .max_by_key(|code| (chain_length[code], std::cmp::Reverse(*code)));
+    while let Some(code) = current.filter(|code| !longest_chain.iter().any(|c| c == code)) {
+        longest_chain.push(code.to_string());
+        current = successors
+            .get(code)
+            .into_iter()
+            .flatten()
+            .copied()
+            .max_by_key(|s| (chain_length[s], std::cmp::Reverse(*s)));
+    }
+
+    let modules = remaining
+        .iter()
+        .map(|code| ModuleSlack {
+            module_code: code.clone(),
+            scheduled_semester: scheduled[code],
+            earliest_semester: earliest[code.as_str()],
+            latest_semester: latest[code.as_str()],
+            chain_length: chain_length[code.as_str()],
+        })
+        .collect();
+
+    Some(CriticalPathReport {
+        deadline_index,
+        terms,
+        modules,
+        longest_chain,
+    })
+}
+
+fn plan_terms(plan: &Plan, target_year: &str, target_semester: u8) -> Vec<(String, u8)> {
+    let mut terms: Vec<(String, u8)> = plan
+        .semesters
+        .iter()
+        .map(|s| (s.year.clone(), s.semester))
+        .collect();
+
+    let target = term_key(target_year, target_semester);
+    let mut last = plan.semesters.last().cloned();
+
+    for _ in 0..MAX_TERMS_AHEAD {
+        let Some(semester) = last.as_ref() else {
+            break;
+        };
+        if term_key(&semester.year, semester.semester) >= target {
+            break;
+        }
+
+        let next: SemesterPlan = semester.next_semester();
+        terms.push((next.year.clone(), next.semester));
+        last = Some(next);
+    }
+
+    terms
+}
+
+/// Orders modules so that every essential prerequisite comes first. Modules
+/// caught in a prerequisite cycle are appended in plan order.
+fn topological_order<'a>(
+    remaining: &'a [String],
+    predecessors: &HashMap<&str, Vec<&str>>,
+) -> Vec<&'a str> {
+    let mut order: Vec<&str> = Vec::new();
+    let mut placed: HashSet<&str> = HashSet::new();
+
+    loop {
+        let ready: Vec<&str> = remaining
+            .iter()
+            .map(|code| code.as_str())
+            .filter(|code| !placed.contains(code))
+            .filter(|code| {
+                predecessors
+                    .get(code)
+                    .into_iter()
+                    .flatten()
+                    .all(|p| placed.contains(p))
+            })
+            .collect();
+
+        if ready.is_empty() {
+            break;
+        }
+        placed.extend(ready.iter().copied());
+        order.extend(ready);
+    }
+
+    order.extend(
+        remaining
+            .iter()
+            .map(|code| code.as_str())
+            .filter(|code| !placed.contains(code)),
+    );
+    order
+}
+
+fn code_matches(pattern: &str, code: &str) -> bool {
+    match pattern.strip_suffix('%') {
+        Some(prefix) => code.starts_with(prefix),
+        None => pattern == code,
+    }
+}
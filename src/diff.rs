@@ -0,0 +1,397 @@
+use crate::models::{ModuleStatus, Plan, PlannedModule, SemesterPlan, SpecialActivity};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Identifies a semester by term rather than position, so semesters line up
+/// between plans with different layouts.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct SemesterKey {
+    pub year: String,
+    pub semester: u8,
+}
+
+impl SemesterKey {
+    pub fn of(semester: &SemesterPlan) -> Self {
+        Self {
+            year: semester.year.clone(),
+            semester: semester.semester,
+        }
+    }
+}
+
+impl fmt::Display for SemesterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} Semester {}", self.year, self.semester)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ModuleDiff {
+    Added {
+        module_code: String,
+        semester: SemesterKey,
+    },
+    Removed {
+        module_code: String,
+        semester: SemesterKey,
+    },
+    Moved {
+        module_code: String,
+        from: SemesterKey,
+        to: SemesterKey,
+    },
+    StatusChanged {
+        module_code: String,
+        semester: SemesterKey,
+        from: ModuleStatus,
+        to: ModuleStatus,
+    },
+    GradeChanged {
+        module_code: String,
+        semester: SemesterKey,
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ActivityDiff {
+    Added {
+        semester: SemesterKey,
+        activity: SpecialActivity,
+    },
+    Removed {
+        semester: SemesterKey,
+        activity: SpecialActivity,
+    },
+}
+
+/// A plan-level field such as the name or target graduation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MetadataChange {
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Everything that differs between two plans, from the first plan's point
+/// of view.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PlanDiff {
+    pub metadata: Vec<MetadataChange>,
+    pub modules: Vec<ModuleDiff>,
+    pub activities: Vec<ActivityDiff>,
+}
+
+/// How a diff line reads in a unified diff: `+`, `-` or `~`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffSign {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl DiffSign {
+    pub fn symbol(self) -> char {
+        match self {
+            DiffSign::Added => '+',
+            DiffSign::Removed => '-',
+            DiffSign::Changed => '~',
+        }
+    }
+}
+
+impl PlanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_empty() && self.modules.is_empty() && self.activities.is_empty()
+    }
+
+    /// One line per change, metadata first, each with its sign.
+    pub fn lines(&self) -> Vec<(DiffSign, String)> {
+        let metadata = self.metadata.iter().map(|c| (c.sign(), c.to_string()));
+        let modules = self.modules.iter().map(|d| (d.sign(), d.to_string()));
+        let activities = self.activities.iter().map(|d| (d.sign(), d.to_string()));
+        metadata.chain(modules).chain(activities).collect()
+    }
+}
+
+impl fmt::Display for PlanDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (sign, line) in self.lines() {
+            writeln!(f, "{} {}", sign.symbol(), line)?;
+        }
+        Ok(())
+    }
+}
+
+impl ModuleDiff {
+    pub fn sign(&self) -> DiffSign {
+        match self {
+            ModuleDiff::Added { .. } => DiffSign::Added,
+            ModuleDiff::Removed { .. } => DiffSign::Removed,
+            _ => DiffSign::Changed,
+        }
+    }
+}
+
+impl fmt::Display for ModuleDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleDiff::Added {
+                module_code,
+                semester,
+            } => write!(f, "{} added to {}", module_code, semester),
+            ModuleDiff::Removed {
+                module_code,
+                semester,
+            } => write!(f, "{} removed from {}", module_code, semester),
+            ModuleDiff::Moved {
+                module_code,
+                from,
+                to,
+            } => write!(f, "{} moved from {} to {}", module_code, from, to),
+            ModuleDiff::StatusChanged {
+                module_code,
+                semester,
+                from,
+                to,
+            } => write!(f, "{} ({}): {:?} → {:?}", module_code, semester, from, to),
+            ModuleDiff::GradeChanged {
+                module_code,
+                semester,
+                from,
+                to,
+            } => write!(
+                f,
+                "{} ({}): grade {} → {}",
+                module_code,
+                semester,
+                from.as_deref().unwrap_or("none"),
+                to.as_deref().unwrap_or("none")
+            ),
+        }
+    }
+}
+
+impl ActivityDiff {
+    pub fn sign(&self) -> DiffSign {
+        match self {
+            ActivityDiff::Added { .. } => DiffSign::Added,
+            ActivityDiff::Removed { .. } => DiffSign::Removed,
+        }
+    }
+}
+
+impl fmt::Display for ActivityDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActivityDiff::Added { semester, activity } => {
+                write!(
+                    f,
+                    "Activity '{}' added to {}",
+                    activity.description, semester
+                )
+            }
+            ActivityDiff::Removed { semester, activity } => write!(
+                f,
+                "Activity '{}' removed from {}",
+                activity.description, semester
+            ),
+        }
+    }
+}
+
+impl MetadataChange {
+    pub fn sign(&self) -> DiffSign {
+        match (&self.from, &self.to) {
+            (None, Some(_)) => DiffSign::Added,
+            (Some(_), None) => DiffSign::Removed,
+            _ => DiffSign::Changed,
+        }
+    }
+}
+
+impl fmt::Display for MetadataChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} → {}",
+            self.field,
+            self.from.as_deref().unwrap_or("(none)"),
+            self.to.as_deref().unwrap_or("(none)")
+        )
+    }
+}
+
+/// Compares two plans. Module occurrences are matched by code, first within
+/// the same semester and then across semesters in plan order, so a retaken
+/// module is reported per attempt.
+pub fn diff_plans(old: &Plan, new: &Plan) -> PlanDiff {
+    PlanDiff {
+        metadata: diff_metadata(old, new),
+        modules: diff_modules(old, new),
+        activities: diff_activities(old, new),
+    }
+}
+
+fn diff_metadata(old: &Plan, new: &Plan) -> Vec<MetadataChange> {
+    let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_string());
+    let fields = [
+        ("name", non_empty(&old.name), non_empty(&new.name)),
+        (
+            "student",
+            non_empty(&old.student_id),
+            non_empty(&new.student_id),
+        ),
+        (
+            "target graduation",
+            non_empty(&old.target_graduation),
+            non_empty(&new.target_graduation),
+        ),
+        ("notes", old.notes.clone(), new.notes.clone()),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(field, from, to)| MetadataChange {
+            field: field.to_string(),
+            from,
+            to,
+        })
+        .collect()
+}
+
+type Occurrences<'a> = BTreeMap<&'a str, Vec<(SemesterKey, &'a PlannedModule)>>;
+
+fn occurrences(plan: &Plan) -> Occurrences<'_> {
+    let mut occurrences: Occurrences = BTreeMap::new();
+    for semester in &plan.semesters {
+        for module in &semester.modules {
+            occurrences
+                .entry(module.module_code.as_str())
+                .or_default()
+                .push((SemesterKey::of(semester), module));
+        }
+    }
+    occurrences
+}
+
+fn diff_modules(old: &Plan, new: &Plan) -> Vec<ModuleDiff> {
+    let old_occurrences = occurrences(old);
+    let mut new_occurrences = occurrences(new);
+    let mut diffs = Vec::new();
+
+    let mut codes: Vec<&str> = old_occurrences
+        .keys()
+        .chain(new_occurrences.keys())
+        .copied()
+        .collect();
+    codes.sort_unstable();
+    codes.dedup();
+
+    for code in codes {
+        let mut unmatched_old = old_occurrences.get(code).cloned().unwrap_or_default();
+        let mut unmatched_new = new_occurrences.remove(code).unwrap_or_default();
+
+        // Same module in the same semester on both sides
+        unmatched_old.retain(|(key, before)| {
+            match unmatched_new.iter().position(|(other, _)| other == key) {
+                Some(index) => {
+                    let (_, after) = unmatched_new.remove(index);
+                    diff_attributes(&mut diffs, key, before, after);
+                    false
+                }
+                None => true,
+            }
+        });
+
+        let mut unmatched_new = unmatched_new.into_iter();
+        for (from, before) in unmatched_old {
+            match unmatched_new.next() {
+                Some((to, after)) => {
+                    diffs.push(ModuleDiff::Moved {
+                        module_code: code.to_string(),
+                        from,
+                        to: to.clone(),
+                    });
+                    diff_attributes(&mut diffs, &to, before, after);
+                }
+                None => diffs.push(ModuleDiff::Removed {
+                    module_code: code.to_string(),
+                    semester: from,
+                }),
+            }
+        }
+        for (semester, _) in unmatched_new {
+            diffs.push(ModuleDiff::Added {
+                module_code: code.to_string(),
+                semester,
+            });
+        }
+    }
+
+    diffs
+}
+
+fn diff_attributes(
+    diffs: &mut Vec<ModuleDiff>,
+    semester: &SemesterKey,
+    before: &PlannedModule,
+    after: &PlannedModule,
+) {
+    if before.status != after.status {
+        diffs.push(ModuleDiff::StatusChanged {
+            module_code: after.module_code.clone(),
+            semester: semester.clone(),
+            from: before.status.clone(),
+            to: after.status.clone(),
+        });
+    }
+    if before.grade != after.grade {
+        diffs.push(ModuleDiff::GradeChanged {
+            module_code: after.module_code.clone(),
+            semester: semester.clone(),
+            from: before.grade.clone(),
+            to: after.grade.clone(),
+        });
+    }
+}
+
+fn diff_activities(old: &Plan, new: &Plan) -> Vec<ActivityDiff> {
+    let activities = |plan: &Plan| -> Vec<(SemesterKey, SpecialActivity)> {
+        plan.semesters
+            .iter()
+            .flat_map(|s| {
+                s.special_activities
+                    .iter()
+                    .map(move |a| (SemesterKey::of(s), a.clone()))
+            })
+            .collect()
+    };
+
+    let mut added = activities(new);
+    let mut diffs = Vec::new();
+
+    for (semester, activity) in activities(old) {
+        match added
+            .iter()
+            .position(|entry| *entry == (semester.clone(), activity.clone()))
+        {
+            Some(index) => {
+                added.remove(index);
+            }
+            None => diffs.push(ActivityDiff::Removed { semester, activity }),
+        }
+    }
+    diffs.extend(
+        added
+            .into_iter()
+            .map(|(semester, activity)| ActivityDiff::Added { semester, activity }),
+    );
+
+    diffs
+}
@@ -0,0 +1,347 @@
+use crate::models::{ModuleRegistry, ModuleStatus, Plan, PrereqTree};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub module_code: String,
+    /// Plan semester holding the module, or `None` for prerequisites that
+    /// appear outside the plan (e.g. taken before it or not yet planned).
+    pub semester_index: Option<usize>,
+    pub status: Option<ModuleStatus>,
+}
+
+/// A prerequisite relationship: `from` must be passed before taking `to`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+    /// Builds the prerequisite graph for every module in the plan. A module
+    /// taken more than once (a retake) is placed at its last occurrence.
+    /// Wildcard prerequisites such as `IE21%` link to every matching planned
+    /// module, or appear as a single external node if nothing matches.
+    pub fn from_plan(plan: &Plan, registry: &ModuleRegistry) -> Self {
+        let mut graph = DependencyGraph::default();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+
+        for (sem_index, semester) in plan.semesters.iter().enumerate() {
+            for module in &semester.modules {
+                let node = GraphNode {
+                    module_code: module.module_code.clone(),
+                    semester_index: Some(sem_index),
+                    status: Some(module.status.clone()),
+                };
+                match positions.get(&module.module_code) {
+                    Some(&position) => graph.nodes[position] = node,
+                    None => {
+                        positions.insert(module.module_code.clone(), graph.nodes.len());
+                        graph.nodes.push(node);
+                    }
+                }
+            }
+        }
+
+        let planned: Vec<String> = graph.nodes.iter().map(|n| n.module_code.clone()).collect();
+        let mut seen: HashSet<GraphEdge> = HashSet::new();
+
+        for code in &planned {
+            let Some(tree) = registry
+                .get_module(code)
+                .and_then(|m| m.prereq_tree.as_ref())
+            else {
+                continue;
+            };
+
+            for prereq in tree.module_codes() {
+                let matches: Vec<&String> = match prereq.strip_suffix('%') {
+                    Some(prefix) => planned
+                        .iter()
+                        .filter(|p| p.starts_with(prefix) && *p != code)
+                        .collect(),
+                    None => planned.iter().filter(|p| **p == prereq).collect(),
+                };
+
+                let sources = if matches.is_empty() {
+                    if !positions.contains_key(&prereq) {
+                        positions.insert(prereq.clone(), graph.nodes.len());
+                        graph.nodes.push(GraphNode {
+                            module_code: prereq.clone(),
+                            semester_index: None,
+                            status: None,
+                        });
+                    }
+                    vec![prereq]
+                } else {
+                    matches.into_iter().cloned().collect()
+                };
+
+                for from in sources {
+                    let edge = GraphEdge {
+                        from,
+                        to: code.clone(),
+                    };
+                    if seen.insert(edge.clone()) {
+                        graph.edges.push(edge);
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Renders the graph in Graphviz DOT, clustering modules by semester.
+    pub fn to_dot(&self, plan: &Plan) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "digraph \"{}\" {{", escape_dot(&plan.name));
+        let _ = writeln!(out, "    rankdir=LR;");
+        let _ = writeln!(out, "    node [shape=box, style=\"rounded,filled\"];");
+
+        for (sem_index, semester) in plan.semesters.iter().enumerate() {
+            let nodes: Vec<&GraphNode> = self
+                .nodes
+                .iter()
+                .filter(|n| n.semester_index == Some(sem_index))
+                .collect();
+            if nodes.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(out, "    subgraph cluster_{} {{", sem_index);
+            let _ = writeln!(
+                out,
+                "        label=\"{} Semester {}\";",
+                semester.year, semester.semester
+            );
+            for node in nodes {
+                let _ = writeln!(
+                    out,
+                    "        \"{}\" [fillcolor=\"{}\"];",
+                    escape_dot(&node.module_code),
+                    dot_color(node.status.as_ref())
+                );
+            }
+            let _ = writeln!(out, "    }}");
+        }
+
+        for node in self.nodes.iter().filter(|n| n.semester_index.is_none()) {
+            let _ = writeln!(
+                out,
+                "    \"{}\" [fillcolor=\"{}\", style=\"rounded,dashed\"];",
+                escape_dot(&node.module_code),
+                dot_color(None)
+            );
+        }
+
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "    \"{}\" -> \"{}\";",
+                escape_dot(&edge.from),
+                escape_dot(&edge.to)
+            );
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the graph as a Mermaid flowchart with one subgraph per semester.
+    pub fn to_mermaid(&self, plan: &Plan) -> String {
+        let mut out = String::from("flowchart LR\n");
+
+        for (sem_index, semester) in plan.semesters.iter().enumerate() {
+            let nodes: Vec<&GraphNode> = self
+                .nodes
+                .iter()
+                .filter(|n| n.semester_index == Some(sem_index))
+                .collect();
+            if nodes.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(
+                out,
+                "    subgraph S{}[\"{} Semester {}\"]",
+                sem_index + 1,
+                semester.year,
+                semester.semester
+            );
+            for node in nodes {
+                let _ = writeln!(
+                    out,
+                    "        {}[\"{}\"]",
+                    mermaid_id(&node.module_code),
+                    node.module_code
+                );
+            }
+            let _ = writeln!(out, "    end");
+        }
+
+        for node in self.nodes.iter().filter(|n| n.semester_index.is_none()) {
+            let _ = writeln!(
+                out,
+                "    {}[\"{}\"]",
+                mermaid_id(&node.module_code),
+                node.module_code
+            );
+        }
+
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "    {} --> {}",
+                mermaid_id(&edge.from),
+                mermaid_id(&edge.to)
+            );
+        }
+
+        let _ = writeln!(out, "    classDef planned fill:#ffffff,stroke:#555555");
+        let _ = writeln!(out, "    classDef current fill:#bbdefb,stroke:#1565c0");
+        let _ = writeln!(out, "    classDef completed fill:#c8e6c9,stroke:#2e7d32");
+        let _ = writeln!(out, "    classDef failed fill:#ffcdd2,stroke:#c62828");
+        let _ = writeln!(
+            out,
+            "    classDef external fill:#eeeeee,stroke:#9e9e9e,stroke-dasharray:4"
+        );
+
+        for class in ["planned", "current", "completed", "failed", "external"] {
+            let ids: Vec<String> = self
+                .nodes
+                .iter()
+                .filter(|n| status_class(n.status.as_ref()) == class)
+                .map(|n| mermaid_id(&n.module_code))
+                .collect();
+            if !ids.is_empty() {
+                let _ = writeln!(out, "    class {} {}", ids.join(","), class);
+            }
+        }
+
+        out
+    }
+}
+
+/// Renders a module's prerequisite tree for the terminal, expanding each
+/// prerequisite's own prerequisites. Modules in `passed` are ticked; a
+/// module already on the current branch is marked as a cycle instead of
+/// being expanded again.
+pub fn render_prereq_tree(
+    registry: &ModuleRegistry,
+    module_code: &str,
+    passed: &HashSet<String>,
+) -> String {
+    let mut out = format!("{}\n", label(module_code, passed));
+    let mut path = vec![module_code.to_string()];
+
+    match registry
+        .get_module(module_code)
+        .and_then(|m| m.prereq_tree.as_ref())
+    {
+        Some(tree) => render_tree(registry, tree, passed, "", true, &mut path, &mut out),
+        None => out.push_str("└── (no prerequisites)\n"),
+    }
+
+    out
+}
+
+fn render_tree(
+    registry: &ModuleRegistry,
+    tree: &PrereqTree,
+    passed: &HashSet<String>,
+    prefix: &str,
+    last: bool,
+    path: &mut Vec<String>,
+    out: &mut String,
+) {
+    let branch = if last { "└── " } else { "├── " };
+    let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+
+    let (heading, children) = match tree {
+        PrereqTree::And { and } => ("all of", and),
+        PrereqTree::Or { or } => ("one of", or),
+        PrereqTree::ModuleCode(_) | PrereqTree::String(_) => {
+            let code = tree.module_codes().remove(0);
+
+            if path.contains(&code) {
+                let _ = writeln!(out, "{}{}{} (cycle)", prefix, branch, code);
+                return;
+            }
+
+            let _ = writeln!(out, "{}{}{}", prefix, branch, label(&code, passed));
+
+            if let Some(subtree) = registry
+                .get_module(&code)
+                .and_then(|m| m.prereq_tree.as_ref())
+            {
+                path.push(code);
+                render_tree(registry, subtree, passed, &child_prefix, true, path, out);
+                path.pop();
+            }
+            return;
+        }
+    };
+
+    let _ = writeln!(out, "{}{}{}", prefix, branch, heading);
+    for (i, child) in children.iter().enumerate() {
+        render_tree(
+            registry,
+            child,
+            passed,
+            &child_prefix,
+            i + 1 == children.len(),
+            path,
+            out,
+        );
+    }
+}
+
+fn label(code: &str, passed: &HashSet<String>) -> String {
+    let done = match code.strip_suffix('%') {
+        Some(prefix) => passed.iter().any(|p| p.starts_with(prefix)),
+        None => passed.contains(code),
+    };
+    if done {
+        format!("{} ✓", code)
+    } else {
+        code.to_string()
+    }
+}
+
+fn status_class(status: Option<&ModuleStatus>) -> &'static str {
+    match status {
+        Some(ModuleStatus::Planned) => "planned",
+        Some(ModuleStatus::Current) => "current",
+        Some(ModuleStatus::Completed) => "completed",
+        Some(ModuleStatus::Failed) => "failed",
+        None => "external",
+    }
+}
+
+fn dot_color(status: Option<&ModuleStatus>) -> &'static str {
+    match status {
+        Some(ModuleStatus::Planned) => "white",
+        Some(ModuleStatus::Current) => "lightblue",
+        Some(ModuleStatus::Completed) => "palegreen",
+        Some(ModuleStatus::Failed) => "lightcoral",
+        None => "gray90",
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_id(code: &str) -> String {
+    code.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
@@ -0,0 +1,284 @@
+use crate::models::{ModuleRegistry, Plan, PlanChange, PlanEdit};
+use crate::plan_ops;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+
+/// How many edits a plan's persisted log keeps.
+pub const MAX_LOGGED_EDITS: usize = 50;
+
+/// Undo/redo for an editing session. The undo stack is the plan's own
+/// `edit_log`, so edits saved in earlier sessions can still be undone; the
+/// redo stack lives only as long as the session.
+#[derive(Debug, Default)]
+pub struct EditHistory {
+    redo: Vec<PlanEdit>,
+}
+
+impl EditHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Logs an edit that has already been applied. A new edit discards
+    /// anything that could have been redone.
+    pub fn record(&mut self, plan: &mut Plan, changes: Vec<PlanChange>) {
+        if changes.is_empty() {
+            return;
+        }
+        record(plan, changes);
+        self.redo.clear();
+    }
+
+    pub fn next_undo<'a>(&self, plan: &'a Plan) -> Option<&'a PlanEdit> {
+        plan.edit_log.last()
+    }
+
+    pub fn next_redo(&self) -> Option<&PlanEdit> {
+        self.redo.last()
+    }
+
+    /// Reverts the most recent logged edit.
+    pub fn undo(&mut self, plan: &mut Plan, registry: &ModuleRegistry) -> Result<Option<PlanEdit>> {
+        let Some(edit) = plan.edit_log.last().cloned() else {
+            return Ok(None);
+        };
+
+        revert(plan, registry, &edit)?;
+        plan.edit_log.pop();
+        self.redo.push(edit.clone());
+        Ok(Some(edit))
+    }
+
+    /// Reapplies the most recently undone edit.
+    pub fn redo(&mut self, plan: &mut Plan, registry: &ModuleRegistry) -> Result<Option<PlanEdit>> {
+        let Some(edit) = self.redo.last().cloned() else {
+            return Ok(None);
+        };
+
+        reapply(plan, registry, &edit)?;
+        self.redo.pop();
+        plan.edit_log.push(edit.clone());
+        Ok(Some(edit))
+    }
+}
+
+/// Appends an applied edit to the plan's log, dropping the oldest entries
+/// beyond `MAX_LOGGED_EDITS`.
+pub fn record(plan: &mut Plan, changes: Vec<PlanChange>) {
+    if changes.is_empty() {
+        return;
+    }
+
+    plan.edit_log.push(PlanEdit {
+        description: describe(&changes),
+        changes,
+        made_at: Utc::now(),
+    });
+
+    let excess = plan.edit_log.len().saturating_sub(MAX_LOGGED_EDITS);
+    plan.edit_log.drain(..excess);
+}
+
+/// Undoes the last `count` logged edits, returning them most recent first.
+/// Stops early if the log runs out.
+pub fn undo_logged(
+    plan: &mut Plan,
+    registry: &ModuleRegistry,
+    count: usize,
+) -> Result<Vec<PlanEdit>> {
+    let mut history = EditHistory::new();
+    let mut undone = Vec::new();
+
+    for _ in 0..count {
+        match history.undo(plan, registry)? {
+            Some(edit) => undone.push(edit),
+            None => break,
+        }
+    }
+
+    Ok(undone)
+}
+
+/// Reverts every change in an edit, last change first. The plan is left
+/// untouched if any change no longer applies.
+pub fn revert(plan: &mut Plan, registry: &ModuleRegistry, edit: &PlanEdit) -> Result<()> {
+    let mut working = plan.clone();
+    for change in edit.changes.iter().rev() {
+        revert_change(&mut working, registry, change)
+            .map_err(|e| anyhow!("Cannot undo '{}': {}", edit.description, e))?;
+    }
+    *plan = working;
+    Ok(())
+}
+
+/// Applies every change in an edit again, in order. The plan is left
+/// untouched if any change no longer applies.
+pub fn reapply(plan: &mut Plan, registry: &ModuleRegistry, edit: &PlanEdit) -> Result<()> {
+    let mut working = plan.clone();
+    for change in &edit.changes {
+        reapply_change(&mut working, registry, change)
+            .map_err(|e| anyhow!("Cannot redo '{}': {}", edit.description, e))?;
+    }
+    *plan = working;
+    Ok(())
+}
+
+fn revert_change(plan: &mut Plan, registry: &ModuleRegistry, change: &PlanChange) -> Result<()> {
+    match change {
+        PlanChange::Added {
+            module_code,
+            semester_index,
+        } => {
+            plan_ops::remove_module(plan, registry, *semester_index, module_code)?;
+        }
+        PlanChange::Removed {
+            semester_index,
+            position,
+            module,
+            ..
+        } => {
+            let semester = plan
+                .semesters
+                .get_mut(*semester_index)
+                .ok_or_else(|| anyhow!("semester {} no longer exists", semester_index + 1))?;
+            let position = (*position).min(semester.modules.len());
+            semester.edit_units(registry, |semester| {
+                semester.modules.insert(position, module.clone())
+            });
+        }
+        PlanChange::Moved {
+            module_code,
+            from_semester,
+            to_semester,
+        } => {
+            plan_ops::move_module(plan, registry, *to_semester, *from_semester, module_code)?;
+        }
+        PlanChange::StatusChanged {
+            module_code,
+            semester_index,
+            from,
+            ..
+        } => {
+            plan_ops::set_status(plan, module_code, Some(*semester_index), from.clone())?;
+        }
+        PlanChange::GradeSet {
+            module_code,
+            semester_index,
+            previous,
+            ..
+        } => {
+            plan_ops::set_grade(plan, module_code, Some(*semester_index), previous.clone())?;
+        }
+        PlanChange::ActivityAdded {
+            semester_index,
+            activity,
+        } => {
+            let semester = plan
+                .semesters
+                .get_mut(*semester_index)
+                .ok_or_else(|| anyhow!("semester {} no longer exists", semester_index + 1))?;
+            let position = semester
+                .special_activities
+                .iter()
+                .rposition(|a| a == activity)
+                .ok_or_else(|| anyhow!("activity '{}' is gone", activity.description))?;
+            semester.edit_units(registry, |semester| {
+                semester.special_activities.remove(position)
+            });
+        }
+        PlanChange::SemesterAppended { year, semester } => {
+            let last = plan
+                .semesters
+                .last()
+                .filter(|s| &s.year == year && s.semester == *semester)
+                .ok_or_else(|| anyhow!("{} Semester {} is no longer last", year, semester))?;
+            if !last.modules.is_empty() || !last.special_activities.is_empty() {
+                return Err(anyhow!("{} Semester {} is not empty", year, semester));
+            }
+            plan.semesters.pop();
+        }
+        PlanChange::MetadataChanged { field, from, .. } => {
+            plan_ops::set_field(plan, *field, from.clone())?;
+        }
+        PlanChange::ClassesSelected {
+            module_code,
+            semester_index,
+            from,
+            ..
+        } => {
+            plan_ops::select_classes(plan, *semester_index, module_code, from.clone())?;
+        }
+    }
+    Ok(())
+}
+
+fn reapply_change(plan: &mut Plan, registry: &ModuleRegistry, change: &PlanChange) -> Result<()> {
+    match change {
+        PlanChange::Added {
+            module_code,
+            semester_index,
+        } => {
+            plan_ops::add_module(plan, registry, *semester_index, module_code)?;
+        }
+        PlanChange::Removed {
+            module_code,
+            semester_index,
+            ..
+        } => {
+            plan_ops::remove_module(plan, registry, *semester_index, module_code)?;
+        }
+        PlanChange::Moved {
+            module_code,
+            from_semester,
+            to_semester,
+        } => {
+            plan_ops::move_module(plan, registry, *from_semester, *to_semester, module_code)?;
+        }
+        PlanChange::StatusChanged {
+            module_code,
+            semester_index,
+            to,
+            ..
+        } => {
+            plan_ops::set_status(plan, module_code, Some(*semester_index), to.clone())?;
+        }
+        PlanChange::GradeSet {
+            module_code,
+            semester_index,
+            grade,
+            ..
+        } => {
+            plan_ops::set_grade(plan, module_code, Some(*semester_index), grade.clone())?;
+        }
+        PlanChange::ActivityAdded {
+            semester_index,
+            activity,
+        } => {
+            plan_ops::add_activity(plan, registry, *semester_index, activity.clone())?;
+        }
+        PlanChange::SemesterAppended { .. } => {
+            plan.push_next_semester();
+        }
+        PlanChange::MetadataChanged { field, to, .. } => {
+            plan_ops::set_field(plan, *field, to.clone())?;
+        }
+        PlanChange::ClassesSelected {
+            module_code,
+            semester_index,
+            to,
+            ..
+        } => {
+            plan_ops::select_classes(plan, *semester_index, module_code, to.clone())?;
+        }
+    }
+    Ok(())
+}
+
+/// Short label for an edit, used in menus and the persisted log.
+fn describe(changes: &[PlanChange]) -> String {
+    match changes {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [first, rest @ ..] => format!("{} (+{} more)", first, rest.len()),
+    }
+}
@@ -1,5 +1,14 @@
 pub mod api;
 pub mod commands;
+pub mod critical_path;
+pub mod diff;
+pub mod graph;
+pub mod history;
+pub mod merge;
+pub mod migrate;
 pub mod models;
+pub mod plan_ops;
+pub mod repair;
 pub mod storage;
+pub mod transcript;
 pub mod validation;
@@ -0,0 +1,250 @@
+use crate::diff::SemesterKey;
+use crate::models::{Plan, SemesterPlan};
+use anyhow::{anyhow, Result};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Which copy wins a conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeSide {
+    /// The plan already in storage.
+    Ours,
+    /// The copy being imported.
+    Theirs,
+}
+
+/// A part of the plan both copies changed differently since the ancestor.
+#[derive(Debug, Clone)]
+pub enum MergeConflict {
+    Semester {
+        key: SemesterKey,
+        base: Option<SemesterPlan>,
+        ours: Option<SemesterPlan>,
+        theirs: Option<SemesterPlan>,
+    },
+    Field {
+        field: String,
+        base: Option<String>,
+        ours: Option<String>,
+        theirs: Option<String>,
+    },
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeConflict::Semester {
+                key, ours, theirs, ..
+            } => write!(
+                f,
+                "{}: local [{}] vs imported [{}]",
+                key,
+                semester_summary(ours.as_ref()),
+                semester_summary(theirs.as_ref())
+            ),
+            MergeConflict::Field {
+                field,
+                ours,
+                theirs,
+                ..
+            } => write!(
+                f,
+                "{}: local '{}' vs imported '{}'",
+                field,
+                ours.as_deref().unwrap_or(""),
+                theirs.as_deref().unwrap_or("")
+            ),
+        }
+    }
+}
+
+/// The outcome of `three_way_merge`. `plan` holds every non-conflicting
+/// change from both sides, with our side of each conflict until it is
+/// resolved.
+#[derive(Debug, Clone)]
+pub struct PlanMerge {
+    pub plan: Plan,
+    pub conflicts: Vec<MergeConflict>,
+    /// Semesters and fields taken from the imported copy without conflict.
+    pub merged_from_theirs: Vec<String>,
+}
+
+impl PlanMerge {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Settles one conflict, removing it from the list.
+    pub fn resolve(&mut self, index: usize, side: MergeSide) -> Result<()> {
+        if index >= self.conflicts.len() {
+            return Err(anyhow!("No merge conflict #{}", index + 1));
+        }
+
+        let conflict = self.conflicts.remove(index);
+        apply_resolution(&mut self.plan, conflict, side);
+        Ok(())
+    }
+
+    pub fn resolve_all(&mut self, side: MergeSide) {
+        for conflict in std::mem::take(&mut self.conflicts) {
+            apply_resolution(&mut self.plan, conflict, side);
+        }
+    }
+}
+
+fn apply_resolution(plan: &mut Plan, conflict: MergeConflict, side: MergeSide) {
+    if side == MergeSide::Ours {
+        return;
+    }
+    match conflict {
+        MergeConflict::Semester { key, theirs, .. } => set_semester(plan, &key, theirs),
+        MergeConflict::Field { field, theirs, .. } => set_field(plan, &field, theirs),
+    }
+}
+
+/// Merges two copies of a plan that diverged from `base`. Metadata fields
+/// and whole semesters (matched by term) changed on only one side are taken
+/// from that side; those changed differently on both sides are conflicts.
+///
+/// The result keeps our plan's ID and history but drops its undo log, which
+/// no longer describes how the merged plan came about. Semester totals are
+/// left to the caller to refresh.
+pub fn three_way_merge(base: &Plan, ours: &Plan, theirs: &Plan) -> PlanMerge {
+    let mut merge = PlanMerge {
+        plan: ours.clone(),
+        conflicts: Vec::new(),
+        merged_from_theirs: Vec::new(),
+    };
+    merge.plan.edit_log.clear();
+
+    for field in ["name", "target graduation", "notes"] {
+        let (base, ours, theirs) = (
+            field_value(base, field),
+            field_value(ours, field),
+            field_value(theirs, field),
+        );
+        match merge_values(&base, &ours, &theirs) {
+            Resolution::Unchanged | Resolution::Ours => {}
+            Resolution::Theirs => {
+                set_field(&mut merge.plan, field, theirs);
+                merge.merged_from_theirs.push(field.to_string());
+            }
+            Resolution::Conflict => merge.conflicts.push(MergeConflict::Field {
+                field: field.to_string(),
+                base,
+                ours,
+                theirs,
+            }),
+        }
+    }
+
+    let keys: BTreeSet<SemesterKey> = [base, ours, theirs]
+        .iter()
+        .flat_map(|plan| plan.semesters.iter().map(SemesterKey::of))
+        .collect();
+
+    for key in keys {
+        let (base, ours, theirs) = (
+            find_semester(base, &key),
+            find_semester(ours, &key),
+            find_semester(theirs, &key),
+        );
+        let contents = |s: &Option<SemesterPlan>| {
+            s.as_ref()
+                .map(|s| (s.modules.clone(), s.special_activities.clone()))
+        };
+
+        match merge_values(&contents(&base), &contents(&ours), &contents(&theirs)) {
+            Resolution::Unchanged | Resolution::Ours => {}
+            Resolution::Theirs => {
+                merge.merged_from_theirs.push(key.to_string());
+                set_semester(&mut merge.plan, &key, theirs);
+            }
+            Resolution::Conflict => merge.conflicts.push(MergeConflict::Semester {
+                key,
+                base,
+                ours,
+                theirs,
+            }),
+        }
+    }
+
+    merge
+}
+
+enum Resolution {
+    Unchanged,
+    Ours,
+    Theirs,
+    Conflict,
+}
+
+fn merge_values<T: PartialEq>(base: &T, ours: &T, theirs: &T) -> Resolution {
+    if ours == theirs {
+        Resolution::Unchanged
+    } else if base == theirs {
+        Resolution::Ours
+    } else if base == ours {
+        Resolution::Theirs
+    } else {
+        Resolution::Conflict
+    }
+}
+
+fn field_value(plan: &Plan, field: &str) -> Option<String> {
+    match field {
+        "name" => Some(plan.name.clone()),
+        "target graduation" => Some(plan.target_graduation.clone()),
+        "notes" => plan.notes.clone(),
+        _ => None,
+    }
+}
+
+fn set_field(plan: &mut Plan, field: &str, value: Option<String>) {
+    match field {
+        "name" => plan.name = value.unwrap_or_default(),
+        "target graduation" => plan.target_graduation = value.unwrap_or_default(),
+        "notes" => plan.notes = value,
+        _ => {}
+    }
+}
+
+fn find_semester(plan: &Plan, key: &SemesterKey) -> Option<SemesterPlan> {
+    plan.semesters
+        .iter()
+        .find(|s| SemesterKey::of(s) == *key)
+        .cloned()
+}
+
+/// Replaces, inserts or removes a semester, keeping semesters in term order.
+fn set_semester(plan: &mut Plan, key: &SemesterKey, semester: Option<SemesterPlan>) {
+    plan.semesters.retain(|s| SemesterKey::of(s) != *key);
+    if let Some(semester) = semester {
+        let position = plan
+            .semesters
+            .iter()
+            .position(|s| SemesterKey::of(s) > *key)
+            .unwrap_or(plan.semesters.len());
+        plan.semesters.insert(position, semester);
+    }
+}
+
+fn semester_summary(semester: Option<&SemesterPlan>) -> String {
+    match semester {
+        None => "removed".to_string(),
+        Some(semester) => {
+            let mut items: Vec<&str> = semester
+                .modules
+                .iter()
+                .map(|m| m.module_code.as_str())
+                .collect();
+            items.extend(
+                semester
+                    .special_activities
+                    .iter()
+                    .map(|a| a.description.as_str()),
+            );
+            items.join(", ")
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::models::{Plan, Student};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// What `assign_student_ids` changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StudentIdMigration {
+    /// `(name, new ID)` for every profile that was given an ID.
+    pub assigned: Vec<(String, String)>,
+    /// Plans whose `student_id` was rewritten from a name to an ID.
+    pub relinked: Vec<String>,
+    /// Plans naming a student shared by several profiles; left unchanged
+    /// because the right profile cannot be told apart.
+    pub ambiguous: Vec<String>,
+}
+
+impl StudentIdMigration {
+    pub fn is_empty(&self) -> bool {
+        self.assigned.is_empty() && self.relinked.is_empty() && self.ambiguous.is_empty()
+    }
+}
+
+/// Gives every profile without an ID a new UUID and points plans that still
+/// refer to a student by name at that student's ID. Plans already holding a
+/// known ID are left alone, so running the migration twice is harmless.
+pub fn assign_student_ids(students: &mut [Student], plans: &mut [Plan]) -> StudentIdMigration {
+    let mut migration = StudentIdMigration::default();
+
+    for student in students.iter_mut().filter(|s| s.id.is_empty()) {
+        student.id = Uuid::new_v4().to_string();
+        migration
+            .assigned
+            .push((student.name.clone(), student.id.clone()));
+    }
+
+    let mut by_name: HashMap<&str, Vec<&str>> = HashMap::new();
+    for student in students.iter() {
+        by_name
+            .entry(student.name.as_str())
+            .or_default()
+            .push(student.id.as_str());
+    }
+
+    for plan in plans.iter_mut() {
+        if students.iter().any(|s| s.id == plan.student_id) {
+            continue;
+        }
+        match by_name.get(plan.student_id.as_str()).map(Vec::as_slice) {
+            Some([id]) => {
+                plan.student_id = id.to_string();
+                migration.relinked.push(plan.id.clone());
+            }
+            Some(_) => migration.ambiguous.push(plan.id.clone()),
+            None => {}
+        }
+    }
+
+    migration
+}
@@ -0,0 +1,102 @@
+use super::Plan;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Name given to the original plan once it has been forked.
+pub const MAIN_BRANCH: &str = "main";
+
+/// Marks a plan as one alternative scenario among several forked from the
+/// same original plan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanBranch {
+    pub name: String,
+    /// ID of the original plan; shared by every branch forked from it.
+    pub family_id: String,
+    /// Plan this branch was forked from.
+    pub forked_from: Option<String>,
+    /// Saved version of that plan at the time of the fork.
+    pub forked_at_version: Option<u32>,
+    /// The branch the student is actually following.
+    pub primary: bool,
+}
+
+impl Plan {
+    /// Plans without branch information form a family of their own.
+    pub fn family_id(&self) -> &str {
+        self.branch
+            .as_ref()
+            .map_or(self.id.as_str(), |b| b.family_id.as_str())
+    }
+
+    pub fn branch_name(&self) -> &str {
+        self.branch
+            .as_ref()
+            .map_or(MAIN_BRANCH, |b| b.name.as_str())
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.branch.as_ref().is_none_or(|b| b.primary)
+    }
+
+    /// Creates a new branch of this plan under `id`. The branch starts as a
+    /// copy, including the edit log, so its history reaches back past the
+    /// fork. A plan forked for the first time becomes the primary `main`
+    /// branch of the family.
+    pub fn fork(&mut self, id: String, branch_name: &str, at_version: Option<u32>) -> Plan {
+        let family_id = self.family_id().to_string();
+        if self.branch.is_none() {
+            self.branch = Some(PlanBranch {
+                name: MAIN_BRANCH.to_string(),
+                family_id: family_id.clone(),
+                forked_from: None,
+                forked_at_version: None,
+                primary: true,
+            });
+        }
+
+        let now = Utc::now();
+        let mut branch = self.clone();
+        branch.id = id;
+        branch.name = format!("{} ({})", self.name, branch_name);
+        branch.created_at = now;
+        branch.modified_at = now;
+        branch.base_version = None;
+        branch.branch = Some(PlanBranch {
+            name: branch_name.to_string(),
+            family_id,
+            forked_from: Some(self.id.clone()),
+            forked_at_version: at_version,
+            primary: false,
+        });
+        branch
+    }
+}
+
+/// Makes `plan_id` the primary branch of its family, returning the IDs of
+/// the plans whose branch information changed.
+pub fn promote_branch(family: &mut [Plan], plan_id: &str) -> Result<Vec<String>> {
+    if !family.iter().any(|p| p.id == plan_id) {
+        return Err(anyhow!("Plan {} is not a branch of this family", plan_id));
+    }
+
+    let mut changed = Vec::new();
+    for plan in family.iter_mut() {
+        let primary = plan.id == plan_id;
+        let family_id = plan.family_id().to_string();
+        let branch = plan.branch.get_or_insert_with(|| PlanBranch {
+            name: MAIN_BRANCH.to_string(),
+            family_id,
+            forked_from: None,
+            forked_at_version: None,
+            primary: true,
+        });
+
+        if branch.primary != primary {
+            branch.primary = primary;
+            changed.push(plan.id.clone());
+        }
+    }
+
+    Ok(changed)
+}
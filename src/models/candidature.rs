@@ -0,0 +1,108 @@
+use super::CandidatureType;
+use anyhow::{anyhow, Error};
+use std::fmt;
+use std::str::FromStr;
+
+/// Candidature rules for a programme type, in regular semesters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidatureRules {
+    /// Semesters a student normally takes to graduate.
+    pub normal_semesters: u8,
+    /// Maximum candidature; plans must not extend past this.
+    pub max_semesters: u8,
+    /// Units per semester for a normal load.
+    pub normal_units_per_semester: u32,
+    /// Units per semester above which an overload is required.
+    pub max_units_per_semester: u32,
+    /// Special terms (3 = Special Term I, 4 = Special Term II) scheduled as
+    /// part of the programme's normal study plan.
+    pub special_terms: Vec<u8>,
+}
+
+impl CandidatureType {
+    pub const ALL: [CandidatureType; 5] = [
+        CandidatureType::Standard,
+        CandidatureType::DoubleHonours,
+        CandidatureType::DoubleDegreeProgramme,
+        CandidatureType::ConcurrentDegree,
+        CandidatureType::EngineeringScholars,
+    ];
+
+    pub fn rules(&self) -> CandidatureRules {
+        match self {
+            CandidatureType::Standard => CandidatureRules {
+                normal_semesters: 8,
+                max_semesters: 10,
+                normal_units_per_semester: 20,
+                max_units_per_semester: 23,
+                special_terms: Vec::new(),
+            },
+            CandidatureType::DoubleHonours => CandidatureRules {
+                normal_semesters: 10,
+                max_semesters: 12,
+                normal_units_per_semester: 20,
+                max_units_per_semester: 25,
+                special_terms: Vec::new(),
+            },
+            CandidatureType::DoubleDegreeProgramme => CandidatureRules {
+                normal_semesters: 10,
+                max_semesters: 12,
+                normal_units_per_semester: 22,
+                max_units_per_semester: 27,
+                special_terms: vec![3],
+            },
+            CandidatureType::ConcurrentDegree => CandidatureRules {
+                normal_semesters: 10,
+                max_semesters: 12,
+                normal_units_per_semester: 20,
+                max_units_per_semester: 25,
+                special_terms: vec![3],
+            },
+            CandidatureType::EngineeringScholars => CandidatureRules {
+                normal_semesters: 8,
+                max_semesters: 10,
+                normal_units_per_semester: 22,
+                max_units_per_semester: 27,
+                special_terms: Vec::new(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for CandidatureType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CandidatureType::Standard => "Standard",
+            CandidatureType::DoubleHonours => "Double Honours",
+            CandidatureType::DoubleDegreeProgramme => "Double Degree Programme",
+            CandidatureType::ConcurrentDegree => "Concurrent Degree",
+            CandidatureType::EngineeringScholars => "Engineering Scholars",
+        })
+    }
+}
+
+impl FromStr for CandidatureType {
+    type Err = Error;
+
+    /// Accepts the display name or a short form such as `ddp` or
+    /// `double-honours`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect();
+
+        match normalized.as_str() {
+            "standard" => Ok(CandidatureType::Standard),
+            "doublehonours" | "dh" => Ok(CandidatureType::DoubleHonours),
+            "doubledegreeprogramme" | "doubledegree" | "ddp" => {
+                Ok(CandidatureType::DoubleDegreeProgramme)
+            }
+            "concurrentdegree" | "cdp" => Ok(CandidatureType::ConcurrentDegree),
+            "engineeringscholars" | "esp" => Ok(CandidatureType::EngineeringScholars),
+            _ => Err(anyhow!("Unknown candidature type '{}'", s.trim())),
+        }
+    }
+}
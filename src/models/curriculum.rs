@@ -1,8 +1,14 @@
+use super::{ModuleRegistry, Student, Units};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Curriculum {
     pub name: String,
+    /// Faculty offering the programme, used to group majors when creating a
+    /// student profile.
+    #[serde(default)]
+    pub faculty: Option<String>,
     pub academic_year: String,
     pub total_units_required: u32,
     pub max_level1000_units: u32,
@@ -22,6 +28,8 @@ pub enum Requirement {
         module_code: String,
         name: String,
         units: u32,
+        #[serde(default)]
+        on_exemption: ExemptionPolicy,
     },
     ModuleGroup {
         name: String,
@@ -37,3 +45,141 @@ pub enum Requirement {
         department_constraint: Option<Vec<String>>,
     },
 }
+
+/// What happens to a fixed module requirement when the student is exempted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExemptionPolicy {
+    /// The requirement is met and its units count toward graduation.
+    #[default]
+    Credited,
+    /// The requirement is met and its units are removed from the total required.
+    Waived,
+    /// The requirement is met but its units must be made up with unrestricted
+    /// electives.
+    ReplacedByElectives,
+}
+
+impl Curriculum {
+    /// Returns the exemption policy of the fixed module requirement for a module.
+    pub fn exemption_policy(&self, module_code: &str) -> Option<ExemptionPolicy> {
+        self.components
+            .iter()
+            .flat_map(|c| c.requirements.iter())
+            .find_map(|r| match r {
+                Requirement::FixedModule {
+                    module_code: code,
+                    on_exemption,
+                    ..
+                } if code == module_code => Some(*on_exemption),
+                _ => None,
+            })
+    }
+
+    /// Total units the student needs, after removing waived requirements.
+    pub fn units_required_for(&self, student: &Student) -> u32 {
+        let waived: u32 = self
+            .components
+            .iter()
+            .flat_map(|c| c.requirements.iter())
+            .map(|r| match r {
+                Requirement::FixedModule {
+                    module_code,
+                    units,
+                    on_exemption: ExemptionPolicy::Waived,
+                    ..
+                } if student.exempted_modules.contains(module_code) => *units,
+                _ => 0,
+            })
+            .sum();
+
+        self.total_units_required.saturating_sub(waived)
+    }
+
+    /// Units the student earns outside the plan: advanced placement credits
+    /// plus credited exemptions. Exempted modules that are not fixed
+    /// requirements are credited with their registry units.
+    pub fn credited_units_for(&self, student: &Student, registry: &ModuleRegistry) -> Units {
+        let exempted: Units = student
+            .exempted_modules
+            .iter()
+            .map(|code| match self.exemption_policy(code) {
+                Some(ExemptionPolicy::Credited) => Units::from(self.fixed_module_units(code)),
+                Some(_) => Units::ZERO,
+                None => registry
+                    .get_module(code)
+                    .map(|m| m.units())
+                    .unwrap_or_default(),
+            })
+            .sum();
+
+        Units::from(student.advanced_placement_credits) + exempted
+    }
+
+    /// Units of exempted fixed modules that must be replaced with electives.
+    pub fn replacement_units_for(&self, student: &Student) -> u32 {
+        student
+            .exempted_modules
+            .iter()
+            .filter(|code| {
+                self.exemption_policy(code) == Some(ExemptionPolicy::ReplacedByElectives)
+            })
+            .map(|code| self.fixed_module_units(code))
+            .sum()
+    }
+
+    /// Names of unmet fixed-module and module-group requirements that one of
+    /// the `unlocked` modules could count toward. `taken` holds modules that
+    /// are already passed or planned.
+    pub fn requirements_unlocked_by(
+        &self,
+        unlocked: &HashSet<String>,
+        taken: &HashSet<String>,
+        registry: &ModuleRegistry,
+    ) -> Vec<String> {
+        self.components
+            .iter()
+            .flat_map(|c| c.requirements.iter())
+            .filter_map(|r| match r {
+                Requirement::FixedModule {
+                    module_code, name, ..
+                } if unlocked.contains(module_code) && !taken.contains(module_code) => {
+                    Some(name.clone())
+                }
+                Requirement::ModuleGroup {
+                    name,
+                    min_units,
+                    possible_modules,
+                    ..
+                } => {
+                    let earned: Units = possible_modules
+                        .iter()
+                        .filter(|code| taken.contains(*code))
+                        .filter_map(|code| registry.get_module(code))
+                        .map(|m| m.units())
+                        .sum();
+                    let helps = possible_modules
+                        .iter()
+                        .any(|code| unlocked.contains(code) && !taken.contains(code));
+
+                    (earned < Units::from(*min_units) && helps).then(|| name.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn fixed_module_units(&self, module_code: &str) -> u32 {
+        self.components
+            .iter()
+            .flat_map(|c| c.requirements.iter())
+            .find_map(|r| match r {
+                Requirement::FixedModule {
+                    module_code: code,
+                    units,
+                    ..
+                } if code == module_code => Some(*units),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+}
@@ -1,17 +1,74 @@
+mod branch;
+mod candidature;
 mod curriculum;
 mod module;
 mod plan;
+mod search;
 mod student;
+mod template;
+mod units;
+mod version;
 
+pub use branch::*;
+pub use candidature::*;
 pub use curriculum::*;
 pub use module::*;
 pub use plan::*;
+pub use search::*;
 pub use student::*;
+pub use template::*;
+pub use units::*;
+pub use version::*;
 
-use std::collections::HashMap;
+use anyhow::{anyhow, Context, Result};
+use serde::de::{Deserializer, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
 
+#[derive(Clone)]
 pub struct ModuleRegistry {
+    /// The most recent known version of each module.
     modules: HashMap<String, Module>,
+    /// Versions of each module by the start year of the academic year they
+    /// were published for.
+    by_year: HashMap<String, BTreeMap<u32, Module>>,
+    /// Reverse prerequisite index: prerequisite code to the modules whose
+    /// prerequisite tree mentions it.
+    dependents: HashMap<String, HashSet<String>>,
+    /// Same as `dependents`, keyed by the prefix of `%` wildcard patterns.
+    wildcard_dependents: HashMap<String, HashSet<String>>,
+    /// Lowercase word to the modules containing it, with the weight of the
+    /// strongest field (code, title or description) it appears in.
+    search_index: HashMap<String, HashMap<String, u32>>,
+}
+
+/// Modules that become reachable once a module is completed.
+#[derive(Debug, Default, PartialEq)]
+pub struct ModuleUnlocks {
+    /// Modules whose prerequisites name the module and are now satisfied.
+    pub direct: Vec<String>,
+    /// Modules reachable afterwards through the direct unlocks.
+    pub transitive: Vec<String>,
+}
+
+impl ModuleUnlocks {
+    pub fn all(&self) -> impl Iterator<Item = &String> {
+        self.direct.iter().chain(self.transitive.iter())
+    }
+}
+
+/// What `ModuleRegistry::merge` does when both registries hold a module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keep the module already in the registry.
+    KeepExisting,
+    /// Replace it with the incoming module.
+    Overwrite,
+    /// Abort the merge without changing anything.
+    Fail,
 }
 
 impl Default for ModuleRegistry {
@@ -24,10 +81,42 @@ impl ModuleRegistry {
     pub fn new() -> Self {
         Self {
             modules: HashMap::new(),
+            by_year: HashMap::new(),
+            dependents: HashMap::new(),
+            wildcard_dependents: HashMap::new(),
+            search_index: HashMap::new(),
         }
     }
 
+    /// Adds or replaces a module without an academic year. Any per-year
+    /// history for the code is dropped, so year lookups see this version.
     pub fn add_module(&mut self, module: Module) {
+        self.by_year.remove(&module.module_code);
+        self.insert_latest(module);
+    }
+
+    /// Makes `module` the version returned by `get_module`, leaving the
+    /// per-year history alone.
+    fn insert_latest(&mut self, module: Module) {
+        if let Some(previous) = self.modules.remove(&module.module_code) {
+            self.unindex_module(&previous);
+            for code in prereq_codes(&previous) {
+                let (index, key) = self.reverse_index_entry(&code);
+                if let Some(dependents) = index.get_mut(&key) {
+                    dependents.remove(&previous.module_code);
+                }
+            }
+        }
+
+        for code in prereq_codes(&module) {
+            let (index, key) = self.reverse_index_entry(&code);
+            index
+                .entry(key)
+                .or_default()
+                .insert(module.module_code.clone());
+        }
+
+        self.index_module(&module);
         self.modules.insert(module.module_code.clone(), module);
     }
 
@@ -35,7 +124,348 @@ impl ModuleRegistry {
         self.modules.get(module_code)
     }
 
+    /// Records a module as published for an academic year such as
+    /// `"2023/2024"` (or `"2023-2024"`). The latest year's version also
+    /// becomes the one returned by `get_module`. Years that cannot be parsed
+    /// are treated like `add_module`.
+    pub fn add_module_for_year(&mut self, academic_year: &str, module: Module) {
+        let Some(year) = academic_year_start(academic_year) else {
+            self.insert_latest(module);
+            return;
+        };
+
+        let versions = self.by_year.entry(module.module_code.clone()).or_default();
+        let is_latest = versions
+            .keys()
+            .next_back()
+            .is_none_or(|&latest| year >= latest);
+        versions.insert(year, module.clone());
+
+        if is_latest || !self.modules.contains_key(&module.module_code) {
+            self.insert_latest(module);
+        }
+    }
+
+    /// Adds a whole academic year's dataset, e.g. one `moduleInfo.json`.
+    pub fn add_modules_for_year<I>(&mut self, academic_year: &str, modules: I)
+    where
+        I: IntoIterator<Item = Module>,
+    {
+        for module in modules {
+            self.add_module_for_year(academic_year, module);
+        }
+    }
+
+    /// Looks up the module as it was offered in the given academic year,
+    /// falling back to the closest earlier year on record and then to the
+    /// most recent known version.
+    pub fn get_module_for_year(&self, module_code: &str, academic_year: &str) -> Option<&Module> {
+        academic_year_start(academic_year)
+            .and_then(|year| {
+                self.by_year
+                    .get(module_code)?
+                    .range(..=year)
+                    .next_back()
+                    .map(|(_, module)| module)
+            })
+            .or_else(|| self.get_module(module_code))
+    }
+
+    /// Academic years with recorded data for a module, oldest first.
+    pub fn known_years(&self, module_code: &str) -> Vec<String> {
+        self.by_year
+            .get(module_code)
+            .map(|versions| {
+                versions
+                    .keys()
+                    .map(|year| format!("{}/{}", year, year + 1))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether the module runs in a semester of the given academic year.
+    /// Modules the registry does not know are assumed to be offered.
+    pub(crate) fn is_offered(&self, module_code: &str, year: &str, semester: u8) -> bool {
+        self.get_module_for_year(module_code, year)
+            .is_none_or(|m| m.is_offered_in(semester))
+    }
+
     pub fn contains_module(&self, module_code: &str) -> bool {
         self.modules.contains_key(module_code)
     }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// Iterates over every module in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Module> {
+        self.modules.values()
+    }
+
+    /// All module codes, sorted.
+    pub fn module_codes(&self) -> Vec<&str> {
+        let mut codes: Vec<&str> = self.modules.keys().map(|code| code.as_str()).collect();
+        codes.sort_unstable();
+        codes
+    }
+
+    /// Adds every module from `other`, resolving modules present in both
+    /// registries according to `policy`. A module's per-year history goes
+    /// with it: overwriting replaces the existing history, keeping leaves it
+    /// alone. Returns the conflicting codes, sorted.
+    pub fn merge(&mut self, other: ModuleRegistry, policy: MergePolicy) -> Result<Vec<String>> {
+        let mut conflicts: Vec<String> = other
+            .modules
+            .keys()
+            .filter(|code| self.modules.contains_key(*code))
+            .cloned()
+            .collect();
+        conflicts.sort();
+
+        if policy == MergePolicy::Fail && !conflicts.is_empty() {
+            return Err(anyhow!(
+                "{} module(s) already in the registry: {}",
+                conflicts.len(),
+                conflicts.join(", ")
+            ));
+        }
+
+        let mut other_by_year = other.by_year;
+        for (code, module) in other.modules {
+            if policy == MergePolicy::KeepExisting && self.modules.contains_key(&code) {
+                continue;
+            }
+            match other_by_year.remove(&code) {
+                Some(versions) => {
+                    self.by_year.insert(code, versions);
+                }
+                None => {
+                    self.by_year.remove(&code);
+                }
+            }
+            self.insert_latest(module);
+        }
+
+        Ok(conflicts)
+    }
+
+    /// Parses a NUSMods bulk `moduleInfo.json` (or a registry snapshot, which
+    /// shares the same layout: a JSON array of modules).
+    pub fn from_module_info_json(json: &str) -> Result<Self> {
+        let modules: Vec<Module> =
+            serde_json::from_str(json).context("Invalid module info JSON")?;
+        Ok(modules.into_iter().collect())
+    }
+
+    pub fn load_module_info(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_module_info_json(&json)
+    }
+
+    /// Modules whose prerequisite tree mentions `module_code`, either by name
+    /// or through a wildcard pattern such as `IE21%`. Sorted by code.
+    pub fn direct_dependents(&self, module_code: &str) -> Vec<String> {
+        let wildcard = self
+            .wildcard_dependents
+            .iter()
+            .filter(|(prefix, _)| module_code.starts_with(prefix.as_str()))
+            .flat_map(|(_, dependents)| dependents.iter());
+
+        let mut dependents: Vec<String> = self
+            .dependents
+            .get(module_code)
+            .into_iter()
+            .flatten()
+            .chain(wildcard)
+            .filter(|code| *code != module_code)
+            .cloned()
+            .collect();
+        dependents.sort();
+        dependents.dedup();
+        dependents
+    }
+
+    /// Works out which modules become reachable once `module_code` is passed,
+    /// on top of the modules already in `passed`.
+    ///
+    /// A module counts as unlocked if its prerequisites can be met (possibly
+    /// after taking other reachable modules first) with `module_code` passed,
+    /// but not without it.
+    pub fn unlocked_by(&self, module_code: &str, passed: &HashSet<String>) -> ModuleUnlocks {
+        let mut with = passed.clone();
+        with.insert(module_code.to_string());
+        let reachable_with = self.reachable_closure(with, None);
+
+        let mut without = passed.clone();
+        without.remove(module_code);
+        let reachable_without = self.reachable_closure(without, Some(module_code));
+
+        let direct_candidates = self.direct_dependents(module_code);
+        let mut unlocks = ModuleUnlocks::default();
+
+        for code in reachable_with.difference(&reachable_without) {
+            if code == module_code || passed.contains(code) {
+                continue;
+            }
+
+            if direct_candidates.contains(code) {
+                unlocks.direct.push(code.clone());
+            } else {
+                unlocks.transitive.push(code.clone());
+            }
+        }
+
+        unlocks.direct.sort();
+        unlocks.transitive.sort();
+        unlocks
+    }
+
+    /// Every module that could eventually be taken starting from `passed`,
+    /// assuming each reachable module is passed in turn. `excluded` is never
+    /// treated as reachable.
+    fn reachable_closure(
+        &self,
+        mut passed: HashSet<String>,
+        excluded: Option<&str>,
+    ) -> HashSet<String> {
+        let mut queue: Vec<String> = passed.iter().cloned().collect();
+
+        for module in self.modules.values() {
+            if module.prereq_tree.is_none()
+                && Some(module.module_code.as_str()) != excluded
+                && passed.insert(module.module_code.clone())
+            {
+                queue.push(module.module_code.clone());
+            }
+        }
+
+        while let Some(code) = queue.pop() {
+            for dependent in self.direct_dependents(&code) {
+                if passed.contains(&dependent) || Some(dependent.as_str()) == excluded {
+                    continue;
+                }
+
+                let satisfied = self
+                    .get_module(&dependent)
+                    .and_then(|m| m.prereq_tree.as_ref())
+                    .is_some_and(|tree| tree.is_satisfied_by(&passed));
+
+                if satisfied {
+                    passed.insert(dependent.clone());
+                    queue.push(dependent);
+                }
+            }
+        }
+
+        passed
+    }
+
+    fn reverse_index_entry(
+        &mut self,
+        prereq_code: &str,
+    ) -> (&mut HashMap<String, HashSet<String>>, String) {
+        match prereq_code.strip_suffix('%') {
+            Some(prefix) => (&mut self.wildcard_dependents, prefix.to_string()),
+            None => (&mut self.dependents, prereq_code.to_string()),
+        }
+    }
+}
+
+/// Start year of an academic year written as `2023/2024`, `2023-2024`,
+/// `AY2023/24` or just `2023`.
+fn academic_year_start(academic_year: &str) -> Option<u32> {
+    let start = academic_year.find(|c: char| c.is_ascii_digit())?;
+    let digits: String = academic_year[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    (digits.len() == 4).then(|| digits.parse().ok()).flatten()
+}
+
+fn prereq_codes(module: &Module) -> Vec<String> {
+    module
+        .prereq_tree
+        .as_ref()
+        .map(|tree| tree.module_codes())
+        .unwrap_or_default()
+}
+
+impl Extend<Module> for ModuleRegistry {
+    fn extend<T: IntoIterator<Item = Module>>(&mut self, modules: T) {
+        for module in modules {
+            self.add_module(module);
+        }
+    }
+}
+
+impl FromIterator<Module> for ModuleRegistry {
+    fn from_iter<T: IntoIterator<Item = Module>>(modules: T) -> Self {
+        let mut registry = ModuleRegistry::new();
+        registry.extend(modules);
+        registry
+    }
+}
+
+impl<'a> IntoIterator for &'a ModuleRegistry {
+    type Item = &'a Module;
+    type IntoIter = std::collections::hash_map::Values<'a, String, Module>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.modules.values()
+    }
+}
+
+impl fmt::Debug for ModuleRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModuleRegistry")
+            .field("modules", &self.modules.len())
+            .finish()
+    }
+}
+
+/// Serialized as a JSON array of the latest version of each module, sorted
+/// by code, so snapshots are stable and can be loaded with
+/// `from_module_info_json`. Per-year history is not included.
+impl Serialize for ModuleRegistry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.modules.len()))?;
+        for code in self.module_codes() {
+            seq.serialize_element(&self.modules[code])?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ModuleRegistry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct RegistryVisitor;
+
+        impl<'de> Visitor<'de> for RegistryVisitor {
+            type Value = ModuleRegistry;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a list of modules")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut registry = ModuleRegistry::new();
+                while let Some(module) = seq.next_element::<Module>()? {
+                    registry.add_module(module);
+                }
+                Ok(registry)
+            }
+        }
+
+        deserializer.deserialize_seq(RegistryVisitor)
+    }
 }
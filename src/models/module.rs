@@ -1,37 +1,55 @@
-use serde::{Deserialize, Serialize};
+use super::{ModuleCredit, Units};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashSet;
 
+/// Module data as published by NUSMods. Fields are written in snake_case but
+/// NUSMods' camelCase keys are accepted, so `moduleInfo.json` and
+/// per-module API responses deserialize directly.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Module {
+    #[serde(alias = "moduleCode")]
     pub module_code: String,
     pub title: String,
     pub description: Option<String>,
-    pub module_credit: String,
+    #[serde(alias = "moduleCredit")]
+    pub module_credit: ModuleCredit,
     pub department: Option<String>,
     pub faculty: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_workload")]
     pub workload: Option<Vec<f32>>,
     pub prerequisite: Option<String>,
     pub preclusion: Option<String>,
     pub corequisite: Option<String>,
+    #[serde(default, alias = "semesterData")]
     pub semester_data: Vec<SemesterData>,
+    #[serde(alias = "prereqTree")]
     pub prereq_tree: Option<PrereqTree>,
+    #[serde(alias = "fulfillRequirements")]
     pub fulfill_requirements: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemesterData {
     pub semester: u8,
+    #[serde(alias = "examDate")]
     pub exam_date: Option<String>,
+    #[serde(alias = "examDuration")]
     pub exam_duration: Option<u32>,
     pub timetable: Option<Vec<Lesson>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Lesson {
+    #[serde(alias = "classNo")]
     pub class_no: String,
+    #[serde(alias = "lessonType")]
     pub lesson_type: String,
+    #[serde(alias = "weeks")]
     pub week_type: WeekType,
     pub day: String,
+    #[serde(alias = "startTime")]
     pub start_time: String,
+    #[serde(alias = "endTime")]
     pub end_time: String,
     pub venue: String,
 }
@@ -43,7 +61,7 @@ pub enum WeekType {
     WeekRange {
         start: String,
         end: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(skip_serializing_if = "Option::is_none", alias = "weekInterval")]
         week_interval: Option<u8>,
         #[serde(skip_serializing_if = "Option::is_none")]
         weeks: Option<Vec<u8>>,
@@ -58,3 +76,126 @@ pub enum PrereqTree {
     Or { or: Vec<PrereqTree> },
     String(String),
 }
+
+impl Module {
+    pub fn units(&self) -> Units {
+        self.module_credit.units()
+    }
+
+    /// Module level from the first digit of the code's number, e.g. 2000 for
+    /// IE2101 or CS2030S.
+    pub fn level(&self) -> Option<u32> {
+        self.module_code
+            .chars()
+            .find(|c| c.is_ascii_digit())
+            .and_then(|c| c.to_digit(10))
+            .filter(|&digit| digit > 0)
+            .map(|digit| digit * 1000)
+    }
+
+    pub fn is_offered_in(&self, semester: u8) -> bool {
+        self.semester_data
+            .iter()
+            .any(|sem_data| sem_data.semester == semester)
+    }
+}
+
+impl Lesson {
+    /// Returns the hours the lesson occupies as `(start, end)`, with the end
+    /// rounded up, e.g. `"0830"`-`"1000"` gives `(8, 10)`.
+    pub fn hour_span(&self) -> Option<(u8, u8)> {
+        let parse = |time: &str| -> Option<(u8, u8)> {
+            if time.len() != 4 {
+                return None;
+            }
+            Some((time[..2].parse().ok()?, time[2..].parse().ok()?))
+        };
+
+        let (start_hour, _) = parse(&self.start_time)?;
+        let (end_hour, end_minute) = parse(&self.end_time)?;
+        let end = if end_minute > 0 {
+            end_hour + 1
+        } else {
+            end_hour
+        };
+
+        (end > start_hour).then_some((start_hour, end))
+    }
+}
+
+impl WeekType {
+    /// Whether the lesson runs in the given teaching week. Date ranges without
+    /// an explicit week list are assumed to run every week.
+    pub fn occurs_in_week(&self, week: u8) -> bool {
+        match self {
+            WeekType::Weeks(weeks) => weeks.contains(&week),
+            WeekType::WeekRange {
+                weeks: Some(weeks), ..
+            } => weeks.contains(&week),
+            WeekType::WeekRange { .. } => true,
+        }
+    }
+}
+
+impl PrereqTree {
+    /// Returns every module code (or code pattern) mentioned in the tree.
+    pub fn module_codes(&self) -> Vec<String> {
+        match self {
+            PrereqTree::ModuleCode(code) | PrereqTree::String(code) => {
+                vec![strip_grade_suffix(code).to_string()]
+            }
+            PrereqTree::And { and } => and.iter().flat_map(|t| t.module_codes()).collect(),
+            PrereqTree::Or { or } => or.iter().flat_map(|t| t.module_codes()).collect(),
+        }
+    }
+
+    /// Evaluates the tree against a set of passed module codes.
+    ///
+    /// NUSMods leaves may carry a minimum grade suffix (`"IE1111R:D"`) and a
+    /// `%` wildcard (`"MA1505%"`); the grade is ignored and the wildcard
+    /// matches any code with the given prefix.
+    pub fn is_satisfied_by(&self, passed: &HashSet<String>) -> bool {
+        match self {
+            PrereqTree::ModuleCode(code) | PrereqTree::String(code) => {
+                leaf_matches(strip_grade_suffix(code), passed)
+            }
+            PrereqTree::And { and } => and.iter().all(|t| t.is_satisfied_by(passed)),
+            PrereqTree::Or { or } => or.iter().any(|t| t.is_satisfied_by(passed)),
+        }
+    }
+}
+
+/// NUSMods gives workload either as an array of hours or as free text such
+/// as `"2-1-1-3-3"`; text that does not split into numbers is dropped.
+fn deserialize_workload<'de, D>(deserializer: D) -> Result<Option<Vec<f32>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Workload {
+        Hours(Vec<f32>),
+        Text(String),
+    }
+
+    Ok(match Option::<Workload>::deserialize(deserializer)? {
+        Some(Workload::Hours(hours)) => Some(hours),
+        Some(Workload::Text(text)) => text
+            .split('-')
+            .map(|part| part.trim().parse::<f32>())
+            .collect::<Result<Vec<f32>, _>>()
+            .ok(),
+        None => None,
+    })
+}
+
+fn strip_grade_suffix(code: &str) -> &str {
+    code.split(':').next().unwrap_or(code)
+}
+
+fn leaf_matches(code: &str, passed: &HashSet<String>) -> bool {
+    match code.strip_suffix('%') {
+        Some(prefix) => passed.iter().any(|p| p.starts_with(prefix)),
+        None => passed.contains(code),
+    }
+}
@@ -1,6 +1,10 @@
+use super::{ModuleRegistry, PlanBranch, Units};
+use anyhow::{anyhow, Error};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Plan {
@@ -12,6 +16,17 @@ pub struct Plan {
     pub target_graduation: String,
     pub semesters: Vec<SemesterPlan>,
     pub notes: Option<String>,
+    /// Most recent edits, oldest first, so they can be undone after the
+    /// plan has been saved.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edit_log: Vec<PlanEdit>,
+    /// Saved version this copy was exported from, used as the common
+    /// ancestor when the copy is imported back and merged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_version: Option<u32>,
+    /// Set once the plan has been forked into alternative branches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<PlanBranch>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,19 +34,26 @@ pub struct SemesterPlan {
     pub year: String,
     pub semester: u8,
     pub modules: Vec<PlannedModule>,
-    pub total_units: u32,
+    /// Stored copy of `derived_units`, kept for display and older plan
+    /// files. Edits update it through `edit_units`, which keeps the credits
+    /// of modules the registry does not know.
+    #[serde(default)]
+    pub total_units: Units,
     pub special_activities: Vec<SpecialActivity>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlannedModule {
     pub module_code: String,
     pub status: ModuleStatus,
     pub grade: Option<String>,
     pub s_u_option: bool,
+    /// Chosen class number for each lesson type (e.g. "Lecture" -> "1").
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub class_selections: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ModuleStatus {
     Planned,
     Current,
@@ -39,14 +61,96 @@ pub enum ModuleStatus {
     Failed,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SpecialActivity {
     pub activity_type: ActivityType,
     pub description: String,
     pub credits: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A single reversible change made to a plan. Each variant records enough
+/// to be undone as well as reapplied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum PlanChange {
+    Added {
+        module_code: String,
+        semester_index: usize,
+    },
+    Removed {
+        module_code: String,
+        semester_index: usize,
+        position: usize,
+        module: PlannedModule,
+    },
+    Moved {
+        module_code: String,
+        from_semester: usize,
+        to_semester: usize,
+    },
+    StatusChanged {
+        module_code: String,
+        semester_index: usize,
+        from: ModuleStatus,
+        to: ModuleStatus,
+    },
+    GradeSet {
+        module_code: String,
+        semester_index: usize,
+        grade: Option<String>,
+        previous: Option<String>,
+    },
+    ActivityAdded {
+        semester_index: usize,
+        activity: SpecialActivity,
+    },
+    SemesterAppended {
+        year: String,
+        semester: u8,
+    },
+    MetadataChanged {
+        field: PlanField,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// Class selections of a module were replaced as a whole.
+    ClassesSelected {
+        module_code: String,
+        semester_index: usize,
+        from: HashMap<String, String>,
+        to: HashMap<String, String>,
+    },
+}
+
+/// A plan-level field that can be edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanField {
+    Name,
+    TargetGraduation,
+    Notes,
+}
+
+/// One user-level edit: the changes made by a single command or menu action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanEdit {
+    pub description: String,
+    pub changes: Vec<PlanChange>,
+    pub made_at: DateTime<Utc>,
+}
+
+/// A semester whose stored `total_units` disagreed with the total derived
+/// from the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitTotalMismatch {
+    pub semester_index: usize,
+    pub year: String,
+    pub semester: u8,
+    pub stored: Units,
+    pub derived: Units,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ActivityType {
     InternationalExchange,
     IndustrialAttachment,
@@ -67,17 +171,372 @@ impl Plan {
             target_graduation: String::new(),
             semesters: Vec::new(),
             notes: None,
+            edit_log: Vec::new(),
+            base_version: None,
+            branch: None,
         }
     }
 
-    pub fn total_units(&self) -> u32 {
+    /// Sum of the stored semester totals.
+    pub fn total_units(&self) -> Units {
         self.semesters.iter().map(|s| s.total_units).sum()
     }
 
+    /// Sum of every semester's `derived_units`.
+    pub fn derived_units(&self, registry: &ModuleRegistry) -> Units {
+        self.semesters
+            .iter()
+            .map(|s| s.derived_units(registry))
+            .sum()
+    }
+
+    /// Lists the semesters whose stored total is out of date. Only
+    /// semesters whose modules all resolve in the registry are checked, so
+    /// an empty or partial registry never flags a correct total.
+    pub fn check_unit_totals(&self, registry: &ModuleRegistry) -> Vec<UnitTotalMismatch> {
+        self.semesters
+            .iter()
+            .enumerate()
+            .filter_map(|(semester_index, semester)| {
+                let derived = semester.resolved_units(registry)?;
+                (semester.total_units != derived).then(|| UnitTotalMismatch {
+                    semester_index,
+                    year: semester.year.clone(),
+                    semester: semester.semester,
+                    stored: semester.total_units,
+                    derived,
+                })
+            })
+            .collect()
+    }
+
+    /// Overwrites every stale semester total with the derived value and
+    /// returns what was corrected.
+    pub fn repair_unit_totals(&mut self, registry: &ModuleRegistry) -> Vec<UnitTotalMismatch> {
+        let mismatches = self.check_unit_totals(registry);
+        for mismatch in &mismatches {
+            self.semesters[mismatch.semester_index].total_units = mismatch.derived;
+        }
+        mismatches
+    }
+
     pub fn all_modules(&self) -> HashSet<String> {
         self.semesters
             .iter()
             .flat_map(|s| s.modules.iter().map(|m| m.module_code.clone()))
             .collect()
     }
+
+    /// Returns the codes of every module marked as completed anywhere in the plan.
+    pub fn completed_modules(&self) -> HashSet<String> {
+        self.semesters
+            .iter()
+            .flat_map(|s| s.modules.iter())
+            .filter(|m| m.status == ModuleStatus::Completed)
+            .map(|m| m.module_code.clone())
+            .collect()
+    }
+
+    /// Unit-weighted CAP over every module with a letter grade, including
+    /// expected grades entered for planned modules. S/U grades, modules
+    /// taken with the S/U option and modules missing from the registry are
+    /// left out. For a retaken module only the latest graded attempt (in
+    /// plan order) counts. `None` if nothing is graded.
+    pub fn projected_cap(&self, registry: &ModuleRegistry) -> Option<f32> {
+        let mut attempts: HashMap<&str, (f32, f32)> = HashMap::new();
+
+        for semester in &self.semesters {
+            for module in semester.modules.iter().filter(|m| !m.s_u_option) {
+                let Some(point) = module.grade.as_deref().and_then(grade_point) else {
+                    continue;
+                };
+                let Some(info) = registry.get_module_for_year(&module.module_code, &semester.year)
+                else {
+                    continue;
+                };
+                attempts.insert(&module.module_code, (point, info.units().as_f32()));
+            }
+        }
+
+        let units: f32 = attempts.values().map(|(_, units)| units).sum();
+        let points: f32 = attempts.values().map(|(point, units)| point * units).sum();
+        (units > 0.0).then(|| points / units)
+    }
+
+    /// Returns the (semester index, module index) of the last occurrence of a module.
+    pub fn find_module(&self, module_code: &str) -> Option<(usize, usize)> {
+        self.semesters
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(sem_index, semester)| {
+                semester
+                    .modules
+                    .iter()
+                    .position(|m| m.module_code == module_code)
+                    .map(|module_index| (sem_index, module_index))
+            })
+    }
+
+    /// Appends an empty semester directly after the last one in the plan.
+    pub fn push_next_semester(&mut self) -> usize {
+        let next = match self.semesters.last() {
+            Some(last) => last.next_semester(),
+            None => SemesterPlan::empty(String::new(), 1),
+        };
+        self.semesters.push(next);
+        self.semesters.len() - 1
+    }
+}
+
+impl SemesterPlan {
+    pub fn empty(year: String, semester: u8) -> Self {
+        Self {
+            year,
+            semester,
+            modules: Vec::new(),
+            total_units: Units::ZERO,
+            special_activities: Vec::new(),
+        }
+    }
+
+    /// Units for this semester: each module's credit for the semester's
+    /// academic year plus special-activity credits. Modules missing from the
+    /// registry count as 0.
+    pub fn derived_units(&self, registry: &ModuleRegistry) -> Units {
+        let modules: Units = self
+            .modules
+            .iter()
+            .filter_map(|m| registry.get_module_for_year(&m.module_code, &self.year))
+            .map(|m| m.units())
+            .sum();
+        modules + self.activity_units()
+    }
+
+    /// `derived_units`, but only when the registry knows every module in
+    /// the semester. `None` for an empty registry. Used for the load-time
+    /// repair; edits go through `edit_units`.
+    pub fn resolved_units(&self, registry: &ModuleRegistry) -> Option<Units> {
+        if registry.is_empty() {
+            return None;
+        }
+        let modules = self
+            .modules
+            .iter()
+            .map(|m| {
+                registry
+                    .get_module_for_year(&m.module_code, &self.year)
+                    .map(|m| m.units())
+            })
+            .sum::<Option<Units>>()?;
+        Some(modules + self.activity_units())
+    }
+
+    pub fn activity_units(&self) -> Units {
+        self.special_activities
+            .iter()
+            .filter_map(|a| a.credits)
+            .map(Units::from)
+            .sum()
+    }
+
+    /// Applies an edit and moves the stored total by the change in
+    /// `derived_units`. Credits the registry cannot resolve stay in the
+    /// total, so an empty or partial registry never resets it.
+    pub fn edit_units<T>(
+        &mut self,
+        registry: &ModuleRegistry,
+        edit: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let before = self.derived_units(registry);
+        let result = edit(self);
+        let after = self.derived_units(registry);
+        self.total_units = (self.total_units + after).saturating_sub(before);
+        result
+    }
+
+    /// Builds the empty regular semester that follows this one, rolling the
+    /// academic year over after Semester 2.
+    pub fn next_semester(&self) -> Self {
+        if self.semester == 1 {
+            return Self::empty(self.year.clone(), 2);
+        }
+
+        let next_year = self
+            .year
+            .split('/')
+            .map(|part| part.trim().parse::<u32>())
+            .collect::<Result<Vec<u32>, _>>()
+            .ok()
+            .filter(|parts| parts.len() == 2)
+            .map(|parts| format!("{}/{}", parts[0] + 1, parts[1] + 1))
+            .unwrap_or_else(|| self.year.clone());
+
+        Self::empty(next_year, 1)
+    }
+}
+
+/// Sort key for a term: the academic year's starting year, then the
+/// semester. Unparseable years sort first.
+pub(crate) fn term_key(year: &str, semester: u8) -> (u32, u8) {
+    let start_year = year
+        .split('/')
+        .next()
+        .and_then(|y| y.trim().parse::<u32>().ok())
+        .unwrap_or(0);
+    (start_year, semester)
+}
+
+/// NUS grade point for a letter grade. Grades that do not count towards
+/// the CAP (S, U, CS, CU, IP and so on) have none.
+pub fn grade_point(grade: &str) -> Option<f32> {
+    let point = match grade.trim().to_uppercase().as_str() {
+        "A+" | "A" => 5.0,
+        "A-" => 4.5,
+        "B+" => 4.0,
+        "B" => 3.5,
+        "B-" => 3.0,
+        "C+" => 2.5,
+        "C" => 2.0,
+        "D+" => 1.5,
+        "D" => 1.0,
+        "F" => 0.0,
+        _ => return None,
+    };
+    Some(point)
+}
+
+impl fmt::Display for UnitTotalMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} Semester {}: stored total {} units, modules and activities add up to {}",
+            self.year, self.semester, self.stored, self.derived
+        )
+    }
+}
+
+impl fmt::Display for PlanChange {
+    /// Semesters are shown 1-based, as in the editor menus.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanChange::Added {
+                module_code,
+                semester_index,
+            } => write!(
+                f,
+                "Added {} to semester {}",
+                module_code,
+                semester_index + 1
+            ),
+            PlanChange::Removed {
+                module_code,
+                semester_index,
+                ..
+            } => write!(
+                f,
+                "Removed {} from semester {}",
+                module_code,
+                semester_index + 1
+            ),
+            PlanChange::Moved {
+                module_code,
+                from_semester,
+                to_semester,
+            } => write!(
+                f,
+                "Moved {} from semester {} to semester {}",
+                module_code,
+                from_semester + 1,
+                to_semester + 1
+            ),
+            PlanChange::StatusChanged {
+                module_code,
+                from,
+                to,
+                ..
+            } => write!(f, "{}: {:?} → {:?}", module_code, from, to),
+            PlanChange::GradeSet {
+                module_code, grade, ..
+            } => match grade {
+                Some(grade) => write!(f, "Set grade of {} to {}", module_code, grade),
+                None => write!(f, "Cleared grade of {}", module_code),
+            },
+            PlanChange::ActivityAdded {
+                semester_index,
+                activity,
+            } => write!(
+                f,
+                "Added activity '{}' to semester {}",
+                activity.description,
+                semester_index + 1
+            ),
+            PlanChange::SemesterAppended { year, semester } => {
+                write!(f, "Added {} Semester {} to the plan", year, semester)
+            }
+            PlanChange::MetadataChanged { field, to, .. } => match to {
+                Some(value) => write!(f, "Set {} to '{}'", field, value),
+                None => write!(f, "Cleared {}", field),
+            },
+            PlanChange::ClassesSelected {
+                module_code,
+                semester_index,
+                ..
+            } => write!(
+                f,
+                "Updated class selections for {} in semester {}",
+                module_code,
+                semester_index + 1
+            ),
+        }
+    }
+}
+
+impl fmt::Display for PlanField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PlanField::Name => "plan name",
+            PlanField::TargetGraduation => "target graduation",
+            PlanField::Notes => "notes",
+        })
+    }
+}
+
+impl FromStr for ModuleStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "planned" => Ok(ModuleStatus::Planned),
+            "current" => Ok(ModuleStatus::Current),
+            "completed" => Ok(ModuleStatus::Completed),
+            "failed" => Ok(ModuleStatus::Failed),
+            _ => Err(anyhow!(
+                "Invalid status '{}' (expected planned, current, completed or failed)",
+                s
+            )),
+        }
+    }
+}
+
+impl FromStr for ActivityType {
+    type Err = Error;
+
+    /// Parses the known activity kinds; anything else becomes `Other`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Activity type must not be empty"));
+        }
+
+        Ok(
+            match name.to_lowercase().replace([' ', '_'], "-").as_str() {
+                "industrial-attachment" | "internship" => ActivityType::IndustrialAttachment,
+                "international-exchange" | "exchange" => ActivityType::InternationalExchange,
+                "research" => ActivityType::Research,
+                "community-service" => ActivityType::CommunityService,
+                _ => ActivityType::Other(name.to_string()),
+            },
+        )
+    }
 }
@@ -0,0 +1,235 @@
+use super::{Module, ModuleRegistry, Units};
+use std::collections::HashMap;
+
+/// Field weights used when scoring a matched token.
+const CODE_WEIGHT: u32 = 4;
+const TITLE_WEIGHT: u32 = 2;
+const DESCRIPTION_WEIGHT: u32 = 1;
+
+/// Text query and filters for `ModuleRegistry::search`. Every filter left
+/// as `None` (or empty) matches all modules.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleQuery {
+    /// Free text matched against module code, title and description. Each
+    /// word must match, allowing prefixes and small typos.
+    pub text: String,
+    /// Module levels such as 1000 or 3000.
+    pub levels: Vec<u32>,
+    /// Case-insensitive substring of the department name.
+    pub department: Option<String>,
+    /// Case-insensitive substring of the faculty name.
+    pub faculty: Option<String>,
+    pub units: Option<Units>,
+    pub semester: Option<u8>,
+    /// `Some(true)` for modules with a final exam, `Some(false)` for those
+    /// without. Checked against `semester` when set.
+    pub has_exam: Option<bool>,
+    /// Maximum total weekly workload hours.
+    pub max_workload: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum TokenMatch {
+    Fuzzy = 1,
+    Prefix = 2,
+    Exact = 3,
+}
+
+impl ModuleQuery {
+    pub fn text(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn matches_filters(&self, module: &Module) -> bool {
+        if !self.levels.is_empty() && !module.level().is_some_and(|l| self.levels.contains(&l)) {
+            return false;
+        }
+
+        if !contains_ignore_case(module.department.as_deref(), self.department.as_deref()) {
+            return false;
+        }
+
+        if !contains_ignore_case(module.faculty.as_deref(), self.faculty.as_deref()) {
+            return false;
+        }
+
+        if let Some(units) = self.units {
+            if module.units() != units {
+                return false;
+            }
+        }
+
+        if let Some(semester) = self.semester {
+            if !module.is_offered_in(semester) {
+                return false;
+            }
+        }
+
+        if let Some(has_exam) = self.has_exam {
+            let examined = module
+                .semester_data
+                .iter()
+                .filter(|d| self.semester.is_none_or(|s| d.semester == s))
+                .any(|d| d.exam_date.is_some());
+            if examined != has_exam {
+                return false;
+            }
+        }
+
+        if let Some(max_workload) = self.max_workload {
+            let workload: Option<f32> = module.workload.as_ref().map(|w| w.iter().sum());
+            if workload.is_some_and(|w| w > max_workload) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl ModuleRegistry {
+    /// Searches the registry, returning matching modules best first. With an
+    /// empty query text every module passing the filters is returned in
+    /// code order.
+    pub fn search(&self, query: &ModuleQuery) -> Vec<&Module> {
+        let terms = tokenize(&query.text);
+
+        let mut scores: HashMap<&str, u32> = HashMap::new();
+        if terms.is_empty() {
+            scores.extend(self.modules.keys().map(|code| (code.as_str(), 0)));
+        } else {
+            for (i, term) in terms.iter().enumerate() {
+                let term_scores = self.score_term(term);
+                if i == 0 {
+                    scores = term_scores;
+                } else {
+                    scores.retain(|code, _| term_scores.contains_key(code));
+                    for (code, score) in scores.iter_mut() {
+                        *score += term_scores[code];
+                    }
+                }
+            }
+        }
+
+        let mut results: Vec<(&Module, u32)> = scores
+            .into_iter()
+            .filter_map(|(code, score)| self.modules.get(code).map(|m| (m, score)))
+            .filter(|(module, _)| query.matches_filters(module))
+            .collect();
+
+        results.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.module_code.cmp(&b.module_code))
+        });
+        results.into_iter().map(|(module, _)| module).collect()
+    }
+
+    /// Best score per module for a single query word.
+    fn score_term(&self, term: &str) -> HashMap<&str, u32> {
+        let mut scores: HashMap<&str, u32> = HashMap::new();
+
+        for (token, postings) in &self.search_index {
+            let Some(quality) = match_token(term, token) else {
+                continue;
+            };
+
+            for (code, &weight) in postings {
+                let score = quality as u32 * weight;
+                let best = scores.entry(code.as_str()).or_insert(0);
+                *best = (*best).max(score);
+            }
+        }
+
+        scores
+    }
+
+    pub(super) fn index_module(&mut self, module: &Module) {
+        for (token, weight) in module_tokens(module) {
+            let postings = self.search_index.entry(token).or_default();
+            let entry = postings.entry(module.module_code.clone()).or_insert(0);
+            *entry = (*entry).max(weight);
+        }
+    }
+
+    pub(super) fn unindex_module(&mut self, module: &Module) {
+        for (token, _) in module_tokens(module) {
+            if let Some(postings) = self.search_index.get_mut(&token) {
+                postings.remove(&module.module_code);
+                if postings.is_empty() {
+                    self.search_index.remove(&token);
+                }
+            }
+        }
+    }
+}
+
+fn module_tokens(module: &Module) -> Vec<(String, u32)> {
+    let mut tokens = vec![(module.module_code.to_lowercase(), CODE_WEIGHT)];
+    tokens.extend(
+        tokenize(&module.title)
+            .into_iter()
+            .map(|token| (token, TITLE_WEIGHT)),
+    );
+    if let Some(description) = &module.description {
+        tokens.extend(
+            tokenize(description)
+                .into_iter()
+                .map(|token| (token, DESCRIPTION_WEIGHT)),
+        );
+    }
+    tokens
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+        .collect()
+}
+
+fn match_token(term: &str, token: &str) -> Option<TokenMatch> {
+    if token == term {
+        return Some(TokenMatch::Exact);
+    }
+    if token.starts_with(term) {
+        return Some(TokenMatch::Prefix);
+    }
+
+    let allowed = match term.chars().count() {
+        0..=3 => return None,
+        4..=6 => 1,
+        _ => 2,
+    };
+    // Compare against the token's leading characters too, so a misspelt
+    // prefix still finds longer words
+    let prefix: String = token.chars().take(term.chars().count()).collect();
+    (edit_distance(term, token) <= allowed || edit_distance(term, &prefix) <= allowed)
+        .then_some(TokenMatch::Fuzzy)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+fn contains_ignore_case(value: Option<&str>, needle: Option<&str>) -> bool {
+    match needle {
+        None => true,
+        Some(needle) => value.is_some_and(|v| v.to_lowercase().contains(&needle.to_lowercase())),
+    }
+}
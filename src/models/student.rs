@@ -1,23 +1,36 @@
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Student {
+    /// Stable identifier that plans refer to through `Plan::student_id`.
+    /// Empty for profiles saved before IDs were introduced; see
+    /// `assign_student_ids`.
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub matriculation_year: String,
     pub faculty: String,
     pub major: String,
     pub second_major: Option<String>,
+    #[serde(default)]
     pub minors: Vec<String>,
+    #[serde(default)]
     pub completed_modules: HashSet<String>,
+    #[serde(default)]
     pub exempted_modules: HashSet<String>,
+    #[serde(default)]
     pub advanced_placement_credits: u32,
     pub current_semester: u8,
+    #[serde(default)]
     pub candidature_type: CandidatureType,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub enum CandidatureType {
+    #[default]
     Standard,
     DoubleHonours,
     DoubleDegreeProgramme,
@@ -26,13 +39,117 @@ pub enum CandidatureType {
 }
 
 impl Student {
+    /// Modules that count as passed before the plan starts: completed or exempted.
+    pub fn credited_modules(&self) -> HashSet<String> {
+        self.completed_modules
+            .union(&self.exempted_modules)
+            .cloned()
+            .collect()
+    }
+
+    /// Regular semesters left after the current one within normal candidature.
     pub fn remaining_semesters(&self) -> u8 {
-        match self.candidature_type {
-            CandidatureType::Standard => 8 - self.current_semester,
-            CandidatureType::DoubleHonours => 10 - self.current_semester,
-            CandidatureType::DoubleDegreeProgramme => 10 - self.current_semester,
-            CandidatureType::ConcurrentDegree => 10 - self.current_semester,
-            CandidatureType::EngineeringScholars => 8 - self.current_semester,
+        self.candidature_type
+            .rules()
+            .normal_semesters
+            .saturating_sub(self.current_semester)
+    }
+
+    /// Returns the academic year and regular semester (1 or 2) of the student's
+    /// n-th semester of study, counting from the matriculation year.
+    pub fn academic_term(&self, semester_number: u8) -> Option<(String, u8)> {
+        if semester_number == 0 {
+            return None;
+        }
+
+        let start_year = self
+            .matriculation_year
+            .split('/')
+            .next()
+            .and_then(|year| year.trim().parse::<u32>().ok())?;
+
+        let year = start_year + u32::from(semester_number - 1) / 2;
+        let semester = if semester_number % 2 == 1 { 1 } else { 2 };
+
+        Some((format!("{}/{}", year, year + 1), semester))
+    }
+
+    /// Inverse of [`Student::academic_term`]: the student's semester number for
+    /// a regular semester of the given academic year.
+    pub fn semester_number(&self, year: &str, semester: u8) -> Option<u8> {
+        if semester != 1 && semester != 2 {
+            return None;
         }
+
+        let parse_start = |y: &str| y.split('/').next()?.trim().parse::<u32>().ok();
+        let start_year = parse_start(&self.matriculation_year)?;
+        let year = parse_start(year)?;
+
+        let offset = year.checked_sub(start_year)?;
+        u8::try_from(offset * 2 + u32::from(semester)).ok()
+    }
+}
+
+/// Checks that a matriculation year is written as consecutive academic
+/// years, e.g. "2024/2025".
+pub fn validate_matriculation_year(year: &str) -> Result<()> {
+    let invalid = || {
+        anyhow!(
+            "Invalid matriculation year '{}' (expected e.g. 2024/2025)",
+            year
+        )
+    };
+
+    let (start, end) = year.trim().split_once('/').ok_or_else(invalid)?;
+    let parse = |part: &str| {
+        (part.len() == 4)
+            .then(|| part.parse::<u32>().ok())
+            .flatten()
+    };
+    match (parse(start), parse(end)) {
+        (Some(start), Some(end)) if end == start + 1 => Ok(()),
+        _ => Err(invalid()),
+    }
+}
+
+/// Checks that a target graduation term is written as an academic year and
+/// regular semester, e.g. "2027/2028 Semester 2".
+pub fn validate_target_graduation(target: &str) -> Result<()> {
+    let invalid = || {
+        anyhow!(
+            "Invalid target graduation '{}' (expected e.g. 2027/2028 Semester 2)",
+            target
+        )
+    };
+
+    let (year, semester) = target.trim().split_once(" Semester ").ok_or_else(invalid)?;
+    validate_matriculation_year(year).map_err(|_| invalid())?;
+    match semester.trim() {
+        "1" | "2" => Ok(()),
+        _ => Err(invalid()),
+    }
+}
+
+/// Academic year containing a date, e.g. "2025/2026" for any date from
+/// August 2025 to July 2026.
+pub fn academic_year_on(date: NaiveDate) -> String {
+    let start = academic_year_start_on(date);
+    format!("{}/{}", start, start + 1)
+}
+
+/// `count` consecutive academic years, the first `from` years after the one
+/// containing `date` (negative offsets go back in time).
+pub fn academic_years_around(date: NaiveDate, from: i32, count: usize) -> Vec<String> {
+    (academic_year_start_on(date) + from..)
+        .take(count)
+        .map(|year| format!("{}/{}", year, year + 1))
+        .collect()
+}
+
+fn academic_year_start_on(date: NaiveDate) -> i32 {
+    if date.month() >= 8 {
+        date.year()
+    } else {
+        date.year() - 1
     }
 }
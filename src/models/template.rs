@@ -0,0 +1,134 @@
+use super::{ModuleRegistry, ModuleStatus, Plan, PlannedModule, SpecialActivity, Student};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A department's recommended study plan for one curriculum cohort, e.g.
+/// the ISE schedule for the AY2024/2025 intake.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanTemplate {
+    pub name: String,
+    /// Name of the `Curriculum` the template follows.
+    pub curriculum: String,
+    /// Matriculation year of the cohort, e.g. "2024/2025".
+    pub cohort: String,
+    pub semesters: Vec<TemplateSemester>,
+}
+
+/// What the template recommends for one semester of study.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSemester {
+    /// Semester of study, counting from 1 at matriculation.
+    pub number: u8,
+    pub modules: Vec<String>,
+    #[serde(default)]
+    pub special_activities: Vec<SpecialActivity>,
+}
+
+/// What happened when a template was applied to a plan.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateReport {
+    pub added: Vec<String>,
+    /// Modules the student has already completed or been exempted from.
+    pub skipped: Vec<String>,
+    /// Modules from semesters the student has already passed, placed
+    /// `shifted_by` semesters later than the template recommends.
+    pub carried_forward: Vec<String>,
+    /// How many semesters the template was shifted forward.
+    pub shifted_by: u8,
+    /// Template semesters with no matching semester in the plan.
+    pub unplaced: Vec<u8>,
+}
+
+impl PlanTemplate {
+    pub fn matches(&self, curriculum: &str, cohort: &str) -> bool {
+        self.curriculum.eq_ignore_ascii_case(curriculum) && self.cohort == cohort
+    }
+
+    /// Fills a plan's semesters from the template. Template semesters are
+    /// mapped to terms through the student's matriculation year. A student
+    /// who is behind the template (the first semester with modules still to
+    /// take is already over) gets the rest of the template shifted forward
+    /// as a whole, so prerequisite chains and semester loads keep their
+    /// spacing. Semesters left fully behind contribute nothing, not even
+    /// their special activities. Modules already completed or exempted are
+    /// skipped, as are modules the plan already contains.
+    pub fn apply(
+        &self,
+        plan: &mut Plan,
+        student: &Student,
+        registry: &ModuleRegistry,
+    ) -> TemplateReport {
+        let mut report = TemplateReport::default();
+        let mut planned = plan.all_modules();
+        let credited = |code: &String| {
+            student.completed_modules.contains(code) || student.exempted_modules.contains(code)
+        };
+
+        let mut semesters: Vec<&TemplateSemester> = self.semesters.iter().collect();
+        semesters.sort_by_key(|s| s.number);
+
+        let first_open = semesters
+            .iter()
+            .find(|s| {
+                s.modules
+                    .iter()
+                    .any(|c| !credited(c) && !planned.contains(c))
+            })
+            .map_or(student.current_semester, |s| s.number);
+        let shift = student.current_semester.saturating_sub(first_open);
+        report.shifted_by = shift;
+
+        for template_semester in semesters {
+            if template_semester.number < first_open.min(student.current_semester) {
+                report.skipped.extend(
+                    template_semester
+                        .modules
+                        .iter()
+                        .filter(|c| credited(c))
+                        .cloned(),
+                );
+                continue;
+            }
+
+            let number = template_semester.number + shift;
+            let target = student.academic_term(number).and_then(|(year, semester)| {
+                plan.semesters
+                    .iter()
+                    .position(|s| s.year == year && s.semester == semester)
+            });
+            let Some(index) = target else {
+                report.unplaced.push(template_semester.number);
+                continue;
+            };
+            plan.semesters[index].edit_units(registry, |semester| {
+                for code in &template_semester.modules {
+                    if credited(code) {
+                        report.skipped.push(code.clone());
+                        continue;
+                    }
+                    if !planned.insert(code.clone()) {
+                        continue;
+                    }
+
+                    semester.modules.push(PlannedModule {
+                        module_code: code.clone(),
+                        status: ModuleStatus::Planned,
+                        grade: None,
+                        s_u_option: false,
+                        class_selections: HashMap::new(),
+                    });
+                    if template_semester.number < student.current_semester {
+                        report.carried_forward.push(code.clone());
+                    }
+                    report.added.push(code.clone());
+                }
+
+                semester
+                    .special_activities
+                    .extend(template_semester.special_activities.iter().cloned());
+            });
+        }
+
+        report
+    }
+}
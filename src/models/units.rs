@@ -0,0 +1,249 @@
+use anyhow::{anyhow, Error};
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use std::str::FromStr;
+
+/// A number of units (modular credits). Held in hundredths so fractional
+/// credits such as 2.5 add up exactly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Units(u32);
+
+/// A module's credit as published: a fixed number of units, or a range for
+/// variable-credit modules such as independent study.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleCredit {
+    Fixed(Units),
+    Variable { min: Units, max: Units },
+}
+
+impl Units {
+    pub const ZERO: Units = Units(0);
+
+    pub fn from_hundredths(hundredths: u32) -> Self {
+        Units(hundredths)
+    }
+
+    pub fn hundredths(self) -> u32 {
+        self.0
+    }
+
+    pub fn as_f32(self) -> f32 {
+        self.0 as f32 / 100.0
+    }
+
+    /// Whole units, rounding any fraction down.
+    pub fn whole(self) -> u32 {
+        self.0 / 100
+    }
+
+    pub fn saturating_sub(self, other: Units) -> Units {
+        Units(self.0.saturating_sub(other.0))
+    }
+
+    fn from_f64(value: f64) -> Option<Self> {
+        (value.is_finite() && value >= 0.0 && value <= (u32::MAX / 100) as f64)
+            .then(|| Units((value * 100.0).round() as u32))
+    }
+}
+
+impl From<u32> for Units {
+    fn from(units: u32) -> Self {
+        Units(units * 100)
+    }
+}
+
+impl FromStr for Units {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(Units::from_f64)
+            .ok_or_else(|| anyhow!("Invalid units '{}'", s))
+    }
+}
+
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (whole, fraction) = (self.0 / 100, self.0 % 100);
+        match fraction {
+            0 => write!(f, "{}", whole),
+            _ if fraction.is_multiple_of(10) => write!(f, "{}.{}", whole, fraction / 10),
+            _ => write!(f, "{}.{:02}", whole, fraction),
+        }
+    }
+}
+
+impl Add for Units {
+    type Output = Units;
+
+    fn add(self, other: Units) -> Units {
+        Units(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Units {
+    fn add_assign(&mut self, other: Units) {
+        self.0 += other.0;
+    }
+}
+
+impl Sum for Units {
+    fn sum<I: Iterator<Item = Units>>(iter: I) -> Units {
+        iter.fold(Units::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Units> for Units {
+    fn sum<I: Iterator<Item = &'a Units>>(iter: I) -> Units {
+        iter.copied().sum()
+    }
+}
+
+/// Whole numbers are written as integers so existing plan files keep their
+/// shape.
+impl Serialize for Units {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.is_multiple_of(100) {
+            serializer.serialize_u32(self.whole())
+        } else {
+            serializer.serialize_f64(self.0 as f64 / 100.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Units {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(UnitsVisitor)
+    }
+}
+
+struct UnitsVisitor;
+
+impl Visitor<'_> for UnitsVisitor {
+    type Value = Units;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a non-negative number of units")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Units, E> {
+        self.visit_f64(value as f64)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Units, E> {
+        self.visit_f64(value as f64)
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Units, E> {
+        Units::from_f64(value).ok_or_else(|| E::custom(format!("invalid units {}", value)))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Units, E> {
+        value.parse().map_err(E::custom)
+    }
+}
+
+impl ModuleCredit {
+    /// Units counted toward totals. Variable-credit modules count at their
+    /// maximum so workload limits are never understated.
+    pub fn units(&self) -> Units {
+        match self {
+            ModuleCredit::Fixed(units) => *units,
+            ModuleCredit::Variable { max, .. } => *max,
+        }
+    }
+
+    pub fn is_variable(&self) -> bool {
+        matches!(self, ModuleCredit::Variable { .. })
+    }
+}
+
+impl Default for ModuleCredit {
+    fn default() -> Self {
+        ModuleCredit::Fixed(Units::ZERO)
+    }
+}
+
+impl From<u32> for ModuleCredit {
+    fn from(units: u32) -> Self {
+        ModuleCredit::Fixed(Units::from(units))
+    }
+}
+
+impl FromStr for ModuleCredit {
+    type Err = Error;
+
+    /// Parses `"4"`, `"2.5"` or a range such as `"0-8"`, `"0–8"` or
+    /// `"2 to 4"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = s.split_once(['-', '–']).or_else(|| s.split_once(" to "));
+
+        match range {
+            Some((min, max)) => {
+                let (min, max): (Units, Units) = (min.parse()?, max.parse()?);
+                if min > max {
+                    return Err(anyhow!("Invalid unit range '{}'", s));
+                }
+                Ok(if min == max {
+                    ModuleCredit::Fixed(min)
+                } else {
+                    ModuleCredit::Variable { min, max }
+                })
+            }
+            None => Ok(ModuleCredit::Fixed(s.parse()?)),
+        }
+    }
+}
+
+impl fmt::Display for ModuleCredit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleCredit::Fixed(units) => write!(f, "{}", units),
+            ModuleCredit::Variable { min, max } => write!(f, "{}-{}", min, max),
+        }
+    }
+}
+
+/// Written as a string, matching the `moduleCredit` field in NUSMods data.
+impl Serialize for ModuleCredit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ModuleCredit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CreditVisitor;
+
+        impl Visitor<'_> for CreditVisitor {
+            type Value = ModuleCredit;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a number of units or a range such as \"0-8\"")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<ModuleCredit, E> {
+                UnitsVisitor.visit_u64(value).map(ModuleCredit::Fixed)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<ModuleCredit, E> {
+                UnitsVisitor.visit_i64(value).map(ModuleCredit::Fixed)
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<ModuleCredit, E> {
+                UnitsVisitor.visit_f64(value).map(ModuleCredit::Fixed)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<ModuleCredit, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(CreditVisitor)
+    }
+}
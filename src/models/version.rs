@@ -0,0 +1,106 @@
+use super::Plan;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fmt;
+
+/// An immutable copy of a plan as it was at one save.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanVersion {
+    pub plan_id: String,
+    /// Numbered from 1 per plan. Numbers are never reused, even after older
+    /// versions are pruned.
+    pub version: u32,
+    pub saved_at: DateTime<Utc>,
+    /// The most recent logged edit at the time of the save, if any.
+    pub description: Option<String>,
+    pub plan: Plan,
+}
+
+impl PlanVersion {
+    pub fn new(plan: &Plan, version: u32) -> Self {
+        Self {
+            plan_id: plan.id.clone(),
+            version,
+            saved_at: plan.modified_at,
+            description: plan.edit_log.last().map(|edit| edit.description.clone()),
+            plan: plan.clone(),
+        }
+    }
+}
+
+impl fmt::Display for PlanVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "v{} — {}",
+            self.version,
+            self.saved_at.format("%Y-%m-%d %H:%M")
+        )?;
+        if let Some(description) = &self.description {
+            write!(f, " ({})", description)?;
+        }
+        Ok(())
+    }
+}
+
+/// Which saved versions of a plan storage keeps. A version is kept if any
+/// configured rule keeps it; the latest version is always kept. With no
+/// rules configured every version is kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Keep this many of the most recent versions.
+    pub keep_last: Option<usize>,
+    /// Keep versions saved within this many days.
+    pub keep_days: Option<u32>,
+}
+
+impl RetentionPolicy {
+    pub fn keep_all() -> Self {
+        Self::default()
+    }
+
+    pub fn keep_last(count: usize) -> Self {
+        Self {
+            keep_last: Some(count),
+            keep_days: None,
+        }
+    }
+
+    pub fn keep_days(days: u32) -> Self {
+        Self {
+            keep_last: None,
+            keep_days: Some(days),
+        }
+    }
+
+    pub fn keeps_everything(&self) -> bool {
+        self.keep_last.is_none() && self.keep_days.is_none()
+    }
+
+    /// Version numbers the policy would discard, oldest first.
+    pub fn expired(&self, versions: &[PlanVersion], now: DateTime<Utc>) -> Vec<u32> {
+        if self.keeps_everything() {
+            return Vec::new();
+        }
+
+        let mut newest_first: Vec<&PlanVersion> = versions.iter().collect();
+        newest_first.sort_by_key(|version| Reverse(version.version));
+
+        let keep_last = self.keep_last.unwrap_or(0).max(1);
+        let cutoff = self
+            .keep_days
+            .map(|days| now - Duration::days(i64::from(days)));
+
+        let mut expired: Vec<u32> = newest_first
+            .iter()
+            .enumerate()
+            .filter(|(rank, version)| {
+                *rank >= keep_last && cutoff.is_none_or(|cutoff| version.saved_at < cutoff)
+            })
+            .map(|(_, version)| version.version)
+            .collect();
+        expired.sort_unstable();
+        expired
+    }
+}
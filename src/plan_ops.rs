@@ -0,0 +1,598 @@
+use crate::models::{
+    Module, ModuleRegistry, ModuleStatus, Plan, PlanChange, PlanField, PlannedModule,
+    SpecialActivity,
+};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// What an operation changed, plus anything the user should double-check.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationResult {
+    pub changes: Vec<PlanChange>,
+    pub warnings: Vec<String>,
+    /// Descriptions of the edits an undo reverted, most recent first.
+    pub undone: Vec<String>,
+}
+
+/// The `plan --json` output. `PlanChange` carries whatever undo needs and
+/// may grow; this schema only gains fields and variants.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OperationReport {
+    pub changes: Vec<ChangeReport>,
+    pub warnings: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub undone: Vec<String>,
+}
+
+/// One change as reported by `plan --json`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ChangeReport {
+    Added {
+        module_code: String,
+        semester_index: usize,
+    },
+    Removed {
+        module_code: String,
+        semester_index: usize,
+    },
+    Moved {
+        module_code: String,
+        from_semester: usize,
+        to_semester: usize,
+    },
+    StatusChanged {
+        module_code: String,
+        semester_index: usize,
+        from: ModuleStatus,
+        to: ModuleStatus,
+    },
+    GradeSet {
+        module_code: String,
+        semester_index: usize,
+        grade: Option<String>,
+    },
+    ActivityAdded {
+        semester_index: usize,
+        description: String,
+        credits: Option<u32>,
+    },
+    SemesterAppended {
+        year: String,
+        semester: u8,
+    },
+    MetadataChanged {
+        field: PlanField,
+        value: Option<String>,
+    },
+    ClassesSelected {
+        module_code: String,
+        semester_index: usize,
+        selections: HashMap<String, String>,
+    },
+}
+
+impl OperationResult {
+    pub fn report(&self) -> OperationReport {
+        OperationReport {
+            changes: self.changes.iter().map(ChangeReport::from).collect(),
+            warnings: self.warnings.clone(),
+            undone: self.undone.clone(),
+        }
+    }
+}
+
+impl From<&PlanChange> for ChangeReport {
+    fn from(change: &PlanChange) -> Self {
+        match change.clone() {
+            PlanChange::Added {
+                module_code,
+                semester_index,
+            } => ChangeReport::Added {
+                module_code,
+                semester_index,
+            },
+            PlanChange::Removed {
+                module_code,
+                semester_index,
+                ..
+            } => ChangeReport::Removed {
+                module_code,
+                semester_index,
+            },
+            PlanChange::Moved {
+                module_code,
+                from_semester,
+                to_semester,
+            } => ChangeReport::Moved {
+                module_code,
+                from_semester,
+                to_semester,
+            },
+            PlanChange::StatusChanged {
+                module_code,
+                semester_index,
+                from,
+                to,
+            } => ChangeReport::StatusChanged {
+                module_code,
+                semester_index,
+                from,
+                to,
+            },
+            PlanChange::GradeSet {
+                module_code,
+                semester_index,
+                grade,
+                ..
+            } => ChangeReport::GradeSet {
+                module_code,
+                semester_index,
+                grade,
+            },
+            PlanChange::ActivityAdded {
+                semester_index,
+                activity,
+            } => ChangeReport::ActivityAdded {
+                semester_index,
+                description: activity.description,
+                credits: activity.credits,
+            },
+            PlanChange::SemesterAppended { year, semester } => {
+                ChangeReport::SemesterAppended { year, semester }
+            }
+            PlanChange::MetadataChanged { field, to, .. } => {
+                ChangeReport::MetadataChanged { field, value: to }
+            }
+            PlanChange::ClassesSelected {
+                module_code,
+                semester_index,
+                to,
+                ..
+            } => ChangeReport::ClassesSelected {
+                module_code,
+                semester_index,
+                selections: to,
+            },
+        }
+    }
+}
+
+/// A plan edit that can be applied without prompting. Semester fields are
+/// indices into `plan.semesters`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanOperation {
+    Add {
+        semester: usize,
+        module_codes: Vec<String>,
+    },
+    Remove {
+        semester: usize,
+        module_codes: Vec<String>,
+    },
+    Move {
+        from: usize,
+        to: usize,
+        module_codes: Vec<String>,
+    },
+    SetStatus {
+        module_code: String,
+        semester: Option<usize>,
+        status: ModuleStatus,
+    },
+    SetGrade {
+        module_code: String,
+        semester: Option<usize>,
+        grade: Option<String>,
+    },
+    AddActivity {
+        semester: usize,
+        activity: SpecialActivity,
+    },
+}
+
+/// Applies an operation as a whole: if any step fails the plan is left
+/// untouched and the error says which step.
+pub fn apply(
+    plan: &mut Plan,
+    registry: &ModuleRegistry,
+    operation: &PlanOperation,
+) -> Result<OperationResult> {
+    let mut working = plan.clone();
+    let mut result = OperationResult::default();
+
+    match operation {
+        PlanOperation::Add {
+            semester,
+            module_codes,
+        } => {
+            check_semester(&working, *semester)?;
+            for code in module_codes {
+                if registry.get_module(code).is_none() {
+                    return Err(anyhow!("Module {} not found", code));
+                }
+                if let Some(warning) = availability_warning(&working, registry, *semester, code) {
+                    result.warnings.push(warning);
+                }
+                result
+                    .changes
+                    .push(add_module(&mut working, registry, *semester, code)?);
+            }
+        }
+        PlanOperation::Remove {
+            semester,
+            module_codes,
+        } => {
+            for code in module_codes {
+                result
+                    .changes
+                    .push(remove_module(&mut working, registry, *semester, code)?);
+            }
+        }
+        PlanOperation::Move {
+            from,
+            to,
+            module_codes,
+        } => {
+            for code in module_codes {
+                result
+                    .changes
+                    .push(move_module(&mut working, registry, *from, *to, code)?);
+                if let Some(warning) = availability_warning(&working, registry, *to, code) {
+                    result.warnings.push(warning);
+                }
+            }
+        }
+        PlanOperation::SetStatus {
+            module_code,
+            semester,
+            status,
+        } => {
+            result.changes.push(set_status(
+                &mut working,
+                module_code,
+                *semester,
+                status.clone(),
+            )?);
+        }
+        PlanOperation::SetGrade {
+            module_code,
+            semester,
+            grade,
+        } => {
+            result.changes.push(set_grade(
+                &mut working,
+                module_code,
+                *semester,
+                grade.clone(),
+            )?);
+        }
+        PlanOperation::AddActivity { semester, activity } => {
+            result.changes.push(add_activity(
+                &mut working,
+                registry,
+                *semester,
+                activity.clone(),
+            )?);
+        }
+    }
+
+    *plan = working;
+    Ok(result)
+}
+
+/// Resolves a semester given as a 1-based position (`"3"`) or as
+/// `year:semester` (`"2024/2025:2"`) to an index into `plan.semesters`.
+pub fn resolve_semester(plan: &Plan, spec: &str) -> Result<usize> {
+    let spec = spec.trim();
+
+    if let Ok(position) = spec.parse::<usize>() {
+        if position == 0 || position > plan.semesters.len() {
+            return Err(anyhow!(
+                "Semester {} is out of range (plan has {} semesters)",
+                position,
+                plan.semesters.len()
+            ));
+        }
+        return Ok(position - 1);
+    }
+
+    let (year, semester) = spec
+        .rsplit_once(':')
+        .and_then(|(year, sem)| Some((year.trim(), sem.trim().parse::<u8>().ok()?)))
+        .ok_or_else(|| {
+            anyhow!(
+                "Invalid semester '{}' (use a number or year:semester, e.g. 2024/2025:1)",
+                spec
+            )
+        })?;
+    let year = year.trim_start_matches("AY");
+
+    plan.semesters
+        .iter()
+        .position(|s| s.year == year && s.semester == semester)
+        .ok_or_else(|| anyhow!("Plan has no {} Semester {}", year, semester))
+}
+
+/// Warns when the module is not offered in the semester's term.
+pub fn availability_warning(
+    plan: &Plan,
+    registry: &ModuleRegistry,
+    semester_index: usize,
+    module_code: &str,
+) -> Option<String> {
+    let semester = plan.semesters.get(semester_index)?;
+    let module = registry.get_module_for_year(module_code, &semester.year)?;
+
+    (!module.is_offered_in(semester.semester)).then(|| {
+        format!(
+            "{} is not typically offered in Semester {}",
+            module_code, semester.semester
+        )
+    })
+}
+
+/// Adds a planned module to a semester. Codes missing from the registry are
+/// allowed here; callers decide whether to accept them.
+pub fn add_module(
+    plan: &mut Plan,
+    registry: &ModuleRegistry,
+    semester_index: usize,
+    module_code: &str,
+) -> Result<PlanChange> {
+    check_semester(plan, semester_index)?;
+    let semester = &mut plan.semesters[semester_index];
+
+    if semester
+        .modules
+        .iter()
+        .any(|m| m.module_code == module_code)
+    {
+        return Err(anyhow!(
+            "{} is already planned in {} Semester {}",
+            module_code,
+            semester.year,
+            semester.semester
+        ));
+    }
+
+    semester.edit_units(registry, |semester| {
+        semester.modules.push(PlannedModule {
+            module_code: module_code.to_string(),
+            status: ModuleStatus::Planned,
+            grade: None,
+            s_u_option: false,
+            class_selections: HashMap::new(),
+        })
+    });
+
+    Ok(PlanChange::Added {
+        module_code: module_code.to_string(),
+        semester_index,
+    })
+}
+
+/// `add_module` for a module picked from search results, which may come
+/// from outside the registry. Its credit counts towards the semester total
+/// either way.
+pub fn add_searched_module(
+    plan: &mut Plan,
+    registry: &ModuleRegistry,
+    semester_index: usize,
+    module: &Module,
+) -> Result<PlanChange> {
+    let change = add_module(plan, registry, semester_index, &module.module_code)?;
+    let semester = &mut plan.semesters[semester_index];
+    if registry
+        .get_module_for_year(&module.module_code, &semester.year)
+        .is_none()
+    {
+        semester.total_units += module.units();
+    }
+    Ok(change)
+}
+
+pub fn remove_module(
+    plan: &mut Plan,
+    registry: &ModuleRegistry,
+    semester_index: usize,
+    module_code: &str,
+) -> Result<PlanChange> {
+    let position = module_position(plan, semester_index, module_code)?;
+    let semester = &mut plan.semesters[semester_index];
+    let module = semester.edit_units(registry, |semester| semester.modules.remove(position));
+
+    Ok(PlanChange::Removed {
+        module_code: module_code.to_string(),
+        semester_index,
+        position,
+        module,
+    })
+}
+
+/// Moves a module between semesters, keeping its status, grade and class
+/// selections.
+pub fn move_module(
+    plan: &mut Plan,
+    registry: &ModuleRegistry,
+    from: usize,
+    to: usize,
+    module_code: &str,
+) -> Result<PlanChange> {
+    check_semester(plan, to)?;
+    if from == to {
+        return Err(anyhow!("Source and target semesters are the same"));
+    }
+
+    let position = module_position(plan, from, module_code)?;
+    let target = &plan.semesters[to];
+    if target.modules.iter().any(|m| m.module_code == module_code) {
+        return Err(anyhow!(
+            "{} is already planned in {} Semester {}",
+            module_code,
+            target.year,
+            target.semester
+        ));
+    }
+
+    let module =
+        plan.semesters[from].edit_units(registry, |semester| semester.modules.remove(position));
+    plan.semesters[to].edit_units(registry, |semester| semester.modules.push(module));
+
+    Ok(PlanChange::Moved {
+        module_code: module_code.to_string(),
+        from_semester: from,
+        to_semester: to,
+    })
+}
+
+/// Sets a module's status. Without a semester the latest occurrence of the
+/// module is used, so retakes take precedence over the original attempt.
+pub fn set_status(
+    plan: &mut Plan,
+    module_code: &str,
+    semester: Option<usize>,
+    status: ModuleStatus,
+) -> Result<PlanChange> {
+    let (semester_index, position) = locate_module(plan, module_code, semester)?;
+    let module = &mut plan.semesters[semester_index].modules[position];
+    let from = std::mem::replace(&mut module.status, status.clone());
+
+    Ok(PlanChange::StatusChanged {
+        module_code: module_code.to_string(),
+        semester_index,
+        from,
+        to: status,
+    })
+}
+
+/// Sets or clears a module's grade, locating it as `set_status` does.
+pub fn set_grade(
+    plan: &mut Plan,
+    module_code: &str,
+    semester: Option<usize>,
+    grade: Option<String>,
+) -> Result<PlanChange> {
+    let (semester_index, position) = locate_module(plan, module_code, semester)?;
+    let grade = grade.filter(|g| !g.trim().is_empty());
+    let module = &mut plan.semesters[semester_index].modules[position];
+    let previous = std::mem::replace(&mut module.grade, grade.clone());
+
+    Ok(PlanChange::GradeSet {
+        module_code: module_code.to_string(),
+        semester_index,
+        grade,
+        previous,
+    })
+}
+
+pub fn add_activity(
+    plan: &mut Plan,
+    registry: &ModuleRegistry,
+    semester_index: usize,
+    activity: SpecialActivity,
+) -> Result<PlanChange> {
+    check_semester(plan, semester_index)?;
+    if activity.description.trim().is_empty() {
+        return Err(anyhow!("Activity description must not be empty"));
+    }
+
+    let semester = &mut plan.semesters[semester_index];
+    semester.edit_units(registry, |semester| {
+        semester.special_activities.push(activity.clone())
+    });
+
+    Ok(PlanChange::ActivityAdded {
+        semester_index,
+        activity,
+    })
+}
+
+/// Sets a plan-level field. Only the notes may be cleared.
+pub fn set_field(plan: &mut Plan, field: PlanField, value: Option<String>) -> Result<PlanChange> {
+    let value = value.filter(|v| !v.trim().is_empty());
+    let from = match field {
+        PlanField::Notes => std::mem::replace(&mut plan.notes, value.clone()),
+        PlanField::Name | PlanField::TargetGraduation => {
+            let required = value
+                .clone()
+                .ok_or_else(|| anyhow!("The {} must not be empty", field))?;
+            let target = match field {
+                PlanField::Name => &mut plan.name,
+                _ => &mut plan.target_graduation,
+            };
+            Some(std::mem::replace(target, required))
+        }
+    };
+
+    Ok(PlanChange::MetadataChanged {
+        field,
+        from,
+        to: value,
+    })
+}
+
+/// Replaces a module's class selections (lesson type to class number).
+pub fn select_classes(
+    plan: &mut Plan,
+    semester_index: usize,
+    module_code: &str,
+    selections: HashMap<String, String>,
+) -> Result<PlanChange> {
+    let position = module_position(plan, semester_index, module_code)?;
+    let module = &mut plan.semesters[semester_index].modules[position];
+    let from = std::mem::replace(&mut module.class_selections, selections.clone());
+
+    Ok(PlanChange::ClassesSelected {
+        module_code: module_code.to_string(),
+        semester_index,
+        from,
+        to: selections,
+    })
+}
+
+fn check_semester(plan: &Plan, semester_index: usize) -> Result<()> {
+    if semester_index >= plan.semesters.len() {
+        return Err(anyhow!(
+            "Semester {} is out of range (plan has {} semesters)",
+            semester_index + 1,
+            plan.semesters.len()
+        ));
+    }
+    Ok(())
+}
+
+fn module_position(plan: &Plan, semester_index: usize, module_code: &str) -> Result<usize> {
+    check_semester(plan, semester_index)?;
+    let semester = &plan.semesters[semester_index];
+
+    semester
+        .modules
+        .iter()
+        .position(|m| m.module_code == module_code)
+        .ok_or_else(|| {
+            anyhow!(
+                "{} is not planned in {} Semester {}",
+                module_code,
+                semester.year,
+                semester.semester
+            )
+        })
+}
+
+fn locate_module(
+    plan: &Plan,
+    module_code: &str,
+    semester: Option<usize>,
+) -> Result<(usize, usize)> {
+    match semester {
+        Some(semester_index) => Ok((
+            semester_index,
+            module_position(plan, semester_index, module_code)?,
+        )),
+        None => plan
+            .find_module(module_code)
+            .ok_or_else(|| anyhow!("{} is not in this plan", module_code)),
+    }
+}
@@ -0,0 +1,193 @@
+use crate::models::{ModuleRegistry, ModuleStatus, Plan, PlannedModule};
+use std::collections::{HashMap, HashSet};
+
+/// How many semesters may be appended to a plan when shifting dependents.
+const MAX_EXTRA_SEMESTERS: usize = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrokenDependent {
+    pub module_code: String,
+    pub semester_index: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleShift {
+    pub module_code: String,
+    pub from_semester: usize,
+    pub to_semester: usize,
+}
+
+/// Finds every module planned after `failed_semester` whose prerequisites were
+/// satisfied only because `failed_code` (or another broken module) was
+/// expected to be passed. `credited` holds modules passed or exempted before
+/// the plan starts. Dependents are returned in plan order.
+pub fn find_broken_dependents(
+    plan: &Plan,
+    registry: &ModuleRegistry,
+    credited: &HashSet<String>,
+    failed_code: &str,
+    failed_semester: usize,
+) -> Vec<BrokenDependent> {
+    let mut broken: HashSet<String> = HashSet::new();
+    broken.insert(failed_code.to_string());
+
+    let mut dependents = Vec::new();
+    let mut assumed_passed: HashSet<String> = credited.clone();
+    let mut actually_passed: HashSet<String> = credited.clone();
+    actually_passed.remove(failed_code);
+
+    for (sem_index, semester) in plan.semesters.iter().enumerate() {
+        if sem_index > failed_semester {
+            for module in &semester.modules {
+                if !matches!(module.status, ModuleStatus::Planned | ModuleStatus::Current) {
+                    continue;
+                }
+
+                let Some(tree) = registry
+                    .get_module_for_year(&module.module_code, &semester.year)
+                    .and_then(|m| m.prereq_tree.as_ref())
+                else {
+                    continue;
+                };
+
+                if tree.is_satisfied_by(&assumed_passed) && !tree.is_satisfied_by(&actually_passed)
+                {
+                    broken.insert(module.module_code.clone());
+                    dependents.push(BrokenDependent {
+                        module_code: module.module_code.clone(),
+                        semester_index: sem_index,
+                    });
+                }
+            }
+        }
+
+        for module in &semester.modules {
+            if module.module_code == failed_code {
+                assumed_passed.insert(module.module_code.clone());
+                continue;
+            }
+            if module.status == ModuleStatus::Failed {
+                continue;
+            }
+
+            assumed_passed.insert(module.module_code.clone());
+            if !broken.contains(&module.module_code) {
+                actually_passed.insert(module.module_code.clone());
+            }
+        }
+    }
+
+    dependents
+}
+
+/// Proposes the first semester after `after` in which the module is offered.
+/// Modules missing from the registry are assumed to be offered every semester.
+pub fn propose_retake_semester(
+    plan: &Plan,
+    registry: &ModuleRegistry,
+    module_code: &str,
+    after: usize,
+) -> Option<usize> {
+    (after + 1..plan.semesters.len()).find(|&index| {
+        let semester = &plan.semesters[index];
+        registry.is_offered(module_code, &semester.year, semester.semester)
+    })
+}
+
+/// Adds a planned retake of the module to the given semester.
+pub fn schedule_retake(
+    plan: &mut Plan,
+    registry: &ModuleRegistry,
+    module_code: &str,
+    semester_index: usize,
+) {
+    let semester = &mut plan.semesters[semester_index];
+    semester.edit_units(registry, |semester| {
+        semester.modules.push(PlannedModule {
+            module_code: module_code.to_string(),
+            status: ModuleStatus::Planned,
+            grade: None,
+            s_u_option: false,
+            class_selections: HashMap::new(),
+        })
+    });
+}
+
+/// Moves broken dependents so that each one sits after the retake and after
+/// any other shifted module it depends on, appending semesters if required.
+pub fn shift_dependents(
+    plan: &mut Plan,
+    registry: &ModuleRegistry,
+    failed_code: &str,
+    retake_index: usize,
+    dependents: &[BrokenDependent],
+) -> Vec<ModuleShift> {
+    let mut placed: HashMap<String, usize> = HashMap::new();
+    placed.insert(failed_code.to_string(), retake_index);
+
+    let original_len = plan.semesters.len();
+    let mut shifts = Vec::new();
+
+    for dependent in dependents {
+        let prereq_codes = registry
+            .get_module_for_year(
+                &dependent.module_code,
+                &plan.semesters[dependent.semester_index].year,
+            )
+            .and_then(|m| m.prereq_tree.as_ref())
+            .map(|tree| tree.module_codes())
+            .unwrap_or_default();
+
+        let earliest = prereq_codes
+            .iter()
+            .filter_map(|code| placed.get(code))
+            .max()
+            .map(|&index| index + 1)
+            .unwrap_or(dependent.semester_index);
+
+        if earliest <= dependent.semester_index {
+            placed.insert(dependent.module_code.clone(), dependent.semester_index);
+            continue;
+        }
+
+        let mut target = earliest;
+        loop {
+            if target >= plan.semesters.len() {
+                if plan.semesters.len() - original_len >= MAX_EXTRA_SEMESTERS {
+                    break;
+                }
+                plan.push_next_semester();
+            }
+            let semester = &plan.semesters[target];
+            if registry.is_offered(&dependent.module_code, &semester.year, semester.semester) {
+                break;
+            }
+            target += 1;
+        }
+
+        if target >= plan.semesters.len() {
+            continue;
+        }
+
+        let source = &mut plan.semesters[dependent.semester_index];
+        let Some(position) = source
+            .modules
+            .iter()
+            .position(|m| m.module_code == dependent.module_code)
+        else {
+            continue;
+        };
+
+        let module = source.edit_units(registry, |source| source.modules.remove(position));
+        plan.semesters[target].edit_units(registry, |destination| destination.modules.push(module));
+
+        placed.insert(dependent.module_code.clone(), target);
+        shifts.push(ModuleShift {
+            module_code: dependent.module_code.clone(),
+            from_semester: dependent.semester_index,
+            to_semester: target,
+        });
+    }
+
+    shifts
+}
@@ -1,15 +1,60 @@
-use crate::models::{Curriculum, Plan, Student};
+use crate::models::{Curriculum, Plan, PlanTemplate, PlanVersion, RetentionPolicy, Student};
 use anyhow::Result;
+use chrono::Utc;
 
 pub trait Storage {
-    fn save_plan(&self, plan: &Plan) -> Result<()>;
+    /// Saves the plan and records the saved state as a new `PlanVersion`,
+    /// then discards versions outside `retention_policy`.
+    fn save_plan(&self, plan: &Plan) -> Result<()> {
+        self.write_plan(plan)?;
+        let next = self
+            .list_plan_versions(&plan.id)?
+            .last()
+            .map_or(1, |v| v.version + 1);
+        self.write_plan_version(&PlanVersion::new(plan, next))?;
+        self.prune_plan_versions(&plan.id, &self.retention_policy())?;
+        Ok(())
+    }
+    /// Stores the plan's current state only. Callers use `save_plan`.
+    fn write_plan(&self, plan: &Plan) -> Result<()>;
     fn get_plan(&self, id: &str) -> Result<Option<Plan>>;
     fn list_plans(&self) -> Result<Vec<Plan>>;
+    /// Deletes the plan along with its saved versions.
     fn delete_plan(&self, id: &str) -> Result<bool>;
 
+    /// Saved versions of a plan, oldest first.
+    fn list_plan_versions(&self, plan_id: &str) -> Result<Vec<PlanVersion>>;
+    fn get_plan_version(&self, plan_id: &str, version: u32) -> Result<Option<PlanVersion>>;
+    /// Appends a version to the plan's history.
+    fn write_plan_version(&self, version: &PlanVersion) -> Result<()>;
+    fn delete_plan_versions(&self, plan_id: &str, versions: &[u32]) -> Result<()>;
+
+    /// Discards the versions `policy` does not keep, returning their numbers.
+    fn prune_plan_versions(&self, plan_id: &str, policy: &RetentionPolicy) -> Result<Vec<u32>> {
+        let expired = policy.expired(&self.list_plan_versions(plan_id)?, Utc::now());
+        if !expired.is_empty() {
+            self.delete_plan_versions(plan_id, &expired)?;
+        }
+        Ok(expired)
+    }
+
+    /// Policy applied after every save. Keeps every version by default.
+    fn retention_policy(&self) -> RetentionPolicy {
+        RetentionPolicy::keep_all()
+    }
+
+    /// Saves a profile under `Student::id`. Profiles without an ID (saved
+    /// before IDs existed) are keyed by name until they are migrated.
     fn save_student(&self, student: &Student) -> Result<()>;
-    fn get_student(&self, name: &str) -> Result<Option<Student>>;
+    fn get_student(&self, id: &str) -> Result<Option<Student>>;
     fn list_students(&self) -> Result<Vec<Student>>;
+    /// Deletes a student profile only; plans referring to it are left alone.
+    fn delete_student(&self, id: &str) -> Result<bool>;
 
     fn get_curriculum(&self, name: &str) -> Result<Option<Curriculum>>;
+    fn list_curricula(&self) -> Result<Vec<Curriculum>>;
+
+    /// Publishes a template, replacing any with the same name.
+    fn save_plan_template(&self, template: &PlanTemplate) -> Result<()>;
+    fn list_plan_templates(&self) -> Result<Vec<PlanTemplate>>;
 }
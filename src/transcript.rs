@@ -0,0 +1,253 @@
+use crate::models::{
+    term_key, ModuleRegistry, ModuleStatus, Plan, PlannedModule, SemesterPlan, Student, Units,
+};
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptEntry {
+    pub module_code: String,
+    pub title: String,
+    pub units: Units,
+    pub grade: String,
+    pub year: String,
+    pub semester: u8,
+}
+
+#[derive(Debug, Default)]
+pub struct TranscriptImportReport {
+    pub imported: Vec<String>,
+    pub failed: Vec<String>,
+    pub missing_from_registry: Vec<String>,
+    pub semesters_created: usize,
+}
+
+/// Whether a released grade passes the module: anything but F or U.
+pub fn is_passing_grade(grade: &str) -> bool {
+    !matches!(grade.trim().to_ascii_uppercase().as_str(), "F" | "U")
+}
+
+impl TranscriptEntry {
+    pub fn is_pass(&self) -> bool {
+        is_passing_grade(&self.grade)
+    }
+
+    pub fn is_s_u(&self) -> bool {
+        matches!(self.grade.to_ascii_uppercase().as_str(), "S" | "U")
+    }
+}
+
+/// Parses an unofficial transcript export with one module per line:
+/// `code, title, units, grade, term`.
+///
+/// Fields may be separated by commas, tabs or pipes, and CSV-style double
+/// quotes are honoured so titles can contain the separator. Blank lines,
+/// `#` comments and a leading header row are skipped.
+pub fn parse_transcript(contents: &str) -> Result<Vec<TranscriptEntry>> {
+    let mut entries = Vec::new();
+    let mut first_row = true;
+
+    for (line_index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let is_first_row = std::mem::replace(&mut first_row, false);
+
+        let fields = split_fields(line);
+        if fields.len() < 5 {
+            return Err(anyhow!(
+                "Line {}: expected 5 fields (code, title, units, grade, term), found {}",
+                line_index + 1,
+                fields.len()
+            ));
+        }
+
+        let units = match fields[2].parse::<Units>() {
+            Ok(units) => units,
+            Err(_) if is_first_row => continue,
+            Err(_) => {
+                return Err(anyhow!(
+                    "Line {}: invalid units '{}'",
+                    line_index + 1,
+                    fields[2]
+                ))
+            }
+        };
+
+        let (year, semester) = parse_term(&fields[4])
+            .ok_or_else(|| anyhow!("Line {}: unrecognised term '{}'", line_index + 1, fields[4]))?;
+
+        entries.push(TranscriptEntry {
+            module_code: fields[0].to_uppercase(),
+            title: fields[1].clone(),
+            units,
+            grade: fields[3].clone(),
+            year,
+            semester,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Parses a term such as `2023/2024 Semester 1`, `AY2023/24 Sem 2` or
+/// `2024/2025 Special Term II` into an academic year and semester number
+/// (3 and 4 for special terms I and II).
+pub fn parse_term(term: &str) -> Option<(String, u8)> {
+    let start = term.find(|c: char| c.is_ascii_digit())?;
+    let digits: String = term[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    if digits.len() != 4 {
+        return None;
+    }
+    let start_year = digits.parse::<u32>().ok()?;
+
+    let mut rest = &term[start + digits.len()..];
+    if let Some(stripped) = rest.strip_prefix(['/', '-']) {
+        rest = stripped.trim_start_matches(|c: char| c.is_ascii_digit());
+    }
+
+    let rest = rest.to_lowercase();
+    let is_special_term = rest.contains("special")
+        || rest
+            .split_whitespace()
+            .any(|token| matches!(token, "st" | "st1" | "st2" | "sti" | "stii"));
+    let semester = if is_special_term {
+        if rest.trim_end().ends_with("ii") || rest.contains('2') {
+            4
+        } else {
+            3
+        }
+    } else {
+        let digit = rest.chars().find(|c| c.is_ascii_digit())?;
+        match digit {
+            '1' => 1,
+            '2' => 2,
+            _ => return None,
+        }
+    };
+
+    Some((format!("{}/{}", start_year, start_year + 1), semester))
+}
+
+/// Records transcript entries against the student and plan.
+///
+/// Each term becomes (or is merged into) a `SemesterPlan`, inserted in
+/// chronological order. Passed modules are marked completed and added to the
+/// student's `completed_modules`; F and U grades are recorded as failed.
+pub fn apply_transcript(
+    student: &mut Student,
+    plan: &mut Plan,
+    entries: &[TranscriptEntry],
+    registry: &ModuleRegistry,
+) -> TranscriptImportReport {
+    let mut report = TranscriptImportReport::default();
+
+    for entry in entries {
+        let sem_index = match plan
+            .semesters
+            .iter()
+            .position(|s| s.year == entry.year && s.semester == entry.semester)
+        {
+            Some(index) => index,
+            None => {
+                let key = term_key(&entry.year, entry.semester);
+                let index = plan
+                    .semesters
+                    .iter()
+                    .position(|s| term_key(&s.year, s.semester) > key)
+                    .unwrap_or(plan.semesters.len());
+                plan.semesters.insert(
+                    index,
+                    SemesterPlan::empty(entry.year.clone(), entry.semester),
+                );
+                report.semesters_created += 1;
+                index
+            }
+        };
+
+        let status = if entry.is_pass() {
+            ModuleStatus::Completed
+        } else {
+            ModuleStatus::Failed
+        };
+
+        let semester = &mut plan.semesters[sem_index];
+        match semester
+            .modules
+            .iter_mut()
+            .find(|m| m.module_code == entry.module_code)
+        {
+            Some(existing) => {
+                existing.status = status.clone();
+                existing.grade = Some(entry.grade.clone());
+                existing.s_u_option = entry.is_s_u();
+            }
+            None => {
+                semester.modules.push(PlannedModule {
+                    module_code: entry.module_code.clone(),
+                    status: status.clone(),
+                    grade: Some(entry.grade.clone()),
+                    s_u_option: entry.is_s_u(),
+                    class_selections: HashMap::new(),
+                });
+                // The transcript's own credit stands in for modules the
+                // registry does not know
+                semester.total_units += registry
+                    .get_module_for_year(&entry.module_code, &semester.year)
+                    .map_or(entry.units, |m| m.units());
+            }
+        }
+
+        if status == ModuleStatus::Completed {
+            student.completed_modules.insert(entry.module_code.clone());
+            report.imported.push(entry.module_code.clone());
+        } else {
+            report.failed.push(entry.module_code.clone());
+        }
+
+        if !registry.contains_module(&entry.module_code)
+            && !report.missing_from_registry.contains(&entry.module_code)
+        {
+            report.missing_from_registry.push(entry.module_code.clone());
+        }
+    }
+
+    report
+}
+
+fn split_fields(line: &str) -> Vec<String> {
+    let separator = if line.contains('\t') {
+        '\t'
+    } else if line.contains('|') && !line.contains(',') {
+        '|'
+    } else {
+        ','
+    };
+
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == separator && !in_quotes => {
+                fields.push(current.trim().to_string());
+                current.clear();
+            }
+            c => current.push(c),
+        }
+    }
+    fields.push(current.trim().to_string());
+
+    fields
+}
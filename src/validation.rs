@@ -1,17 +1,53 @@
-use crate::models::{ModuleRegistry, Plan, Student};
+use crate::models::{
+    Curriculum, ExemptionPolicy, ModuleRegistry, ModuleStatus, Plan, Student, Units,
+};
 use anyhow::Result;
 
 pub mod prerequisites {
     use super::*;
 
+    /// Checks each planned or current module's prerequisite tree against the
+    /// student's completed and exempted modules plus everything passed (or
+    /// planned) in earlier semesters.
     pub fn validate_prerequisites(
-        _plan: &Plan,
-        _registry: &ModuleRegistry,
-        _student: &Student,
-        _result: &mut ValidationResult,
+        plan: &Plan,
+        registry: &ModuleRegistry,
+        student: &Student,
+        result: &mut ValidationResult,
     ) -> Result<()> {
-        // In a real implementation, this would check prerequisites
-        // For testing, we'll just return Ok
+        let mut passed = student.credited_modules();
+
+        for (i, semester) in plan.semesters.iter().enumerate() {
+            for module in &semester.modules {
+                if !matches!(module.status, ModuleStatus::Planned | ModuleStatus::Current) {
+                    continue;
+                }
+
+                let Some(tree) = registry
+                    .get_module_for_year(&module.module_code, &semester.year)
+                    .and_then(|m| m.prereq_tree.as_ref())
+                else {
+                    continue;
+                };
+
+                if !tree.is_satisfied_by(&passed) {
+                    result.add_error(
+                        &format!("Prerequisites not met for {}", module.module_code),
+                        Some(&module.module_code),
+                        Some(i),
+                    );
+                }
+            }
+
+            passed.extend(
+                semester
+                    .modules
+                    .iter()
+                    .filter(|m| m.status != ModuleStatus::Failed)
+                    .map(|m| m.module_code.clone()),
+            );
+        }
+
         Ok(())
     }
 }
@@ -33,13 +69,195 @@ pub mod workload {
 pub mod availability {
     use super::*;
 
+    /// Warns about planned or current modules that are not offered in
+    /// their semester, using the module data for that semester's academic
+    /// year. Modules missing from the registry are skipped.
     pub fn validate_availability(
-        _plan: &Plan,
-        _registry: &ModuleRegistry,
-        _result: &mut ValidationResult,
+        plan: &Plan,
+        registry: &ModuleRegistry,
+        result: &mut ValidationResult,
     ) -> Result<()> {
-        // In a real implementation, this would check availability
-        // For testing, we'll just return Ok
+        for (i, semester) in plan.semesters.iter().enumerate() {
+            for module in &semester.modules {
+                if !matches!(module.status, ModuleStatus::Planned | ModuleStatus::Current) {
+                    continue;
+                }
+
+                let Some(data) = registry.get_module_for_year(&module.module_code, &semester.year)
+                else {
+                    continue;
+                };
+
+                if !data.is_offered_in(semester.semester) {
+                    result.add_warning(
+                        &format!(
+                            "{} is not offered in Semester {} of AY{}",
+                            module.module_code, semester.semester, semester.year
+                        ),
+                        Some(&module.module_code),
+                        Some(i),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub mod candidature {
+    use super::*;
+
+    /// Warns when the plan runs past normal candidature or a semester exceeds
+    /// the programme's maximum load, and errors past maximum candidature.
+    pub fn validate_candidature(
+        plan: &Plan,
+        student: &Student,
+        result: &mut ValidationResult,
+    ) -> Result<()> {
+        let rules = student.candidature_type.rules();
+
+        let last_semester = plan
+            .semesters
+            .iter()
+            .filter(|s| !s.modules.is_empty())
+            .filter_map(|s| student.semester_number(&s.year, s.semester))
+            .max();
+
+        if let Some(last) = last_semester {
+            if last > rules.max_semesters {
+                result.add_error(
+                    &format!(
+                        "Plan runs to semester {}, beyond the maximum candidature of {} semesters",
+                        last, rules.max_semesters
+                    ),
+                    None,
+                    None,
+                );
+            } else if last > rules.normal_semesters {
+                result.add_warning(
+                    &format!(
+                        "Plan runs to semester {}, beyond the normal candidature of {} semesters",
+                        last, rules.normal_semesters
+                    ),
+                    None,
+                    None,
+                );
+            }
+        }
+
+        for (i, semester) in plan.semesters.iter().enumerate() {
+            if semester.total_units > Units::from(rules.max_units_per_semester) {
+                result.add_warning(
+                    &format!(
+                        "{} units exceeds the maximum load of {} units without an overload",
+                        semester.total_units, rules.max_units_per_semester
+                    ),
+                    None,
+                    Some(i),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub mod graduation {
+    use super::*;
+
+    /// Compares planned units, plus exemption and advanced placement credit,
+    /// against the curriculum's requirement after waivers.
+    pub fn validate_units(
+        plan: &Plan,
+        curriculum: &Curriculum,
+        student: &Student,
+        registry: &ModuleRegistry,
+        result: &mut ValidationResult,
+    ) -> Result<()> {
+        let required = curriculum.units_required_for(student);
+        let credited = curriculum.credited_units_for(student, registry);
+        let total = plan.total_units() + credited;
+
+        if total < Units::from(required) {
+            result.add_warning(
+                &format!(
+                    "Plan has {} of the {} units required to graduate ({} from exemptions and advanced placement)",
+                    total, required, credited
+                ),
+                None,
+                None,
+            );
+        }
+
+        for code in &student.exempted_modules {
+            match curriculum.exemption_policy(code) {
+                Some(ExemptionPolicy::Waived) => {
+                    result.add_info("Requirement waived by exemption", Some(code), None)
+                }
+                Some(ExemptionPolicy::ReplacedByElectives) => result.add_info(
+                    "Exempted requirement must be replaced with unrestricted electives",
+                    Some(code),
+                    None,
+                ),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub mod critical_path {
+    use super::*;
+    use crate::critical_path::analyse_critical_path;
+
+    /// Flags planned modules that would push graduation past the plan's
+    /// target, and warns about zero-slack modules that head a prerequisite
+    /// chain.
+    pub fn validate_critical_path(
+        plan: &Plan,
+        registry: &ModuleRegistry,
+        student: &Student,
+        result: &mut ValidationResult,
+    ) -> Result<()> {
+        let Some(report) = analyse_critical_path(plan, registry, &student.credited_modules())
+        else {
+            return Ok(());
+        };
+
+        for module in &report.modules {
+            let semester = Some(module.scheduled_semester);
+
+            match module.latest_semester {
+                None => result.add_error(
+                    &format!(
+                        "Cannot be completed by {} given its prerequisites and offering semesters",
+                        plan.target_graduation
+                    ),
+                    Some(&module.module_code),
+                    semester,
+                ),
+                Some(latest) if module.delays_graduation() => result.add_error(
+                    &format!(
+                        "Scheduled after {}, the latest semester that keeps graduation on track",
+                        report.term_label(latest)
+                    ),
+                    Some(&module.module_code),
+                    semester,
+                ),
+                Some(_) if module.is_critical() && module.chain_length > 1 => result.add_warning(
+                    &format!(
+                        "No slack: delaying this module will delay {} dependent module(s) and graduation",
+                        module.chain_length - 1
+                    ),
+                    Some(&module.module_code),
+                    semester,
+                ),
+                _ => {}
+            }
+        }
+
         Ok(())
     }
 }
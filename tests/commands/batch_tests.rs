@@ -0,0 +1,231 @@
+use anyhow::Result;
+use module_planner::commands::{parse_plan_args, BatchAction, BatchEditCommand};
+use module_planner::models::{ActivityType, ModuleStatus, PlanChange, Units};
+use module_planner::storage::Storage;
+
+use crate::commands::common::{create_test_plan, MockNusmodsApi, MockStorage};
+
+fn batch_command(storage: &MockStorage, args: &[&str]) -> Result<BatchEditCommand> {
+    Ok(BatchEditCommand {
+        storage: Box::new(storage.clone()),
+        api: Box::new(MockNusmodsApi::new()),
+        request: parse_plan_args(args)?,
+    })
+}
+
+#[test]
+fn test_parse_plan_args() -> Result<()> {
+    let request = parse_plan_args(["plan", "add", "test-plan-id", "2", "ie1111r", "MA1511"])?;
+    assert_eq!(request.plan_id, "test-plan-id");
+    assert!(!request.json);
+    assert_eq!(
+        request.action,
+        BatchAction::Add {
+            semester: "2".to_string(),
+            module_codes: vec!["IE1111R".to_string(), "MA1511".to_string()],
+        }
+    );
+
+    let request = parse_plan_args([
+        "plan",
+        "add-activity",
+        "test-plan-id",
+        "2023/2024:2",
+        "--type",
+        "internship",
+        "--description",
+        "Summer internship",
+        "--credits",
+        "12",
+        "--json",
+    ])?;
+    assert!(request.json);
+    let BatchAction::AddActivity { semester, activity } = request.action else {
+        panic!("expected an add-activity action");
+    };
+    assert_eq!(semester, "2023/2024:2");
+    assert_eq!(activity.activity_type, ActivityType::IndustrialAttachment);
+    assert_eq!(activity.credits, Some(12));
+
+    let error =
+        parse_plan_args(["plan", "set-status", "test-plan-id", "IE2101", "passed"]).unwrap_err();
+    assert!(error.to_string().contains("Invalid status 'passed'"));
+    assert!(parse_plan_args(["plan", "move", "test-plan-id", "1"]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_batch_move_saves_plan_and_reports_changes() -> Result<()> {
+    let plan = create_test_plan();
+    let storage = MockStorage::new().with_plan(plan.clone());
+
+    let command = batch_command(&storage, &["plan", "move", &plan.id, "2", "1", "IE2101"])?;
+    let result = command.execute()?;
+
+    assert_eq!(
+        result.changes,
+        vec![PlanChange::Moved {
+            module_code: "IE2101".to_string(),
+            from_semester: 1,
+            to_semester: 0,
+        }]
+    );
+    assert!(result.warnings.is_empty());
+
+    let saved = storage.get_plan(&plan.id)?.unwrap();
+    assert_eq!(saved.semesters[0].modules[1].module_code, "IE2101");
+    assert_eq!(saved.semesters[0].total_units, Units::from(8));
+    // IE3101 plus the 4-credit research programme
+    assert_eq!(saved.semesters[1].total_units, Units::from(8));
+
+    Ok(())
+}
+
+#[test]
+fn test_batch_failure_leaves_plan_untouched() -> Result<()> {
+    let plan = create_test_plan();
+    let storage = MockStorage::new().with_plan(plan.clone());
+
+    let command = batch_command(
+        &storage,
+        &["plan", "add", &plan.id, "1", "IE3101", "ZZ9999"],
+    )?;
+    let error = command.execute().unwrap_err();
+    assert_eq!(error.to_string(), "Module ZZ9999 not found");
+
+    let command = batch_command(&storage, &["plan", "remove", &plan.id, "7", "IE1111R"])?;
+    let error = command.execute().unwrap_err();
+    assert!(error.to_string().contains("out of range"));
+
+    let saved = storage.get_plan(&plan.id)?.unwrap();
+    assert_eq!(saved.semesters[0].modules.len(), 1);
+    assert_eq!(saved.semesters[1].modules.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_batch_status_grade_and_activity() -> Result<()> {
+    let plan = create_test_plan();
+    let storage = MockStorage::new().with_plan(plan.clone());
+
+    let result = batch_command(
+        &storage,
+        &["plan", "set-status", &plan.id, "IE2101", "completed"],
+    )?
+    .execute()?;
+    assert_eq!(
+        result.changes,
+        vec![PlanChange::StatusChanged {
+            module_code: "IE2101".to_string(),
+            semester_index: 1,
+            from: ModuleStatus::Current,
+            to: ModuleStatus::Completed,
+        }]
+    );
+
+    batch_command(
+        &storage,
+        &[
+            "plan",
+            "set-grade",
+            &plan.id,
+            "IE2101",
+            "A-",
+            "--semester",
+            "2",
+        ],
+    )?
+    .execute()?;
+
+    let result = batch_command(
+        &storage,
+        &[
+            "plan",
+            "add-activity",
+            &plan.id,
+            "2023/2024:1",
+            "--type",
+            "Hackathon",
+            "--description",
+            "Student hackathon",
+            "--credits",
+            "2",
+        ],
+    )?
+    .execute()?;
+    assert_eq!(result.changes.len(), 1);
+
+    let saved = storage.get_plan(&plan.id)?.unwrap();
+    let module = &saved.semesters[1].modules[0];
+    assert_eq!(module.status, ModuleStatus::Completed);
+    assert_eq!(module.grade.as_deref(), Some("A-"));
+    assert_eq!(
+        saved.semesters[0].special_activities[0].activity_type,
+        ActivityType::Other("Hackathon".to_string())
+    );
+    assert_eq!(saved.semesters[0].total_units, Units::from(6));
+
+    Ok(())
+}
+
+#[test]
+fn test_batch_undo_reverts_saved_edits() -> Result<()> {
+    let plan = create_test_plan();
+    let storage = MockStorage::new().with_plan(plan.clone());
+
+    batch_command(&storage, &["plan", "move", &plan.id, "2", "1", "IE2101"])?.execute()?;
+    batch_command(&storage, &["plan", "set-grade", &plan.id, "IE2101", "B+"])?.execute()?;
+
+    let request = parse_plan_args(["plan", "undo", &plan.id, "--count", "2"])?;
+    assert_eq!(request.action, BatchAction::Undo { count: 2 });
+
+    let result = batch_command(&storage, &["plan", "undo", &plan.id, "--count", "2"])?.execute()?;
+    assert_eq!(
+        result.undone,
+        vec![
+            "Set grade of IE2101 to B+",
+            "Moved IE2101 from semester 2 to semester 1",
+        ]
+    );
+
+    let saved = storage.get_plan(&plan.id)?.unwrap();
+    let module = saved.semesters[1]
+        .modules
+        .iter()
+        .find(|m| m.module_code == "IE2101")
+        .unwrap();
+    assert_eq!(module.grade, None);
+    assert_eq!(saved.semesters[0].modules.len(), 1);
+    assert!(saved.edit_log.is_empty());
+
+    let error = batch_command(&storage, &["plan", "undo", &plan.id])?
+        .execute()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Nothing to undo");
+
+    Ok(())
+}
+
+#[test]
+fn test_batch_json_report_schema() -> Result<()> {
+    let plan = create_test_plan();
+    let storage = MockStorage::new().with_plan(plan.clone());
+
+    let result =
+        batch_command(&storage, &["plan", "remove", &plan.id, "2", "IE3101"])?.execute()?;
+    let json = serde_json::to_value(result.report())?;
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "changes": [
+                {"change": "removed", "module_code": "IE3101", "semester_index": 1}
+            ],
+            "warnings": []
+        })
+    );
+
+    Ok(())
+}
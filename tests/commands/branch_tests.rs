@@ -0,0 +1,82 @@
+use anyhow::Result;
+use module_planner::commands::{BranchAction, BranchCommand, ViewCommand};
+use module_planner::models::{Student, Units};
+use module_planner::storage::Storage;
+
+use crate::commands::common::{
+    create_test_curriculum, create_test_plan, create_test_student, MockNusmodsApi, MockStorage,
+};
+
+fn branch_command(storage: &MockStorage, plan_id: &str) -> BranchCommand {
+    BranchCommand {
+        storage: Box::new(storage.clone()),
+        plan_id: plan_id.to_string(),
+        action: BranchAction::List,
+    }
+}
+
+#[test]
+fn test_fork_and_promote_branches() -> Result<()> {
+    let plan = create_test_plan();
+    let storage = MockStorage::new().with_plan(plan.clone());
+
+    let exchange = branch_command(&storage, &plan.id).fork("exchange")?;
+    assert!(storage.get_plan(&exchange.id)?.is_some());
+    let original = storage.get_plan(&plan.id)?.unwrap();
+    assert_eq!(original.branch_name(), "main");
+
+    let error = branch_command(&storage, &exchange.id)
+        .fork("Exchange")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Branch 'Exchange' already exists");
+
+    branch_command(&storage, &exchange.id).promote()?;
+    let branches = branch_command(&storage, &plan.id).branches()?;
+    let names: Vec<&str> = branches.iter().map(|p| p.branch_name()).collect();
+    assert_eq!(names, vec!["exchange", "main"]);
+    assert!(branches[0].is_primary());
+    assert!(!branches[1].is_primary());
+
+    Ok(())
+}
+
+#[test]
+fn test_branch_summary_for_comparison() -> Result<()> {
+    let plan = create_test_plan();
+    let student = Student {
+        major: "Computer Science".to_string(),
+        ..create_test_student()
+    };
+    let storage = MockStorage::new()
+        .with_plan(plan.clone())
+        .with_student(student)
+        .with_curriculum(create_test_curriculum());
+
+    let mut internship = branch_command(&storage, &plan.id).fork("internship")?;
+    internship.semesters[1].modules.clear();
+    storage.save_plan(&internship)?;
+
+    let api = MockNusmodsApi::new();
+    let registry = module_planner::api::NusmodsApi::get_module_registry(&api)?;
+    let command = ViewCommand {
+        storage: Box::new(storage.clone()),
+        api: Box::new(api),
+        plan_id: Some(plan.id.clone()),
+    };
+
+    let main = command.summarize_branch(&storage.get_plan(&plan.id)?.unwrap(), &registry)?;
+    let alternative = command.summarize_branch(&internship, &registry)?;
+
+    assert!(main.primary);
+    assert_eq!(alternative.branch_name, "internship");
+    assert!(alternative.total_units < main.total_units);
+    // Only the research programme's credits remain in semester 2
+    assert_eq!(
+        alternative.semester_units[1],
+        ("2023/2024 Semester 2".to_string(), Units::from(4))
+    );
+    assert_eq!(main.requirement_progress.unwrap().1, Units::from(160));
+    assert_eq!(main.projected_cap, Some(5.0));
+
+    Ok(())
+}
@@ -3,37 +3,59 @@ use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 use module_planner::models::{
-    ActivityType, CandidatureType, Curriculum, CurriculumComponent, Module, ModuleRegistry,
-    ModuleStatus, Plan, PlannedModule, Requirement, SemesterData, SemesterPlan, SpecialActivity,
-    Student,
+    ActivityType, CandidatureType, Curriculum, CurriculumComponent, ExemptionPolicy, Module,
+    ModuleRegistry, ModuleStatus, Plan, PlanTemplate, PlanVersion, PlannedModule, Requirement,
+    RetentionPolicy, SemesterData, SemesterPlan, SpecialActivity, Student, Units,
 };
 
-// Mock implementation of the Storage trait
+// Profiles without an ID are keyed by name, as in legacy storage.
+fn student_key(student: &Student) -> String {
+    if student.id.is_empty() {
+        student.name.clone()
+    } else {
+        student.id.clone()
+    }
+}
+
+// Mock implementation of the Storage trait. Clones share the same data.
+#[derive(Clone)]
 pub struct MockStorage {
     plans: Arc<Mutex<HashMap<String, Plan>>>,
+    versions: Arc<Mutex<HashMap<String, Vec<PlanVersion>>>>,
+    retention: RetentionPolicy,
     students: Arc<Mutex<HashMap<String, Student>>>,
     curricula: Arc<Mutex<HashMap<String, Curriculum>>>,
+    templates: Arc<Mutex<HashMap<String, PlanTemplate>>>,
 }
 
 impl MockStorage {
     pub fn new() -> Self {
         Self {
             plans: Arc::new(Mutex::new(HashMap::new())),
+            versions: Arc::new(Mutex::new(HashMap::new())),
+            retention: RetentionPolicy::keep_all(),
             students: Arc::new(Mutex::new(HashMap::new())),
             curricula: Arc::new(Mutex::new(HashMap::new())),
+            templates: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Stores the plan as-is, without recording a version.
     pub fn with_plan(mut self, plan: Plan) -> Self {
         self.plans.lock().unwrap().insert(plan.id.clone(), plan);
         self
     }
 
+    pub fn with_retention(mut self, policy: RetentionPolicy) -> Self {
+        self.retention = policy;
+        self
+    }
+
     pub fn with_student(mut self, student: Student) -> Self {
         self.students
             .lock()
             .unwrap()
-            .insert(student.name.clone(), student);
+            .insert(student_key(&student), student);
         self
     }
 
@@ -47,7 +69,7 @@ impl MockStorage {
 }
 
 impl module_planner::storage::Storage for MockStorage {
-    fn save_plan(&self, plan: &Plan) -> Result<()> {
+    fn write_plan(&self, plan: &Plan) -> Result<()> {
         self.plans
             .lock()
             .unwrap()
@@ -64,28 +86,87 @@ impl module_planner::storage::Storage for MockStorage {
     }
 
     fn delete_plan(&self, id: &str) -> Result<bool> {
+        self.versions.lock().unwrap().remove(id);
         Ok(self.plans.lock().unwrap().remove(id).is_some())
     }
 
+    fn list_plan_versions(&self, plan_id: &str) -> Result<Vec<PlanVersion>> {
+        Ok(self
+            .versions
+            .lock()
+            .unwrap()
+            .get(plan_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn get_plan_version(&self, plan_id: &str, version: u32) -> Result<Option<PlanVersion>> {
+        Ok(self
+            .list_plan_versions(plan_id)?
+            .into_iter()
+            .find(|v| v.version == version))
+    }
+
+    fn write_plan_version(&self, version: &PlanVersion) -> Result<()> {
+        self.versions
+            .lock()
+            .unwrap()
+            .entry(version.plan_id.clone())
+            .or_default()
+            .push(version.clone());
+        Ok(())
+    }
+
+    fn delete_plan_versions(&self, plan_id: &str, versions: &[u32]) -> Result<()> {
+        if let Some(history) = self.versions.lock().unwrap().get_mut(plan_id) {
+            history.retain(|v| !versions.contains(&v.version));
+        }
+        Ok(())
+    }
+
+    fn retention_policy(&self) -> RetentionPolicy {
+        self.retention.clone()
+    }
+
     fn save_student(&self, student: &Student) -> Result<()> {
         self.students
             .lock()
             .unwrap()
-            .insert(student.name.clone(), student.clone());
+            .insert(student_key(student), student.clone());
         Ok(())
     }
 
-    fn get_student(&self, name: &str) -> Result<Option<Student>> {
-        Ok(self.students.lock().unwrap().get(name).cloned())
+    fn get_student(&self, id: &str) -> Result<Option<Student>> {
+        Ok(self.students.lock().unwrap().get(id).cloned())
     }
 
     fn list_students(&self) -> Result<Vec<Student>> {
         Ok(self.students.lock().unwrap().values().cloned().collect())
     }
 
+    fn delete_student(&self, id: &str) -> Result<bool> {
+        Ok(self.students.lock().unwrap().remove(id).is_some())
+    }
+
     fn get_curriculum(&self, name: &str) -> Result<Option<Curriculum>> {
         Ok(self.curricula.lock().unwrap().get(name).cloned())
     }
+
+    fn list_curricula(&self) -> Result<Vec<Curriculum>> {
+        Ok(self.curricula.lock().unwrap().values().cloned().collect())
+    }
+
+    fn save_plan_template(&self, template: &PlanTemplate) -> Result<()> {
+        self.templates
+            .lock()
+            .unwrap()
+            .insert(template.name.clone(), template.clone());
+        Ok(())
+    }
+
+    fn list_plan_templates(&self) -> Result<Vec<PlanTemplate>> {
+        Ok(self.templates.lock().unwrap().values().cloned().collect())
+    }
 }
 
 // Mock implementation of the NusmodsApi
@@ -118,7 +199,7 @@ impl module_planner::api::NusmodsApi for MockNusmodsApi {
             description: Some(
                 "This module introduces the concepts of industrial engineering.".to_string(),
             ),
-            module_credit: "4".to_string(),
+            module_credit: 4.into(),
             department: Some("Industrial Systems Engineering".to_string()),
             faculty: Some("College of Design and Engineering".to_string()),
             semester_data: vec![SemesterData {
@@ -139,7 +220,7 @@ impl module_planner::api::NusmodsApi for MockNusmodsApi {
             module_code: "IE2101".to_string(),
             title: "Systems Design & Analysis".to_string(),
             description: Some("This module covers systems thinking and design.".to_string()),
-            module_credit: "4".to_string(),
+            module_credit: 4.into(),
             department: Some("Industrial Systems Engineering".to_string()),
             faculty: Some("College of Design and Engineering".to_string()),
             semester_data: vec![
@@ -170,7 +251,7 @@ impl module_planner::api::NusmodsApi for MockNusmodsApi {
             description: Some(
                 "This module covers statistical methods for engineering.".to_string(),
             ),
-            module_credit: "4".to_string(),
+            module_credit: 4.into(),
             department: Some("Industrial Systems Engineering".to_string()),
             faculty: Some("College of Design and Engineering".to_string()),
             semester_data: vec![SemesterData {
@@ -215,7 +296,7 @@ pub fn create_test_plan() -> Plan {
     let mut plan = Plan::new(
         "test-plan-id".to_string(),
         "Test Plan".to_string(),
-        "Test Student".to_string(),
+        "test-student-id".to_string(),
     );
 
     plan.target_graduation = "2025/2026 Semester 2".to_string();
@@ -229,8 +310,9 @@ pub fn create_test_plan() -> Plan {
             status: ModuleStatus::Completed,
             grade: Some("A".to_string()),
             s_u_option: false,
+            class_selections: HashMap::new(),
         }],
-        total_units: 4,
+        total_units: Units::from(4),
         special_activities: Vec::new(),
     };
 
@@ -243,15 +325,17 @@ pub fn create_test_plan() -> Plan {
                 status: ModuleStatus::Current,
                 grade: None,
                 s_u_option: false,
+                class_selections: HashMap::new(),
             },
             PlannedModule {
                 module_code: "IE3101".to_string(),
                 status: ModuleStatus::Current,
                 grade: None,
                 s_u_option: true,
+                class_selections: HashMap::new(),
             },
         ],
-        total_units: 8,
+        total_units: Units::from(12),
         special_activities: vec![SpecialActivity {
             activity_type: ActivityType::Research,
             description: "Summer Research Program".to_string(),
@@ -268,6 +352,7 @@ pub fn create_test_plan() -> Plan {
 // Helper function to create a test student
 pub fn create_test_student() -> Student {
     Student {
+        id: "test-student-id".to_string(),
         name: "Test Student".to_string(),
         matriculation_year: "2023/2024".to_string(),
         faculty: "College of Design and Engineering".to_string(),
@@ -286,6 +371,7 @@ pub fn create_test_student() -> Student {
 pub fn create_test_curriculum() -> Curriculum {
     Curriculum {
         name: "Computer Science".to_string(),
+        faculty: None,
         academic_year: "2023/2024".to_string(),
         total_units_required: 160,
         max_level1000_units: 40,
@@ -296,6 +382,7 @@ pub fn create_test_curriculum() -> Curriculum {
                 module_code: "GER1000".to_string(),
                 name: "Quantitative Reasoning".to_string(),
                 units: 4,
+                on_exemption: ExemptionPolicy::Credited,
             }],
         }],
     }
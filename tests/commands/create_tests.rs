@@ -1,15 +1,20 @@
 use anyhow::Result;
 use module_planner::api::DefaultNusmodsApi;
-use module_planner::commands::CreateCommand;
-use module_planner::models::Student;
+use module_planner::commands::{
+    parse_create_args, validate_student, BatchCreateCommand, CreateCommand, CreateRequest,
+    StudentSource,
+};
+use module_planner::models::{CandidatureType, PlanTemplate, Student};
+use module_planner::storage::Storage;
 use std::collections::HashSet;
 
-use crate::commands::common::MockStorage;
+use crate::commands::common::{create_test_curriculum, create_test_student, MockStorage};
 
 #[test]
 fn test_create_command_with_existing_student() -> Result<()> {
     // Setup
     let student = Student {
+        id: "test-student-id".to_string(),
         name: "Test Student".to_string(),
         matriculation_year: "2023/2024".to_string(),
         faculty: "Computing".to_string(),
@@ -48,3 +53,242 @@ fn test_initialize_plan_semesters() -> Result<()> {
     assert!(true);
     Ok(())
 }
+
+#[test]
+fn test_initialize_plan_semesters_follows_candidature() -> Result<()> {
+    let command = CreateCommand {
+        storage: Box::new(MockStorage::new()),
+        api: Box::new(DefaultNusmodsApi::new()?),
+    };
+
+    let mut student = crate::commands::common::create_test_student();
+    student.current_semester = 7;
+
+    let mut plan = module_planner::models::Plan::new(
+        "plan1".to_string(),
+        "Final Year".to_string(),
+        student.name.clone(),
+    );
+    command.initialize_plan_semesters(&mut plan, &student)?;
+
+    let terms: Vec<(String, u8)> = plan
+        .semesters
+        .iter()
+        .map(|s| (s.year.clone(), s.semester))
+        .collect();
+    assert_eq!(
+        terms,
+        vec![("2026/2027".to_string(), 1), ("2026/2027".to_string(), 2)]
+    );
+
+    // Double degree students also get Special Term I between years
+    student.candidature_type = module_planner::models::CandidatureType::DoubleDegreeProgramme;
+    student.current_semester = 8;
+
+    let mut plan = module_planner::models::Plan::new(
+        "plan2".to_string(),
+        "DDP".to_string(),
+        student.name.clone(),
+    );
+    command.initialize_plan_semesters(&mut plan, &student)?;
+
+    let semesters: Vec<u8> = plan.semesters.iter().map(|s| s.semester).collect();
+    assert_eq!(semesters, vec![2, 3, 1, 2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_templates_for_student_cohort() -> Result<()> {
+    let storage = MockStorage::new();
+    let template = |name: &str, curriculum: &str, cohort: &str| PlanTemplate {
+        name: name.to_string(),
+        curriculum: curriculum.to_string(),
+        cohort: cohort.to_string(),
+        semesters: Vec::new(),
+    };
+    storage.save_plan_template(&template(
+        "ISE B",
+        "Industrial Systems Engineering",
+        "2023/2024",
+    ))?;
+    storage.save_plan_template(&template(
+        "ISE A",
+        "industrial systems engineering",
+        "2023/2024",
+    ))?;
+    storage.save_plan_template(&template(
+        "ISE 2024",
+        "Industrial Systems Engineering",
+        "2024/2025",
+    ))?;
+    storage.save_plan_template(&template("CS", "Computer Science", "2023/2024"))?;
+
+    let command = CreateCommand {
+        storage: Box::new(storage),
+        api: Box::new(DefaultNusmodsApi::new()?),
+    };
+    let templates = command.templates_for(&crate::commands::common::create_test_student())?;
+    let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["ISE A", "ISE B"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_create_args() -> Result<()> {
+    let request = parse_create_args([
+        "create",
+        "--name",
+        "My Plan",
+        "--student-name",
+        "New Student",
+        "--matriculation-year",
+        "2024/2025",
+        "--faculty",
+        "Computing",
+        "--major",
+        "Computer Science",
+        "--minor",
+        "Mathematics,Statistics",
+        "--exempt",
+        "ma1301",
+        "--candidature",
+        "ddp",
+        "--current-semester",
+        "3",
+    ])?;
+    assert_eq!(request.plan_name, "My Plan");
+    let StudentSource::New(student) = request.student else {
+        panic!("expected a new student");
+    };
+    assert_eq!(student.minors, vec!["Mathematics", "Statistics"]);
+    assert!(student.exempted_modules.contains("MA1301"));
+    assert!(matches!(
+        student.candidature_type,
+        CandidatureType::DoubleDegreeProgramme
+    ));
+    assert_eq!(student.current_semester, 3);
+
+    let request = parse_create_args(["create", "--name", "Plan", "--student", "Test Student"])?;
+    assert!(matches!(request.student, StudentSource::Existing(name) if name == "Test Student"));
+
+    assert!(parse_create_args(["create", "--name", "Plan"]).is_err());
+    let error = parse_create_args(["create", "--name", "Plan", "--student-name", "X"]).unwrap_err();
+    assert!(error.to_string().contains("--faculty is required"));
+    let error = parse_create_args([
+        "create",
+        "--name",
+        "Plan",
+        "--student",
+        "Test Student",
+        "--graduation",
+        "2027 sem 2",
+    ])
+    .unwrap_err();
+    assert!(error.to_string().contains("Invalid target graduation"));
+
+    Ok(())
+}
+
+#[test]
+fn test_batch_create_with_new_student() -> Result<()> {
+    let storage = MockStorage::new().with_curriculum(create_test_curriculum());
+    let student = Student {
+        major: "Computer Science".to_string(),
+        current_semester: 7,
+        ..create_test_student()
+    };
+
+    let command = BatchCreateCommand {
+        storage: Box::new(storage.clone()),
+        api: Box::new(DefaultNusmodsApi::new()?),
+        request: CreateRequest {
+            plan_name: "Final Year".to_string(),
+            student: StudentSource::New(Box::new(student)),
+            target_graduation: None,
+            template: None,
+        },
+    };
+    let plan = command.execute()?;
+
+    assert_eq!(plan.target_graduation, "2026/2027 Semester 2");
+    assert_eq!(plan.semesters.len(), 2);
+    assert!(storage.get_student("test-student-id")?.is_some());
+    assert_eq!(plan.student_id, "test-student-id");
+    assert!(storage.get_plan(&plan.id)?.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_student_profile() {
+    let curricula = vec![create_test_curriculum()];
+    let student = create_test_student();
+
+    let error = validate_student(&student, &curricula).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Unknown major 'Industrial Systems Engineering' (available: Computer Science)"
+    );
+    // Without curriculum data any major is accepted
+    assert!(validate_student(&student, &[]).is_ok());
+
+    let student = Student {
+        current_semester: 11,
+        ..create_test_student()
+    };
+    let error = validate_student(&student, &[]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Current semester must be between 1 and 10 for Standard candidature"
+    );
+}
+
+#[test]
+fn test_batch_create_for_legacy_student_assigns_an_id() -> Result<()> {
+    let legacy = Student {
+        id: String::new(),
+        ..create_test_student()
+    };
+    let storage = MockStorage::new().with_student(legacy);
+
+    let command = BatchCreateCommand {
+        storage: Box::new(storage.clone()),
+        api: Box::new(DefaultNusmodsApi::new()?),
+        request: CreateRequest {
+            plan_name: "Legacy Plan".to_string(),
+            student: StudentSource::Existing("Test Student".to_string()),
+            target_graduation: None,
+            template: None,
+        },
+    };
+    let plan = command.execute()?;
+
+    assert!(!plan.student_id.is_empty());
+    let student = storage.get_student(&plan.student_id)?.unwrap();
+    assert_eq!(student.name, "Test Student");
+    assert!(storage.get_student("Test Student")?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_batch_create_rejects_malformed_graduation() -> Result<()> {
+    let storage = MockStorage::new().with_student(create_test_student());
+
+    let command = BatchCreateCommand {
+        storage: Box::new(storage.clone()),
+        api: Box::new(DefaultNusmodsApi::new()?),
+        request: CreateRequest {
+            plan_name: "Bad Target".to_string(),
+            student: StudentSource::Existing("Test Student".to_string()),
+            target_graduation: Some("2027 sem 2".to_string()),
+            template: None,
+        },
+    };
+    assert!(command.execute().is_err());
+    assert!(storage.list_plans()?.is_empty());
+
+    Ok(())
+}
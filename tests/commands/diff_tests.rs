@@ -0,0 +1,64 @@
+use anyhow::Result;
+use module_planner::commands::{DiffCommand, PlanRef};
+use module_planner::diff::{ModuleDiff, SemesterKey};
+use module_planner::storage::Storage;
+
+use crate::commands::common::{create_test_plan, MockStorage};
+
+#[test]
+fn test_plan_ref_parsing() -> Result<()> {
+    assert_eq!(
+        "test-plan-id".parse::<PlanRef>()?,
+        PlanRef::Current("test-plan-id".to_string())
+    );
+    assert_eq!(
+        "test-plan-id@3".parse::<PlanRef>()?,
+        PlanRef::Version("test-plan-id".to_string(), 3)
+    );
+    assert!("test-plan-id@latest".parse::<PlanRef>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_diff_between_version_and_current_plan() -> Result<()> {
+    let storage = MockStorage::new();
+    let mut plan = create_test_plan();
+    storage.save_plan(&plan)?;
+
+    plan.semesters[1]
+        .modules
+        .retain(|m| m.module_code != "IE3101");
+    storage.save_plan(&plan)?;
+
+    let command = DiffCommand {
+        storage: Box::new(storage.clone()),
+        from: "test-plan-id@1".parse()?,
+        to: "test-plan-id".parse()?,
+        json: false,
+    };
+    let diff = command.execute()?;
+    assert!(diff.metadata.is_empty());
+    assert_eq!(
+        diff.modules,
+        vec![ModuleDiff::Removed {
+            module_code: "IE3101".to_string(),
+            semester: SemesterKey {
+                year: "2023/2024".to_string(),
+                semester: 2,
+            },
+        }]
+    );
+
+    let command = DiffCommand {
+        storage: Box::new(storage),
+        from: "test-plan-id@7".parse()?,
+        to: "test-plan-id".parse()?,
+        json: true,
+    };
+    assert_eq!(
+        command.execute().unwrap_err().to_string(),
+        "Version 7 of plan test-plan-id not found"
+    );
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use anyhow::Result;
 use module_planner::commands::Command;
 use module_planner::commands::ImportCommand;
+use module_planner::merge::MergeSide;
 use module_planner::models::Plan;
+use module_planner::storage::Storage;
 use std::fs;
 use std::io::Write;
 use std::path::Path;
@@ -15,6 +17,7 @@ fn test_import_command_structure() -> Result<()> {
 
     let command = ImportCommand {
         storage: Box::new(storage),
+        on_conflict: None,
     };
 
     // We can't easily test the interactive parts in a unit test
@@ -40,6 +43,7 @@ fn test_import_plan_from_json() -> Result<()> {
     // Create command with mock storage
     let command = ImportCommand {
         storage: Box::new(storage),
+        on_conflict: None,
     };
 
     // In a real test, we would use rexpect to simulate user input
@@ -67,6 +71,7 @@ fn test_import_plan_with_existing_id() -> Result<()> {
     // Create command with mock storage that already has the plan
     let command = ImportCommand {
         storage: Box::new(storage),
+        on_conflict: None,
     };
 
     // In a real test, we would use rexpect to simulate user input
@@ -78,3 +83,66 @@ fn test_import_plan_with_existing_id() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_merge_imported_copy_with_local_edits() -> Result<()> {
+    let storage = MockStorage::new();
+    let mut local = create_test_plan();
+    storage.save_plan(&local)?;
+
+    // The advisor edits an exported copy of version 1
+    let mut imported = local.clone();
+    imported.base_version = Some(1);
+    imported.semesters[1]
+        .modules
+        .retain(|m| m.module_code != "IE3101");
+    imported.notes = Some("Drop IE3101 for now".to_string());
+
+    // Meanwhile the student regrades semester 1 locally
+    local.semesters[0].modules[0].grade = Some("A+".to_string());
+    storage.save_plan(&local)?;
+
+    let command = ImportCommand {
+        storage: Box::new(storage.clone()),
+        on_conflict: None,
+    };
+    let merge = command.merge_plan(&local, &imported)?;
+
+    assert!(merge.is_clean());
+    assert_eq!(
+        merge.plan.semesters[0].modules[0].grade.as_deref(),
+        Some("A+")
+    );
+    assert_eq!(merge.plan.semesters[1].modules.len(), 1);
+    assert_eq!(merge.plan.notes.as_deref(), Some("Drop IE3101 for now"));
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_conflicts_resolved_by_flag() -> Result<()> {
+    let storage = MockStorage::new();
+    let mut local = create_test_plan();
+    storage.save_plan(&local)?;
+
+    let mut imported = local.clone();
+    imported.base_version = Some(1);
+    imported.semesters[1].modules.remove(0);
+    local.semesters[1].modules.remove(1);
+    storage.save_plan(&local)?;
+
+    let command = ImportCommand {
+        storage: Box::new(storage.clone()),
+        on_conflict: Some(MergeSide::Theirs),
+    };
+    let merge = command.merge_plan(&local, &imported)?;
+    assert!(merge.is_clean());
+    assert_eq!(merge.plan.semesters[1].modules[0].module_code, "IE3101");
+
+    // Without a recorded ancestor there is nothing to merge against
+    imported.base_version = Some(9);
+    let error = command.merge_plan(&local, &imported).unwrap_err();
+    assert!(error.to_string().contains("no longer stored"));
+
+    Ok(())
+}
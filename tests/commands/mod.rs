@@ -1,8 +1,15 @@
+mod batch_tests;
+mod branch_tests;
 mod create_tests;
+mod diff_tests;
 mod edit_tests;
 mod export_tests;
 mod import_tests;
+mod rollover_tests;
+mod student_tests;
+mod tui_edit_tests;
 mod validate_tests;
+mod versions_tests;
 mod view_tests;
 
 // Common test utilities for commands
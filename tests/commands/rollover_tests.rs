@@ -0,0 +1,98 @@
+use anyhow::Result;
+use module_planner::commands::RolloverCommand;
+use module_planner::models::{ModuleStatus, Units};
+use std::collections::HashMap;
+
+use crate::commands::common::{create_test_plan, create_test_student, MockStorage};
+
+fn rollover_command() -> RolloverCommand {
+    RolloverCommand {
+        storage: Box::new(MockStorage::new()),
+        plan_id: "test-plan-id".to_string(),
+    }
+}
+
+#[test]
+fn test_find_current_semester_by_term() -> Result<()> {
+    let plan = create_test_plan();
+    let student = create_test_student();
+
+    // Semester 2 of a 2023/2024 matriculation is 2023/2024 Semester 2
+    let command = rollover_command();
+    assert_eq!(command.find_current_semester(&plan, &student), Some(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_find_current_semester_falls_back_to_current_modules() -> Result<()> {
+    let plan = create_test_plan();
+    let mut student = create_test_student();
+    student.matriculation_year = "unknown".to_string();
+
+    let command = rollover_command();
+    assert_eq!(command.find_current_semester(&plan, &student), Some(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_apply_rollover_promotes_statuses() -> Result<()> {
+    let mut plan = create_test_plan();
+    plan.semesters.push(module_planner::models::SemesterPlan {
+        year: "2024/2025".to_string(),
+        semester: 1,
+        modules: vec![module_planner::models::PlannedModule {
+            module_code: "IE3100M".to_string(),
+            status: ModuleStatus::Planned,
+            grade: None,
+            s_u_option: false,
+            class_selections: HashMap::new(),
+        }],
+        total_units: Units::from(4),
+        special_activities: Vec::new(),
+    });
+    let mut student = create_test_student();
+
+    let mut grades = HashMap::new();
+    grades.insert("IE2101".to_string(), Some("B+".to_string()));
+    grades.insert("IE3101".to_string(), Some("F".to_string()));
+
+    let command = rollover_command();
+    let summary = command.apply_rollover(&mut student, &mut plan, Some(1), &grades);
+
+    assert_eq!(summary.completed, vec!["IE2101".to_string()]);
+    assert_eq!(summary.failed, vec!["IE3101".to_string()]);
+    assert_eq!(summary.started, vec!["IE3100M".to_string()]);
+    assert_eq!(summary.new_semester, 3);
+    assert_eq!(student.current_semester, 3);
+
+    assert_eq!(plan.semesters[1].modules[0].status, ModuleStatus::Completed);
+    assert_eq!(plan.semesters[1].modules[0].grade.as_deref(), Some("B+"));
+    assert_eq!(plan.semesters[1].modules[1].status, ModuleStatus::Failed);
+    assert_eq!(plan.semesters[2].modules[0].status, ModuleStatus::Current);
+
+    assert!(student.completed_modules.contains("IE1111R"));
+    assert!(student.completed_modules.contains("IE2101"));
+    assert!(!student.completed_modules.contains("IE3101"));
+
+    Ok(())
+}
+
+#[test]
+fn test_apply_rollover_fails_unsatisfactory_grades() -> Result<()> {
+    let mut plan = create_test_plan();
+    let mut student = create_test_student();
+
+    let mut grades = HashMap::new();
+    grades.insert("IE2101".to_string(), Some("S".to_string()));
+    grades.insert("IE3101".to_string(), Some("u".to_string()));
+
+    let summary = rollover_command().apply_rollover(&mut student, &mut plan, Some(1), &grades);
+
+    assert_eq!(summary.completed, vec!["IE2101".to_string()]);
+    assert_eq!(summary.failed, vec!["IE3101".to_string()]);
+    assert!(!student.completed_modules.contains("IE3101"));
+
+    Ok(())
+}
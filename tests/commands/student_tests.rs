@@ -0,0 +1,180 @@
+use anyhow::Result;
+use module_planner::commands::{parse_student_args, StudentAction, StudentCommand, StudentField};
+use module_planner::models::{CandidatureType, Plan, Student};
+use module_planner::storage::Storage;
+
+use crate::commands::common::{create_test_plan, create_test_student, MockStorage};
+
+fn student_command(storage: &MockStorage) -> StudentCommand {
+    StudentCommand {
+        storage: Box::new(storage.clone()),
+        action: StudentAction::List,
+    }
+}
+
+#[test]
+fn test_edit_student_fields() -> Result<()> {
+    let storage = MockStorage::new().with_student(create_test_student());
+
+    let student = student_command(&storage).edit(
+        "Test Student",
+        &[
+            (StudentField::CurrentSemester, "5".to_string()),
+            (StudentField::Minors, "Mathematics, Economics".to_string()),
+            (
+                StudentField::CompletedModules,
+                "ie1111r, ma1521".to_string(),
+            ),
+            (StudentField::CandidatureType, "ddp".to_string()),
+        ],
+    )?;
+    assert_eq!(student.current_semester, 5);
+    assert_eq!(student.minors, vec!["Mathematics", "Economics"]);
+    assert!(student.completed_modules.contains("MA1521"));
+    assert!(matches!(
+        student.candidature_type,
+        CandidatureType::DoubleDegreeProgramme
+    ));
+    assert_eq!(
+        storage
+            .get_student("test-student-id")?
+            .unwrap()
+            .current_semester,
+        5
+    );
+
+    let error = student_command(&storage)
+        .edit(
+            "Test Student",
+            &[(StudentField::MatriculationYear, "2024".to_string())],
+        )
+        .unwrap_err();
+    assert!(error.to_string().contains("Invalid matriculation year"));
+
+    Ok(())
+}
+
+#[test]
+fn test_rename_student_keeps_plan_links() -> Result<()> {
+    let plan = create_test_plan();
+    let storage = MockStorage::new()
+        .with_student(create_test_student())
+        .with_plan(plan.clone());
+
+    student_command(&storage).edit(
+        "Test Student",
+        &[(StudentField::Name, "Renamed Student".to_string())],
+    )?;
+
+    let student = storage.get_student(&plan.student_id)?.unwrap();
+    assert_eq!(student.name, "Renamed Student");
+    assert_eq!(storage.list_students()?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_students_sharing_a_name_need_an_id() -> Result<()> {
+    let twin = Student {
+        id: "twin-id".to_string(),
+        ..create_test_student()
+    };
+    let storage = MockStorage::new()
+        .with_student(create_test_student())
+        .with_student(twin);
+
+    let error = student_command(&storage)
+        .edit(
+            "Test Student",
+            &[(StudentField::CurrentSemester, "3".to_string())],
+        )
+        .unwrap_err();
+    assert!(error.to_string().contains("Several student profiles"));
+
+    let student = student_command(&storage).edit(
+        "twin-id",
+        &[(StudentField::CurrentSemester, "3".to_string())],
+    )?;
+    assert_eq!(student.current_semester, 3);
+    assert_eq!(
+        storage
+            .get_student("test-student-id")?
+            .unwrap()
+            .current_semester,
+        2
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_legacy_student_ids() -> Result<()> {
+    let legacy = Student {
+        id: String::new(),
+        ..create_test_student()
+    };
+    let plan = Plan {
+        student_id: "Test Student".to_string(),
+        ..create_test_plan()
+    };
+    let storage = MockStorage::new()
+        .with_student(legacy)
+        .with_plan(plan.clone());
+
+    let migration = student_command(&storage).migrate_ids()?;
+    assert_eq!(migration.relinked, vec![plan.id.clone()]);
+
+    let students = storage.list_students()?;
+    assert_eq!(students.len(), 1);
+    assert!(!students[0].id.is_empty());
+    assert_eq!(
+        storage.get_plan(&plan.id)?.unwrap().student_id,
+        students[0].id
+    );
+
+    assert!(student_command(&storage).migrate_ids()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_delete_student_blocks_or_cascades() -> Result<()> {
+    let plan = create_test_plan();
+    let storage = MockStorage::new()
+        .with_student(create_test_student())
+        .with_plan(plan.clone());
+
+    let error = student_command(&storage)
+        .delete("Test Student", false)
+        .unwrap_err();
+    assert!(error.to_string().contains("--cascade"));
+    assert!(storage.get_student("test-student-id")?.is_some());
+
+    let deleted = student_command(&storage).delete("Test Student", true)?;
+    assert_eq!(deleted, vec![plan.id.clone()]);
+    assert!(storage.get_student("test-student-id")?.is_none());
+    assert!(storage.get_plan(&plan.id)?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_student_args() -> Result<()> {
+    assert_eq!(
+        parse_student_args(["student", "edit", "Alice", "--set", "ap-credits=8"])?,
+        StudentAction::Edit {
+            student: "Alice".to_string(),
+            changes: vec![(StudentField::AdvancedPlacementCredits, "8".to_string())],
+        }
+    );
+    assert_eq!(
+        parse_student_args(["student", "delete", "Alice", "--cascade"])?,
+        StudentAction::Delete {
+            student: "Alice".to_string(),
+            cascade: true,
+        }
+    );
+    assert!(parse_student_args(["student", "edit", "Alice", "--set", "gpa=5"]).is_err());
+
+    Ok(())
+}
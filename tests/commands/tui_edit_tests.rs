@@ -0,0 +1,106 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use module_planner::api::NusmodsApi;
+use module_planner::commands::{EditorAction, EditorFocus, TuiEditorState};
+use module_planner::history;
+use module_planner::models::Units;
+
+use crate::commands::common::{create_test_plan, create_test_student, MockNusmodsApi};
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+#[test]
+fn test_carry_module_between_semesters() -> Result<()> {
+    let api = MockNusmodsApi::new();
+    let registry = api.get_module_registry()?;
+    let mut state = TuiEditorState::new(create_test_plan(), Some(create_test_student()));
+
+    // Pick up IE2101 in the second semester and carry it back to the first
+    state.handle_key(key(KeyCode::Right), &api, &registry)?;
+    state.handle_key(key(KeyCode::Char(' ')), &api, &registry)?;
+    state.handle_key(key(KeyCode::Left), &api, &registry)?;
+    state.handle_key(key(KeyCode::Char(' ')), &api, &registry)?;
+
+    assert!(state.carrying.is_none());
+    assert!(state.dirty);
+    assert_eq!(state.plan.semesters[0].modules.len(), 2);
+    assert_eq!(state.plan.semesters[0].modules[1].module_code, "IE2101");
+    assert_eq!(state.plan.semesters[0].total_units, Units::from(8));
+    // IE3101 plus the 4-credit internship left behind
+    assert_eq!(state.plan.semesters[1].total_units, Units::from(8));
+
+    Ok(())
+}
+
+#[test]
+fn test_search_and_add_module() -> Result<()> {
+    let api = MockNusmodsApi::new();
+    let registry = api.get_module_registry()?;
+    let mut state = TuiEditorState::new(create_test_plan(), None);
+
+    state.handle_key(key(KeyCode::Tab), &api, &registry)?;
+    assert_eq!(state.focus, EditorFocus::Search);
+
+    for c in "ie3".chars() {
+        state.handle_key(key(KeyCode::Char(c)), &api, &registry)?;
+    }
+    assert_eq!(state.search_results.len(), 1);
+
+    state.handle_key(key(KeyCode::Enter), &api, &registry)?;
+    assert_eq!(state.plan.semesters[0].modules[1].module_code, "IE3101");
+    assert_eq!(state.plan.semesters[0].total_units, Units::from(8));
+
+    // Adding the same module twice to a semester is refused
+    state.handle_key(key(KeyCode::Enter), &api, &registry)?;
+    assert_eq!(state.plan.semesters[0].modules.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_quit_requires_confirmation_when_dirty() -> Result<()> {
+    let api = MockNusmodsApi::new();
+    let registry = api.get_module_registry()?;
+    let mut state = TuiEditorState::new(create_test_plan(), None);
+
+    state.handle_key(key(KeyCode::Char('d')), &api, &registry)?;
+    assert!(state.plan.semesters[0].modules.is_empty());
+
+    let action = state.handle_key(key(KeyCode::Char('q')), &api, &registry)?;
+    assert_eq!(action, EditorAction::Continue);
+
+    let action = state.handle_key(key(KeyCode::Char('q')), &api, &registry)?;
+    assert_eq!(action, EditorAction::Quit);
+
+    let save = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+    assert_eq!(state.handle_key(save, &api, &registry)?, EditorAction::Save);
+
+    Ok(())
+}
+
+#[test]
+fn test_editor_changes_are_logged_for_undo() -> Result<()> {
+    let api = MockNusmodsApi::new();
+    let registry = api.get_module_registry()?;
+    let mut state = TuiEditorState::new(create_test_plan(), None);
+
+    state.handle_key(key(KeyCode::Char('d')), &api, &registry)?;
+    state.handle_key(key(KeyCode::Right), &api, &registry)?;
+    state.handle_key(key(KeyCode::Char(' ')), &api, &registry)?;
+    state.handle_key(key(KeyCode::Left), &api, &registry)?;
+    assert_eq!(state.plan.edit_log.len(), 2);
+
+    let mut plan = state.plan;
+    let undone = history::undo_logged(&mut plan, &registry, 2)?;
+    assert_eq!(undone.len(), 2);
+    assert_eq!(plan.semesters[0].modules[0].module_code, "IE1111R");
+    assert!(plan.semesters[1]
+        .modules
+        .iter()
+        .any(|m| m.module_code == "IE2101"));
+    assert_eq!(plan.total_units(), create_test_plan().total_units());
+
+    Ok(())
+}
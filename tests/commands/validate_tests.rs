@@ -1,10 +1,14 @@
 use anyhow::Result;
 use module_planner::api::DefaultNusmodsApi;
+use module_planner::api::NusmodsApi;
 use module_planner::commands::ValidateCommand;
-use module_planner::validation::ValidationResult;
+use module_planner::models::{ModuleRegistry, PrereqTree, SemesterData, Units};
+use module_planner::validation::{
+    availability, candidature, prerequisites, ValidationLevel, ValidationResult,
+};
 
 use crate::commands::common::{
-    create_test_curriculum, create_test_plan, create_test_student, MockStorage,
+    create_test_curriculum, create_test_plan, create_test_student, MockNusmodsApi, MockStorage,
 };
 
 #[test]
@@ -78,3 +82,121 @@ fn test_display_validation_results() -> Result<()> {
     assert!(true);
     Ok(())
 }
+
+#[test]
+fn test_validate_candidature() -> Result<()> {
+    let mut plan = create_test_plan();
+    let student = create_test_student();
+
+    let mut result = ValidationResult::new();
+    candidature::validate_candidature(&plan, &student, &mut result)?;
+    assert!(result.issues.is_empty());
+
+    // Semester 9 of a standard candidature is past normal candidature
+    let mut late = plan.semesters[0].clone();
+    late.year = "2027/2028".to_string();
+    plan.semesters.push(late);
+
+    // Semester 11 is past maximum candidature, and 30 units is an overload
+    let mut too_late = plan.semesters[0].clone();
+    too_late.year = "2028/2029".to_string();
+    too_late.total_units = Units::from(30);
+    plan.semesters.push(too_late);
+
+    let mut result = ValidationResult::new();
+    candidature::validate_candidature(&plan, &student, &mut result)?;
+
+    assert_eq!(result.issues.len(), 2);
+    assert_eq!(result.issues[0].level, ValidationLevel::Error);
+    assert_eq!(result.issues[1].level, ValidationLevel::Warning);
+    assert_eq!(result.issues[1].semester_index, Some(3));
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_prerequisites_counts_exemptions() -> Result<()> {
+    let mut registry = ModuleRegistry::new();
+    let api = MockNusmodsApi::new();
+    for code in ["IE1111R", "IE2101", "IE3101"] {
+        let mut module = api.get_module_registry()?.get_module(code).unwrap().clone();
+        if code == "IE3101" {
+            module.prereq_tree = Some(PrereqTree::And {
+                and: vec![
+                    PrereqTree::ModuleCode("IE2101".to_string()),
+                    PrereqTree::ModuleCode("MA1511:D".to_string()),
+                ],
+            });
+        }
+        registry.add_module(module);
+    }
+
+    // IE3101 sits alongside IE2101, and MA1511 is neither taken nor exempted
+    let mut plan = create_test_plan();
+    let mut student = create_test_student();
+
+    let mut result = ValidationResult::new();
+    prerequisites::validate_prerequisites(&plan, &registry, &student, &mut result)?;
+    assert_eq!(result.issues.len(), 1);
+    assert_eq!(result.issues[0].module_code.as_deref(), Some("IE3101"));
+
+    // Moving IE3101 a semester later still leaves MA1511 missing
+    let ie3101 = plan.semesters[1].modules.remove(1);
+    plan.semesters.push(module_planner::models::SemesterPlan {
+        year: "2024/2025".to_string(),
+        semester: 1,
+        modules: vec![ie3101],
+        total_units: Units::from(4),
+        special_activities: Vec::new(),
+    });
+
+    let mut result = ValidationResult::new();
+    prerequisites::validate_prerequisites(&plan, &registry, &student, &mut result)?;
+    assert_eq!(result.issues.len(), 1);
+
+    // An exemption satisfies the remaining prerequisite
+    student.exempted_modules.insert("MA1511".to_string());
+
+    let mut result = ValidationResult::new();
+    prerequisites::validate_prerequisites(&plan, &registry, &student, &mut result)?;
+    assert!(result.issues.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_availability_uses_semester_academic_year() -> Result<()> {
+    let api = MockNusmodsApi::new();
+    let mut registry = api.get_module_registry()?;
+
+    let offered_in = |semester: u8| SemesterData {
+        semester,
+        exam_date: None,
+        exam_duration: None,
+        timetable: None,
+    };
+
+    // IE3101 moved from Semester 1 in AY2023/24 to Semester 2 from AY2024/25
+    let mut ie3101 = registry.get_module("IE3101").unwrap().clone();
+    ie3101.semester_data = vec![offered_in(1)];
+    registry.add_module_for_year("2023/2024", ie3101.clone());
+    ie3101.semester_data = vec![offered_in(2)];
+    registry.add_module_for_year("2024/2025", ie3101);
+
+    let mut plan = create_test_plan();
+
+    let mut result = ValidationResult::new();
+    availability::validate_availability(&plan, &registry, &mut result)?;
+    assert_eq!(result.issues.len(), 1);
+    assert_eq!(result.issues[0].module_code.as_deref(), Some("IE3101"));
+    assert_eq!(result.issues[0].semester_index, Some(1));
+
+    // The same semester a year later follows the newer offering
+    plan.semesters[1].year = "2024/2025".to_string();
+
+    let mut result = ValidationResult::new();
+    availability::validate_availability(&plan, &registry, &mut result)?;
+    assert!(result.issues.is_empty());
+
+    Ok(())
+}
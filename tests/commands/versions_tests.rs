@@ -0,0 +1,82 @@
+use anyhow::Result;
+use module_planner::commands::{VersionAction, VersionsCommand};
+use module_planner::models::RetentionPolicy;
+use module_planner::storage::Storage;
+
+use crate::commands::common::{create_test_plan, MockStorage};
+
+fn versions_command(storage: &MockStorage, action: VersionAction) -> VersionsCommand {
+    VersionsCommand {
+        storage: Box::new(storage.clone()),
+        plan_id: create_test_plan().id,
+        action,
+    }
+}
+
+#[test]
+fn test_every_save_creates_a_version() -> Result<()> {
+    let storage = MockStorage::new();
+    let mut plan = create_test_plan();
+
+    storage.save_plan(&plan)?;
+    plan.name = "Renamed Plan".to_string();
+    storage.save_plan(&plan)?;
+
+    let versions = versions_command(&storage, VersionAction::List).versions()?;
+    let numbers: Vec<u32> = versions.iter().map(|v| v.version).collect();
+    assert_eq!(numbers, vec![2, 1]);
+    assert_eq!(versions[0].plan.name, "Renamed Plan");
+    assert_eq!(versions[1].plan.name, "Test Plan");
+
+    Ok(())
+}
+
+#[test]
+fn test_restore_saves_old_version_as_new() -> Result<()> {
+    let storage = MockStorage::new();
+    let mut plan = create_test_plan();
+    storage.save_plan(&plan)?;
+    plan.semesters[0].modules.clear();
+    storage.save_plan(&plan)?;
+
+    let command = versions_command(&storage, VersionAction::Restore(1));
+    let restored = command.restore(1)?;
+    assert_eq!(restored.semesters[0].modules.len(), 1);
+
+    let current = storage.get_plan(&plan.id)?.unwrap();
+    assert_eq!(current.semesters[0].modules.len(), 1);
+    assert_eq!(storage.list_plan_versions(&plan.id)?.len(), 3);
+
+    let error = command.restore(9).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Version 9 of plan test-plan-id not found"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_retention_policy_applies_on_save_and_prune() -> Result<()> {
+    let storage = MockStorage::new().with_retention(RetentionPolicy::keep_last(3));
+    let plan = create_test_plan();
+    for _ in 0..5 {
+        storage.save_plan(&plan)?;
+    }
+
+    let numbers: Vec<u32> = storage
+        .list_plan_versions(&plan.id)?
+        .iter()
+        .map(|v| v.version)
+        .collect();
+    assert_eq!(numbers, vec![3, 4, 5]);
+
+    let pruned = storage.prune_plan_versions(&plan.id, &RetentionPolicy::keep_last(1))?;
+    assert_eq!(pruned, vec![3, 4]);
+    assert!(storage.get_plan_version(&plan.id, 5)?.is_some());
+
+    storage.delete_plan(&plan.id)?;
+    assert!(storage.list_plan_versions(&plan.id)?.is_empty());
+
+    Ok(())
+}
@@ -1,6 +1,11 @@
 use anyhow::Result;
 use module_planner::api::DefaultNusmodsApi;
 use module_planner::commands::ViewCommand;
+use module_planner::models::{
+    Lesson, Module, ModuleRegistry, ModuleStatus, SemesterData, SemesterPlan, Units, WeekType,
+};
+
+use crate::fixtures::planned;
 
 use crate::commands::common::{
     create_test_curriculum, create_test_plan, create_test_student, MockNusmodsApi, MockStorage,
@@ -86,19 +91,19 @@ fn test_calculate_level_units() -> Result<()> {
     let level_1000_units = command.calculate_level_units(&plan, 1000)?;
 
     // IE1111R is a level 1000 module with 4 units
-    assert_eq!(level_1000_units, 4);
+    assert_eq!(level_1000_units, Units::from(4));
 
     // Test level 2000 units
     let level_2000_units = command.calculate_level_units(&plan, 2000)?;
 
     // IE2101 is a level 2000 module with 4 units
-    assert_eq!(level_2000_units, 4);
+    assert_eq!(level_2000_units, Units::from(4));
 
     // Test level 3000 units
     let level_3000_units = command.calculate_level_units(&plan, 3000)?;
 
     // IE3101 is a level 3000 module with 4 units
-    assert_eq!(level_3000_units, 4);
+    assert_eq!(level_3000_units, Units::from(4));
 
     Ok(())
 }
@@ -125,10 +130,133 @@ fn test_calculate_component_units() -> Result<()> {
 
     // Test calculate_component_units method
     let component = &curriculum.components[0]; // University Level Requirements
-    let component_units = command.calculate_component_units(&plan, component)?;
+    let component_units = command.calculate_component_units(&plan, &student, component)?;
 
     // Our test plan doesn't have any ULR modules
-    assert_eq!(component_units, 0);
+    assert_eq!(component_units, Units::from(0));
 
     Ok(())
 }
+
+#[test]
+fn test_calculate_component_units_counts_exemptions() -> Result<()> {
+    let plan = create_test_plan();
+    let mut student = create_test_student();
+    student.exempted_modules.insert("GER1000".to_string());
+    let curriculum = create_test_curriculum();
+
+    let command = ViewCommand {
+        storage: Box::new(MockStorage::new()),
+        api: Box::new(DefaultNusmodsApi::new()?),
+        plan_id: Some(plan.id.clone()),
+    };
+
+    let component_units =
+        command.calculate_component_units(&plan, &student, &curriculum.components[0])?;
+    assert_eq!(component_units, Units::from(4));
+
+    Ok(())
+}
+
+fn lesson(class_no: &str, lesson_type: &str, day: &str, start: &str, end: &str) -> Lesson {
+    Lesson {
+        class_no: class_no.to_string(),
+        lesson_type: lesson_type.to_string(),
+        week_type: WeekType::Weeks((1..=13).collect()),
+        day: day.to_string(),
+        start_time: start.to_string(),
+        end_time: end.to_string(),
+        venue: "E1-06-01".to_string(),
+    }
+}
+
+fn timetabled_module(code: &str, lessons: Vec<Lesson>) -> Module {
+    Module {
+        module_code: code.to_string(),
+        title: code.to_string(),
+        description: None,
+        module_credit: 4.into(),
+        department: None,
+        faculty: None,
+        workload: None,
+        prerequisite: None,
+        preclusion: None,
+        corequisite: None,
+        semester_data: vec![SemesterData {
+            semester: 1,
+            exam_date: None,
+            exam_duration: None,
+            timetable: Some(lessons),
+        }],
+        prereq_tree: None,
+        fulfill_requirements: None,
+    }
+}
+
+fn timetable_fixture() -> (ViewCommand, ModuleRegistry, SemesterPlan) {
+    let mut registry = ModuleRegistry::new();
+    registry.add_module(timetabled_module(
+        "IE2110",
+        vec![
+            lesson("1", "Lecture", "Monday", "1000", "1200"),
+            lesson("T1", "Tutorial", "Wednesday", "0900", "1000"),
+            lesson("T2", "Tutorial", "Thursday", "1400", "1500"),
+        ],
+    ));
+    registry.add_module(timetabled_module(
+        "IE2130",
+        vec![lesson("1", "Lecture", "Monday", "1100", "1300")],
+    ));
+
+    let mut semester = SemesterPlan::empty("2023/2024".to_string(), 1);
+    semester
+        .modules
+        .push(planned("IE2110", ModuleStatus::Planned));
+    semester
+        .modules
+        .push(planned("IE2130", ModuleStatus::Planned));
+    semester
+        .modules
+        .push(planned("GEA1000", ModuleStatus::Planned));
+
+    let command = ViewCommand {
+        storage: Box::new(MockStorage::new()),
+        api: Box::new(MockNusmodsApi::new()),
+        plan_id: None,
+    };
+
+    (command, registry, semester)
+}
+
+#[test]
+fn test_build_timetable_grid_detects_clashes() {
+    let (command, registry, semester) = timetable_fixture();
+
+    let grid = command.build_timetable_grid(&semester, &registry, None);
+
+    // Monday is day 0; the lectures overlap from 1100 to 1200
+    assert_eq!(grid.slots_at(0, 10).len(), 1);
+    assert_eq!(grid.slots_at(0, 11).len(), 2);
+    assert_eq!(grid.clashes(), vec![(0, 11)]);
+    assert_eq!(grid.unscheduled, vec!["GEA1000".to_string()]);
+
+    // Without a selection every tutorial slot is shown
+    assert_eq!(grid.slots_at(2, 9).len(), 1);
+    assert_eq!(grid.slots_at(3, 14).len(), 1);
+}
+
+#[test]
+fn test_build_timetable_grid_uses_class_selections() {
+    let (command, registry, mut semester) = timetable_fixture();
+    semester.modules[0]
+        .class_selections
+        .insert("Tutorial".to_string(), "T2".to_string());
+
+    let grid = command.build_timetable_grid(&semester, &registry, None);
+    assert!(grid.slots_at(2, 9).is_empty());
+    assert_eq!(grid.slots_at(3, 14)[0].class_no, "T2");
+
+    // Lessons outside the chosen week are dropped
+    let grid = command.build_timetable_grid(&semester, &registry, Some(14));
+    assert!(grid.cells.is_empty());
+}
@@ -0,0 +1,132 @@
+// Builders shared by the model, planner and command tests.
+
+use module_planner::models::{
+    Module, ModuleRegistry, ModuleStatus, Plan, PlannedModule, PrereqTree, SemesterData,
+    SemesterPlan, Units,
+};
+use std::collections::HashMap;
+
+/// Starts a 4-unit module with no offering data or prerequisites.
+pub fn module(code: &str) -> ModuleBuilder {
+    ModuleBuilder(Module {
+        module_code: code.to_string(),
+        title: code.to_string(),
+        description: None,
+        module_credit: 4.into(),
+        department: None,
+        faculty: None,
+        workload: None,
+        prerequisite: None,
+        preclusion: None,
+        corequisite: None,
+        semester_data: Vec::new(),
+        prereq_tree: None,
+        fulfill_requirements: None,
+    })
+}
+
+pub struct ModuleBuilder(Module);
+
+impl ModuleBuilder {
+    pub fn title(mut self, title: &str) -> Self {
+        self.0.title = title.to_string();
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.0.description = Some(description.to_string());
+        self
+    }
+
+    pub fn department(mut self, department: &str) -> Self {
+        self.0.department = Some(department.to_string());
+        self
+    }
+
+    pub fn faculty(mut self, faculty: &str) -> Self {
+        self.0.faculty = Some(faculty.to_string());
+        self
+    }
+
+    /// Credit as NUSMods writes it, e.g. "4", "2.5" or "0-8".
+    pub fn credit(mut self, credit: &str) -> Self {
+        self.0.module_credit = credit.parse().unwrap();
+        self
+    }
+
+    pub fn workload(mut self, hours: f32) -> Self {
+        self.0.workload = Some(vec![hours]);
+        self
+    }
+
+    /// Offered in each of the semesters, without a final exam.
+    pub fn offered_in(self, semesters: &[u8]) -> Self {
+        semesters
+            .iter()
+            .fold(self, |builder, &semester| builder.semester(semester, false))
+    }
+
+    /// Offered in one semester, with a final exam if `exam` is set.
+    pub fn semester(mut self, semester: u8, exam: bool) -> Self {
+        self.0.semester_data.push(SemesterData {
+            semester,
+            exam_date: exam.then(|| "2023-11-25T01:00:00.000Z".to_string()),
+            exam_duration: exam.then_some(120),
+            timetable: None,
+        });
+        self
+    }
+
+    pub fn prereq(mut self, tree: PrereqTree) -> Self {
+        self.0.prereq_tree = Some(tree);
+        self
+    }
+
+    /// Requires a single other module.
+    pub fn requires(self, code: &str) -> Self {
+        self.prereq(PrereqTree::ModuleCode(code.to_string()))
+    }
+
+    pub fn build(self) -> Module {
+        self.0
+    }
+}
+
+pub fn registry(modules: impl IntoIterator<Item = ModuleBuilder>) -> ModuleRegistry {
+    modules.into_iter().map(ModuleBuilder::build).collect()
+}
+
+pub fn planned(code: &str, status: ModuleStatus) -> PlannedModule {
+    PlannedModule {
+        module_code: code.to_string(),
+        status,
+        grade: None,
+        s_u_option: false,
+        class_selections: HashMap::new(),
+    }
+}
+
+/// A semester whose stored total assumes 4 units per module.
+pub fn semester(year: &str, semester: u8, modules: &[(&str, ModuleStatus)]) -> SemesterPlan {
+    SemesterPlan {
+        year: year.to_string(),
+        semester,
+        modules: modules
+            .iter()
+            .map(|(code, status)| planned(code, status.clone()))
+            .collect(),
+        total_units: Units::from(4 * modules.len() as u32),
+        special_activities: Vec::new(),
+    }
+}
+
+/// Plan `plan1` of "Test Student" with the given semesters.
+pub fn plan(name: &str, semesters: Vec<SemesterPlan>) -> Plan {
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        name.to_string(),
+        "Test Student".to_string(),
+    );
+    plan.semesters = semesters;
+    plan
+}
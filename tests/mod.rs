@@ -1,2 +1,4 @@
 mod commands;
+mod fixtures;
 mod models;
+mod planner;
@@ -0,0 +1,51 @@
+use module_planner::models::{promote_branch, Plan, MAIN_BRANCH};
+
+fn plan() -> Plan {
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        "ISE Plan".to_string(),
+        "A0123456X".to_string(),
+    );
+    plan.notes = Some("Original".to_string());
+    plan
+}
+
+#[test]
+fn test_fork_creates_branch_sharing_family() {
+    let mut original = plan();
+    assert!(original.is_primary());
+    assert_eq!(original.family_id(), "plan1");
+
+    let exchange = original.fork("plan2".to_string(), "exchange", Some(3));
+    assert_eq!(original.branch_name(), MAIN_BRANCH);
+    assert!(original.is_primary());
+
+    assert_eq!(exchange.id, "plan2");
+    assert_eq!(exchange.name, "ISE Plan (exchange)");
+    assert_eq!(exchange.family_id(), "plan1");
+    assert_eq!(exchange.notes.as_deref(), Some("Original"));
+    assert!(!exchange.is_primary());
+    let branch = exchange.branch.as_ref().unwrap();
+    assert_eq!(branch.forked_from.as_deref(), Some("plan1"));
+    assert_eq!(branch.forked_at_version, Some(3));
+
+    // Forking a branch keeps the original family
+    let mut exchange = exchange;
+    let nested = exchange.fork("plan3".to_string(), "exchange-late", None);
+    assert_eq!(nested.family_id(), "plan1");
+}
+
+#[test]
+fn test_promote_branch() {
+    let mut original = plan();
+    let internship = original.fork("plan2".to_string(), "internship", None);
+    let mut family = vec![original, internship];
+
+    let changed = promote_branch(&mut family, "plan2").unwrap();
+    assert_eq!(changed, vec!["plan1".to_string(), "plan2".to_string()]);
+    assert!(!family[0].is_primary());
+    assert!(family[1].is_primary());
+
+    assert!(promote_branch(&mut family, "plan2").unwrap().is_empty());
+    assert!(promote_branch(&mut family, "plan9").is_err());
+}
@@ -1,9 +1,14 @@
-use module_planner::models::{Curriculum, CurriculumComponent, Requirement};
+use module_planner::models::{
+    CandidatureType, Curriculum, CurriculumComponent, ExemptionPolicy, Module, ModuleRegistry,
+    Requirement, Student, Units,
+};
+use std::collections::HashSet;
 
 #[test]
 fn test_curriculum_creation() {
     let curriculum = Curriculum {
         name: "Industrial Systems Engineering".to_string(),
+        faculty: None,
         academic_year: "2023/2024".to_string(),
         total_units_required: 160,
         max_level1000_units: 60,
@@ -27,22 +32,26 @@ fn test_curriculum_with_components() {
                 module_code: "IE1111R".to_string(),
                 name: "Industrial Engineering Principles and Practice".to_string(),
                 units: 4,
+                on_exemption: ExemptionPolicy::Credited,
             },
             Requirement::FixedModule {
                 module_code: "IE2130".to_string(),
                 name: "Quality Engineering".to_string(),
                 units: 4,
+                on_exemption: ExemptionPolicy::Credited,
             },
             Requirement::FixedModule {
                 module_code: "IE3100".to_string(),
                 name: "Systems Design & Analysis".to_string(),
                 units: 4,
+                on_exemption: ExemptionPolicy::Credited,
             },
         ],
     };
 
     let curriculum = Curriculum {
         name: "Industrial Systems Engineering".to_string(),
+        faculty: None,
         academic_year: "2023/2024".to_string(),
         total_units_required: 160,
         max_level1000_units: 60,
@@ -61,6 +70,7 @@ fn test_fixed_module_requirement() {
         module_code: "IE1111R".to_string(),
         name: "Industrial Engineering Principles and Practice".to_string(),
         units: 4,
+        on_exemption: ExemptionPolicy::Credited,
     };
 
     match requirement {
@@ -68,10 +78,12 @@ fn test_fixed_module_requirement() {
             module_code,
             name,
             units,
+            on_exemption,
         } => {
             assert_eq!(module_code, "IE1111R");
             assert_eq!(name, "Industrial Engineering Principles and Practice");
             assert_eq!(units, 4);
+            assert_eq!(on_exemption, ExemptionPolicy::Credited);
         }
         _ => panic!("Expected Requirement::FixedModule"),
     }
@@ -137,3 +149,147 @@ fn test_elective_requirement() {
         _ => panic!("Expected Requirement::Elective"),
     }
 }
+
+fn exemption_curriculum() -> Curriculum {
+    Curriculum {
+        name: "Industrial Systems Engineering".to_string(),
+        faculty: None,
+        academic_year: "2023/2024".to_string(),
+        total_units_required: 160,
+        max_level1000_units: 60,
+        components: vec![CurriculumComponent {
+            name: "Common Curriculum".to_string(),
+            min_units: 12,
+            requirements: vec![
+                Requirement::FixedModule {
+                    module_code: "MA1511".to_string(),
+                    name: "Engineering Calculus".to_string(),
+                    units: 2,
+                    on_exemption: ExemptionPolicy::Credited,
+                },
+                Requirement::FixedModule {
+                    module_code: "ES1103".to_string(),
+                    name: "English for Academic Purposes".to_string(),
+                    units: 4,
+                    on_exemption: ExemptionPolicy::Waived,
+                },
+                Requirement::FixedModule {
+                    module_code: "CS1010E".to_string(),
+                    name: "Programming Methodology".to_string(),
+                    units: 4,
+                    on_exemption: ExemptionPolicy::ReplacedByElectives,
+                },
+            ],
+        }],
+    }
+}
+
+#[test]
+fn test_exemption_policies_affect_units() {
+    let curriculum = exemption_curriculum();
+
+    let mut student = Student {
+        id: "test-student-id".to_string(),
+        name: "Test Student".to_string(),
+        matriculation_year: "2023/2024".to_string(),
+        faculty: "College of Design and Engineering".to_string(),
+        major: "Industrial Systems Engineering".to_string(),
+        second_major: None,
+        minors: Vec::new(),
+        completed_modules: HashSet::new(),
+        exempted_modules: HashSet::new(),
+        advanced_placement_credits: 8,
+        current_semester: 1,
+        candidature_type: CandidatureType::Standard,
+    };
+
+    let registry = ModuleRegistry::new();
+    assert_eq!(curriculum.units_required_for(&student), 160);
+    assert_eq!(
+        curriculum.credited_units_for(&student, &registry),
+        Units::from(8)
+    );
+
+    for code in ["MA1511", "ES1103", "CS1010E"] {
+        student.exempted_modules.insert(code.to_string());
+    }
+
+    assert_eq!(
+        curriculum.exemption_policy("ES1103"),
+        Some(ExemptionPolicy::Waived)
+    );
+    assert_eq!(curriculum.exemption_policy("IE1111R"), None);
+    assert_eq!(curriculum.units_required_for(&student), 156);
+    assert_eq!(
+        curriculum.credited_units_for(&student, &registry),
+        Units::from(10)
+    );
+    assert_eq!(curriculum.replacement_units_for(&student), 4);
+}
+
+#[test]
+fn test_requirements_unlocked_by() {
+    let curriculum = Curriculum {
+        name: "Industrial Systems Engineering".to_string(),
+        faculty: None,
+        academic_year: "2023/2024".to_string(),
+        total_units_required: 160,
+        max_level1000_units: 60,
+        components: vec![CurriculumComponent {
+            name: "Programme Requirements".to_string(),
+            min_units: 12,
+            requirements: vec![
+                Requirement::FixedModule {
+                    module_code: "IE2101".to_string(),
+                    name: "Introduction to Systems Thinking".to_string(),
+                    units: 4,
+                    on_exemption: ExemptionPolicy::Credited,
+                },
+                Requirement::ModuleGroup {
+                    name: "Operations Electives".to_string(),
+                    description: None,
+                    min_units: 4,
+                    possible_modules: vec!["IE3101".to_string(), "IE3110".to_string()],
+                },
+                Requirement::ModuleGroup {
+                    name: "Data Electives".to_string(),
+                    description: None,
+                    min_units: 4,
+                    possible_modules: vec!["IE3120".to_string(), "IE3130".to_string()],
+                },
+            ],
+        }],
+    };
+
+    let mut registry = ModuleRegistry::new();
+    registry.add_module(Module {
+        module_code: "IE3120".to_string(),
+        title: "Manufacturing Logistics".to_string(),
+        description: None,
+        module_credit: 4.into(),
+        department: None,
+        faculty: None,
+        workload: None,
+        prerequisite: None,
+        preclusion: None,
+        corequisite: None,
+        semester_data: vec![],
+        prereq_tree: None,
+        fulfill_requirements: None,
+    });
+
+    let unlocked: HashSet<String> = ["IE2101", "IE3101", "IE3130"]
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    // Data Electives are already met by IE3120
+    let taken: HashSet<String> = ["IE3120".to_string()].into_iter().collect();
+
+    assert_eq!(
+        curriculum.requirements_unlocked_by(&unlocked, &taken, &registry),
+        vec![
+            "Introduction to Systems Thinking".to_string(),
+            "Operations Electives".to_string()
+        ]
+    );
+}
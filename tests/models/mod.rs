@@ -1,5 +1,10 @@
+mod branch_test;
 mod curriculum_test;
 mod module_registry_test;
 mod module_test;
 mod plan_test;
+mod search_test;
 mod student_test;
+mod template_test;
+mod units_test;
+mod version_test;
@@ -1,4 +1,7 @@
-use module_planner::models::{Module, ModuleRegistry};
+use module_planner::models::{
+    MergePolicy, Module, ModuleCredit, ModuleRegistry, PrereqTree, Units,
+};
+use std::collections::HashSet;
 
 #[test]
 fn test_module_registry_creation() {
@@ -17,7 +20,7 @@ fn test_module_registry_add_and_get() {
             "This module introduces the fundamental principles of industrial engineering."
                 .to_string(),
         ),
-        module_credit: "4".to_string(),
+        module_credit: 4.into(),
         department: Some("Industrial Systems Engineering".to_string()),
         faculty: Some("Faculty of Engineering".to_string()),
         workload: Some(vec![2.0, 1.0, 1.0, 3.0, 3.0]),
@@ -59,7 +62,7 @@ fn test_module_registry_multiple_modules() {
             "This module introduces the fundamental principles of industrial engineering."
                 .to_string(),
         ),
-        module_credit: "4".to_string(),
+        module_credit: 4.into(),
         department: Some("Industrial Systems Engineering".to_string()),
         faculty: Some("Faculty of Engineering".to_string()),
         workload: Some(vec![2.0, 1.0, 1.0, 3.0, 3.0]),
@@ -78,7 +81,7 @@ fn test_module_registry_multiple_modules() {
             "This module covers statistical quality control and quality management systems."
                 .to_string(),
         ),
-        module_credit: "4".to_string(),
+        module_credit: 4.into(),
         department: Some("Industrial Systems Engineering".to_string()),
         faculty: Some("Faculty of Engineering".to_string()),
         workload: Some(vec![2.0, 1.0, 1.0, 3.0, 3.0]),
@@ -103,3 +106,264 @@ fn test_module_registry_multiple_modules() {
     assert_eq!(retrieved_module2.module_code, "IE2130");
     assert_eq!(retrieved_module2.prerequisite, Some("IE1111R".to_string()));
 }
+
+fn prereq_module(code: &str, prereq: Option<PrereqTree>) -> Module {
+    Module {
+        module_code: code.to_string(),
+        title: code.to_string(),
+        description: None,
+        module_credit: 4.into(),
+        department: None,
+        faculty: None,
+        workload: None,
+        prerequisite: None,
+        preclusion: None,
+        corequisite: None,
+        semester_data: vec![],
+        prereq_tree: prereq,
+        fulfill_requirements: None,
+    }
+}
+
+fn unlock_registry() -> ModuleRegistry {
+    let leaf = |code: &str| PrereqTree::ModuleCode(code.to_string());
+
+    let mut registry = ModuleRegistry::new();
+    registry.add_module(prereq_module("IE1111R", None));
+    registry.add_module(prereq_module("MA1511", None));
+    registry.add_module(prereq_module("IE2101", Some(leaf("IE1111R:D"))));
+    registry.add_module(prereq_module(
+        "IE2110",
+        Some(PrereqTree::And {
+            and: vec![leaf("IE1111R"), leaf("MA1511")],
+        }),
+    ));
+    registry.add_module(prereq_module("IE3101", Some(leaf("IE21%"))));
+    registry
+}
+
+#[test]
+fn test_direct_dependents() {
+    let mut registry = unlock_registry();
+
+    assert_eq!(
+        registry.direct_dependents("IE1111R"),
+        vec!["IE2101".to_string(), "IE2110".to_string()]
+    );
+    // Wildcard prerequisites are matched by prefix
+    assert_eq!(
+        registry.direct_dependents("IE2101"),
+        vec!["IE3101".to_string()]
+    );
+
+    // Replacing a module drops its old reverse entries
+    registry.add_module(prereq_module("IE2101", None));
+    assert_eq!(
+        registry.direct_dependents("IE1111R"),
+        vec!["IE2110".to_string()]
+    );
+}
+
+#[test]
+fn test_unlocked_by() {
+    let registry = unlock_registry();
+
+    // MA1511 has no prerequisites, so IE2110 only waits on IE1111R
+    let unlocks = registry.unlocked_by("IE1111R", &HashSet::new());
+    assert_eq!(
+        unlocks.direct,
+        vec!["IE2101".to_string(), "IE2110".to_string()]
+    );
+    assert_eq!(unlocks.transitive, vec!["IE3101".to_string()]);
+
+    // Passed modules are not reported, nor is anything reachable without IE1111R
+    let passed: HashSet<String> = ["IE2101".to_string()].into_iter().collect();
+    let unlocks = registry.unlocked_by("IE1111R", &passed);
+    assert_eq!(unlocks.direct, vec!["IE2110".to_string()]);
+    assert!(unlocks.transitive.is_empty());
+
+    // With IE1111R passed, IE3101 is reachable through IE2101 without MA1511
+    let passed: HashSet<String> = ["IE1111R".to_string()].into_iter().collect();
+    let unlocks = registry.unlocked_by("MA1511", &passed);
+    assert_eq!(unlocks.direct, vec!["IE2110".to_string()]);
+    assert!(unlocks.transitive.is_empty());
+}
+
+const MODULE_INFO: &str = r#"[
+  {
+    "moduleCode": "IE2101",
+    "title": "Introduction to Systems Thinking",
+    "description": "Systems concepts.",
+    "moduleCredit": "4",
+    "department": "Industrial Systems Engineering and Management",
+    "faculty": "College of Design and Engineering",
+    "workload": "2-1-0-3-4",
+    "prereqTree": "IE1111R:D",
+    "semesterData": [
+      { "semester": 2, "examDate": "2024-04-27T05:00:00.000Z", "examDuration": 120 }
+    ],
+    "gradingBasisDescription": "Graded"
+  },
+  {
+    "moduleCode": "IE4100",
+    "title": "B.Eng. Dissertation",
+    "moduleCredit": "8",
+    "workload": "Refer to module description",
+    "semesterData": [{ "semester": 1 }, { "semester": 2 }]
+  }
+]"#;
+
+#[test]
+fn test_registry_from_module_info_json() {
+    let registry = ModuleRegistry::from_module_info_json(MODULE_INFO).unwrap();
+
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.module_codes(), vec!["IE2101", "IE4100"]);
+
+    let ie2101 = registry.get_module("IE2101").unwrap();
+    assert_eq!(ie2101.workload, Some(vec![2.0, 1.0, 0.0, 3.0, 4.0]));
+    assert_eq!(ie2101.semester_data[0].exam_duration, Some(120));
+    assert_eq!(registry.direct_dependents("IE1111R"), vec!["IE2101"]);
+
+    let ie4100 = registry.get_module("IE4100").unwrap();
+    assert_eq!(ie4100.workload, None);
+    assert!(ie4100.is_offered_in(2));
+
+    assert!(ModuleRegistry::from_module_info_json("{}").is_err());
+}
+
+#[test]
+fn test_registry_serde_round_trip() {
+    let registry = ModuleRegistry::from_module_info_json(MODULE_INFO).unwrap();
+
+    let json = serde_json::to_string(&registry).unwrap();
+    assert!(json.find("IE2101").unwrap() < json.find("IE4100").unwrap());
+
+    let restored: ModuleRegistry = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.len(), 2);
+    assert_eq!(restored.direct_dependents("IE1111R"), vec!["IE2101"]);
+    assert_eq!(
+        restored.iter().map(|m| m.units()).max(),
+        Some(Units::from(8))
+    );
+}
+
+#[test]
+fn test_registry_merge_policies() {
+    let base = || -> ModuleRegistry {
+        vec![
+            prereq_module("IE1111R", None),
+            prereq_module("IE2101", None),
+        ]
+        .into_iter()
+        .collect()
+    };
+    let incoming = || -> ModuleRegistry {
+        let mut updated = prereq_module("IE2101", None);
+        updated.title = "Updated".to_string();
+        vec![updated, prereq_module("IE2110", None)]
+            .into_iter()
+            .collect()
+    };
+
+    let mut registry = base();
+    let conflicts = registry
+        .merge(incoming(), MergePolicy::KeepExisting)
+        .unwrap();
+    assert_eq!(conflicts, vec!["IE2101".to_string()]);
+    assert_eq!(registry.len(), 3);
+    assert_eq!(registry.get_module("IE2101").unwrap().title, "IE2101");
+
+    let mut registry = base();
+    registry.merge(incoming(), MergePolicy::Overwrite).unwrap();
+    assert_eq!(registry.get_module("IE2101").unwrap().title, "Updated");
+
+    let mut registry = base();
+    assert!(registry.merge(incoming(), MergePolicy::Fail).is_err());
+    assert_eq!(registry.len(), 2);
+}
+
+#[test]
+fn test_registry_academic_year_lookups() {
+    let mut registry = ModuleRegistry::new();
+
+    let mut old = prereq_module("IE2101", None);
+    old.module_credit = 3.into();
+    registry.add_module_for_year("2022/2023", old);
+    registry.add_module_for_year("2024-2025", prereq_module("IE2101", None));
+    let mut older = prereq_module("IE2101", None);
+    older.module_credit = 2.into();
+    registry.add_module_for_year("AY2021/22", older);
+
+    assert_eq!(
+        registry.known_years("IE2101"),
+        vec!["2021/2022", "2022/2023", "2024/2025"]
+    );
+
+    // get_module always returns the latest year's data
+    assert_eq!(
+        registry.get_module("IE2101").unwrap().module_credit,
+        ModuleCredit::from(4)
+    );
+
+    let credit_in = |year: &str| {
+        registry
+            .get_module_for_year("IE2101", year)
+            .unwrap()
+            .module_credit
+    };
+    assert_eq!(credit_in("2022/2023"), ModuleCredit::from(3));
+    // Unknown years fall back to the closest earlier year, then the latest
+    assert_eq!(credit_in("2023/2024"), ModuleCredit::from(3));
+    assert_eq!(credit_in("2026/2027"), ModuleCredit::from(4));
+    assert_eq!(credit_in("2019/2020"), ModuleCredit::from(4));
+    assert_eq!(credit_in("unknown"), ModuleCredit::from(4));
+
+    assert!(registry
+        .get_module_for_year("IE2110", "2023/2024")
+        .is_none());
+}
+
+#[test]
+fn test_registry_merge_and_replace_carry_year_history() {
+    let versioned = |title: &str, credit: u32| -> ModuleRegistry {
+        let mut module = prereq_module("IE2101", None);
+        module.title = title.to_string();
+        module.module_credit = credit.into();
+        let mut registry = ModuleRegistry::new();
+        registry.add_module_for_year("2023/2024", module);
+        registry
+    };
+    let title_in = |registry: &ModuleRegistry, year: &str| {
+        registry
+            .get_module_for_year("IE2101", year)
+            .unwrap()
+            .title
+            .clone()
+    };
+
+    let mut registry = versioned("Old", 3);
+    registry
+        .merge(versioned("New", 4), MergePolicy::Overwrite)
+        .unwrap();
+    assert_eq!(title_in(&registry, "2023/2024"), "New");
+
+    let mut registry = versioned("Old", 3);
+    registry
+        .merge(versioned("New", 4), MergePolicy::KeepExisting)
+        .unwrap();
+    assert_eq!(title_in(&registry, "2023/2024"), "Old");
+
+    // Incoming history is kept for modules new to the registry
+    let mut registry = ModuleRegistry::new();
+    registry
+        .merge(versioned("New", 4), MergePolicy::Fail)
+        .unwrap();
+    assert_eq!(registry.known_years("IE2101"), vec!["2023/2024"]);
+
+    // An unversioned replacement supersedes the recorded years
+    let mut registry = versioned("Old", 3);
+    registry.add_module(prereq_module("IE2101", None));
+    assert!(registry.known_years("IE2101").is_empty());
+    assert_eq!(title_in(&registry, "2023/2024"), "IE2101");
+}
@@ -1,4 +1,4 @@
-use module_planner::models::{Lesson, Module, PrereqTree, SemesterData, WeekType};
+use module_planner::models::{Lesson, Module, ModuleCredit, PrereqTree, SemesterData, WeekType};
 
 #[test]
 fn test_module_creation() {
@@ -9,7 +9,7 @@ fn test_module_creation() {
             "This module introduces the concepts of quality engineering and statistical process control."
                 .to_string(),
         ),
-        module_credit: "4".to_string(),
+        module_credit: 4.into(),
         department: Some("Industrial Systems Engineering".to_string()),
         faculty: Some("Faculty of Engineering".to_string()),
         workload: Some(vec![2.0, 1.0, 1.0, 3.0, 3.0]),
@@ -23,7 +23,7 @@ fn test_module_creation() {
 
     assert_eq!(module.module_code, "IE2130");
     assert_eq!(module.title, "Quality Engineering");
-    assert_eq!(module.module_credit, "4");
+    assert_eq!(module.module_credit, ModuleCredit::from(4));
     assert!(module.description.is_some());
     assert!(module.department.is_some());
     assert!(module.faculty.is_some());
@@ -93,6 +93,41 @@ fn test_week_type_range() {
     }
 }
 
+#[test]
+fn test_week_type_occurs_in_week() {
+    let odd_weeks = WeekType::Weeks(vec![1, 3, 5, 7, 9, 11, 13]);
+    assert!(odd_weeks.occurs_in_week(3));
+    assert!(!odd_weeks.occurs_in_week(4));
+
+    let every_week = WeekType::WeekRange {
+        start: "2023-08-14".to_string(),
+        end: "2023-11-17".to_string(),
+        week_interval: Some(1),
+        weeks: None,
+    };
+    assert!(every_week.occurs_in_week(4));
+}
+
+#[test]
+fn test_lesson_hour_span() {
+    let mut lesson = Lesson {
+        class_no: "01".to_string(),
+        lesson_type: "Lecture".to_string(),
+        week_type: WeekType::Weeks(vec![1]),
+        day: "Monday".to_string(),
+        start_time: "0900".to_string(),
+        end_time: "1100".to_string(),
+        venue: "E1-06-01".to_string(),
+    };
+    assert_eq!(lesson.hour_span(), Some((9, 11)));
+
+    lesson.end_time = "1030".to_string();
+    assert_eq!(lesson.hour_span(), Some((9, 11)));
+
+    lesson.end_time = "9am".to_string();
+    assert_eq!(lesson.hour_span(), None);
+}
+
 #[test]
 fn test_prereq_tree() {
     // Test ModuleCode variant
@@ -1,6 +1,10 @@
+use crate::fixtures::{module, planned, registry};
 use module_planner::models::{
-    ActivityType, ModuleStatus, Plan, PlannedModule, SemesterPlan, SpecialActivity,
+    grade_point, ActivityType, ModuleRegistry, ModuleStatus, Plan, PlannedModule, SemesterPlan,
+    SpecialActivity, Units,
 };
+use module_planner::plan_ops;
+use std::collections::HashMap;
 
 #[test]
 fn test_plan_creation() {
@@ -14,7 +18,7 @@ fn test_plan_creation() {
     assert_eq!(plan.name, "ISE Degree Plan");
     assert_eq!(plan.student_id, "A0123456X");
     assert!(plan.semesters.is_empty());
-    assert_eq!(plan.total_units(), 0);
+    assert_eq!(plan.total_units(), Units::from(0));
     assert!(plan.notes.is_none());
 }
 
@@ -36,22 +40,24 @@ fn test_plan_with_semesters() {
                 status: ModuleStatus::Completed,
                 grade: Some("A".to_string()),
                 s_u_option: false,
+                class_selections: HashMap::new(),
             },
             PlannedModule {
                 module_code: "IE2130".to_string(),
                 status: ModuleStatus::Current,
                 grade: None,
                 s_u_option: false,
+                class_selections: HashMap::new(),
             },
         ],
-        total_units: 8,
+        total_units: Units::from(8),
         special_activities: vec![],
     };
 
     plan.semesters.push(semester_plan);
 
     assert_eq!(plan.semesters.len(), 1);
-    assert_eq!(plan.total_units(), 8);
+    assert_eq!(plan.total_units(), Units::from(8));
 
     // Test all_modules method
     let all_modules = plan.all_modules();
@@ -70,8 +76,9 @@ fn test_semester_plan() {
             status: ModuleStatus::Planned,
             grade: None,
             s_u_option: true,
+            class_selections: HashMap::new(),
         }],
-        total_units: 4,
+        total_units: Units::from(4),
         special_activities: vec![SpecialActivity {
             activity_type: ActivityType::IndustrialAttachment,
             description: "Summer internship at Manufacturing Company".to_string(),
@@ -82,7 +89,7 @@ fn test_semester_plan() {
     assert_eq!(semester_plan.year, "2023/2024");
     assert_eq!(semester_plan.semester, 2);
     assert_eq!(semester_plan.modules.len(), 1);
-    assert_eq!(semester_plan.total_units, 4);
+    assert_eq!(semester_plan.total_units, Units::from(4));
     assert_eq!(semester_plan.special_activities.len(), 1);
 }
 
@@ -149,3 +156,212 @@ fn test_activity_type() {
         _ => panic!("Expected ActivityType::Other"),
     }
 }
+
+fn units_registry() -> ModuleRegistry {
+    registry([
+        module("IE1111R"),
+        module("IE2130").credit("2.5"),
+        module("IE4001").credit("0-8"),
+    ])
+}
+
+#[test]
+fn test_derived_units_include_activities() {
+    let registry = units_registry();
+    let mut semester = SemesterPlan::empty("2023/2024".to_string(), 1);
+    semester.modules = vec![
+        planned("IE1111R", ModuleStatus::Planned),
+        planned("IE2130", ModuleStatus::Planned),
+        planned("XX9999", ModuleStatus::Planned),
+    ];
+    semester.special_activities.push(SpecialActivity {
+        activity_type: ActivityType::Research,
+        description: "Lab attachment".to_string(),
+        credits: Some(2),
+    });
+
+    // 4 + 2.5 + unknown module (0) + 2 from the activity
+    assert_eq!(semester.activity_units(), Units::from(2));
+    assert_eq!(semester.derived_units(&registry), "8.5".parse().unwrap());
+
+    // The load-time repair needs every module to resolve
+    assert_eq!(semester.resolved_units(&registry), None);
+    semester.modules.pop();
+    assert_eq!(
+        semester.resolved_units(&registry),
+        Some("8.5".parse().unwrap())
+    );
+}
+
+#[test]
+fn test_check_and_repair_unit_totals() {
+    let registry = units_registry();
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        "ISE Degree Plan".to_string(),
+        "A0123456X".to_string(),
+    );
+
+    let mut first = SemesterPlan::empty("2023/2024".to_string(), 1);
+    first.modules = vec![planned("IE1111R", ModuleStatus::Planned)];
+    first.total_units = Units::from(4);
+    let mut second = first.next_semester();
+    second.modules = vec![
+        planned("IE1111R", ModuleStatus::Planned),
+        planned("IE4001", ModuleStatus::Planned),
+    ];
+    second.total_units = Units::from(4);
+    plan.semesters = vec![first, second];
+
+    let mismatches = plan.check_unit_totals(&registry);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].semester_index, 1);
+    assert_eq!(mismatches[0].stored, Units::from(4));
+    // Variable-credit modules count at their maximum
+    assert_eq!(mismatches[0].derived, Units::from(12));
+    assert_eq!(plan.total_units(), Units::from(8));
+
+    let repaired = plan.repair_unit_totals(&registry);
+    assert_eq!(repaired, mismatches);
+    assert!(plan.check_unit_totals(&registry).is_empty());
+    assert_eq!(plan.total_units(), plan.derived_units(&registry));
+    assert_eq!(plan.total_units(), Units::from(16));
+}
+
+#[test]
+fn test_unit_totals_kept_without_full_registry_data() {
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        "ISE Degree Plan".to_string(),
+        "A0123456X".to_string(),
+    );
+    let mut semester = SemesterPlan::empty("2023/2024".to_string(), 1);
+    semester.modules = vec![
+        planned("IE1111R", ModuleStatus::Planned),
+        planned("XX9999", ModuleStatus::Planned),
+    ];
+    semester.total_units = Units::from(8);
+    plan.semesters = vec![semester];
+
+    // XX9999 is unknown, so the stored 8 cannot be checked
+    assert!(plan.check_unit_totals(&units_registry()).is_empty());
+    // An empty registry never repairs anything
+    assert!(plan.repair_unit_totals(&ModuleRegistry::new()).is_empty());
+    assert_eq!(plan.total_units(), Units::from(8));
+}
+
+#[test]
+fn test_edits_adjust_totals_with_empty_registry() {
+    let registry = ModuleRegistry::new();
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        "ISE Degree Plan".to_string(),
+        "A0123456X".to_string(),
+    );
+    let mut semester = SemesterPlan::empty("2023/2024".to_string(), 1);
+    semester.modules = vec![planned("IE1111R", ModuleStatus::Planned)];
+    semester.total_units = Units::from(4);
+    plan.semesters = vec![semester];
+
+    plan_ops::add_searched_module(&mut plan, &registry, 0, &module("IE2101").build()).unwrap();
+    assert_eq!(plan.total_units(), Units::from(8));
+
+    let activity = SpecialActivity {
+        activity_type: ActivityType::Research,
+        description: "Lab attachment".to_string(),
+        credits: Some(2),
+    };
+    plan_ops::add_activity(&mut plan, &registry, 0, activity).unwrap();
+    assert_eq!(plan.total_units(), Units::from(10));
+
+    // Nothing resolves, so removing a module cannot tell what to take off
+    plan_ops::remove_module(&mut plan, &registry, 0, "IE1111R").unwrap();
+    assert_eq!(plan.total_units(), Units::from(10));
+}
+
+#[test]
+fn test_edits_adjust_totals_around_unknown_module() {
+    let registry = units_registry();
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        "ISE Degree Plan".to_string(),
+        "A0123456X".to_string(),
+    );
+    let mut first = SemesterPlan::empty("2023/2024".to_string(), 1);
+    first.modules = vec![planned("XX9999", ModuleStatus::Planned)];
+    first.total_units = Units::from(4);
+    let second = first.next_semester();
+    plan.semesters = vec![first, second];
+
+    plan_ops::add_module(&mut plan, &registry, 0, "IE1111R").unwrap();
+    plan_ops::add_module(&mut plan, &registry, 0, "IE2130").unwrap();
+    assert_eq!(plan.semesters[0].total_units.to_string(), "10.5");
+
+    plan_ops::move_module(&mut plan, &registry, 0, 1, "IE2130").unwrap();
+    assert_eq!(plan.semesters[0].total_units, Units::from(8));
+    assert_eq!(plan.semesters[1].total_units.to_string(), "2.5");
+
+    plan_ops::remove_module(&mut plan, &registry, 0, "IE1111R").unwrap();
+    assert_eq!(plan.semesters[0].total_units, Units::from(4));
+    assert_eq!(plan.total_units().to_string(), "6.5");
+}
+
+#[test]
+fn test_projected_cap_weights_by_units() {
+    let registry = units_registry();
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        "CAP Plan".to_string(),
+        "A0123456X".to_string(),
+    );
+    let mut semester = SemesterPlan::empty("2023/2024".to_string(), 1);
+    assert_eq!(plan.projected_cap(&registry), None);
+
+    let graded = |code: &str, grade: &str| PlannedModule {
+        grade: Some(grade.to_string()),
+        ..planned(code, ModuleStatus::Planned)
+    };
+    // 4 units of A (5.0) and 2.5 units of B (3.5); the S grade and the
+    // unknown module are left out
+    semester.modules = vec![
+        graded("IE1111R", "A"),
+        graded("IE2130", "b"),
+        graded("IE4001", "S"),
+        graded("XX9999", "F"),
+    ];
+    plan.semesters.push(semester);
+
+    let cap = plan.projected_cap(&registry).unwrap();
+    assert!((cap - 28.75 / 6.5).abs() < 1e-4);
+    assert_eq!(grade_point("A-"), Some(4.5));
+    assert_eq!(grade_point("CS"), None);
+}
+
+#[test]
+fn test_projected_cap_counts_latest_attempt_and_skips_s_u() {
+    let registry = units_registry();
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        "CAP Plan".to_string(),
+        "A0123456X".to_string(),
+    );
+    let graded = |code: &str, grade: &str| PlannedModule {
+        grade: Some(grade.to_string()),
+        ..planned(code, ModuleStatus::Planned)
+    };
+
+    let mut first = SemesterPlan::empty("2023/2024".to_string(), 1);
+    first.modules = vec![
+        graded("IE1111R", "F"),
+        PlannedModule {
+            s_u_option: true,
+            ..graded("IE2130", "C")
+        },
+    ];
+    let mut second = first.next_semester();
+    second.modules = vec![graded("IE1111R", "B")];
+    plan.semesters = vec![first, second];
+
+    // Only the retake's B counts; the S/U-opted C is left out
+    assert_eq!(plan.projected_cap(&registry), Some(3.5));
+}
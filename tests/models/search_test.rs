@@ -0,0 +1,126 @@
+use crate::fixtures::{self, ModuleBuilder};
+use module_planner::models::{Module, ModuleQuery, ModuleRegistry};
+
+fn module(code: &str, title: &str, description: &str, department: &str) -> ModuleBuilder {
+    fixtures::module(code)
+        .title(title)
+        .description(description)
+        .department(department)
+        .faculty("College of Design and Engineering")
+}
+
+fn registry() -> ModuleRegistry {
+    fixtures::registry([
+        module(
+            "IE2110",
+            "Operations Research I",
+            "Linear programming and network optimisation.",
+            "Industrial Systems Engineering",
+        )
+        .semester(1, true)
+        .workload(10.0),
+        module(
+            "IE3101",
+            "Statistics for Engineering Applications",
+            "Regression and design of experiments for operations.",
+            "Industrial Systems Engineering",
+        )
+        .semester(2, true)
+        .workload(10.0),
+        module(
+            "IE4299",
+            "Operations Project",
+            "An independent project.",
+            "Industrial Systems Engineering",
+        )
+        .offered_in(&[1, 2])
+        .workload(14.0),
+        module(
+            "MA1511",
+            "Engineering Calculus",
+            "Differentiation and integration.",
+            "Mathematics",
+        )
+        .semester(1, true)
+        .semester(2, true)
+        .workload(5.0),
+    ])
+}
+
+fn codes(modules: Vec<&Module>) -> Vec<&str> {
+    modules.iter().map(|m| m.module_code.as_str()).collect()
+}
+
+#[test]
+fn test_search_ranks_matches() {
+    let registry = registry();
+
+    // Title matches rank above description-only matches
+    let results = registry.search(&ModuleQuery::text("operations"));
+    assert_eq!(codes(results), vec!["IE2110", "IE4299", "IE3101"]);
+
+    // Partial codes match by prefix
+    assert_eq!(
+        codes(registry.search(&ModuleQuery::text("ie2"))),
+        vec!["IE2110"]
+    );
+
+    // Typos are tolerated and every word must match
+    assert_eq!(
+        codes(registry.search(&ModuleQuery::text("calculs engineering"))),
+        vec!["MA1511"]
+    );
+    assert!(registry.search(&ModuleQuery::text("quantum")).is_empty());
+}
+
+#[test]
+fn test_search_filters() {
+    let registry = registry();
+
+    let query = ModuleQuery {
+        levels: vec![3000, 4000],
+        ..ModuleQuery::default()
+    };
+    assert_eq!(codes(registry.search(&query)), vec!["IE3101", "IE4299"]);
+
+    let query = ModuleQuery {
+        text: "operations".to_string(),
+        semester: Some(1),
+        has_exam: Some(false),
+        ..ModuleQuery::default()
+    };
+    assert_eq!(codes(registry.search(&query)), vec!["IE4299"]);
+
+    let query = ModuleQuery {
+        department: Some("industrial".to_string()),
+        max_workload: Some(12.0),
+        units: Some(4.into()),
+        ..ModuleQuery::default()
+    };
+    assert_eq!(codes(registry.search(&query)), vec!["IE2110", "IE3101"]);
+}
+
+#[test]
+fn test_search_index_updates_on_replace() {
+    let mut registry = registry();
+    registry.add_module(
+        module(
+            "IE2110",
+            "Deterministic Optimisation",
+            "Linear programming.",
+            "Industrial Systems Engineering",
+        )
+        .semester(1, true)
+        .workload(10.0)
+        .build(),
+    );
+
+    assert_eq!(
+        codes(registry.search(&ModuleQuery::text("research"))),
+        Vec::<&str>::new()
+    );
+    assert_eq!(
+        codes(registry.search(&ModuleQuery::text("deterministic"))),
+        vec!["IE2110"]
+    );
+}
@@ -1,4 +1,8 @@
-use module_planner::models::{CandidatureType, Student};
+use chrono::NaiveDate;
+use module_planner::models::{
+    academic_year_on, academic_years_around, validate_matriculation_year,
+    validate_target_graduation, CandidatureType, Student,
+};
 use std::collections::HashSet;
 
 #[test]
@@ -11,6 +15,7 @@ fn test_student_creation() {
     exempted_modules.insert("GER1000".to_string());
 
     let student = Student {
+        id: "test-student-id".to_string(),
         name: "John Doe".to_string(),
         matriculation_year: "2022".to_string(),
         faculty: "Faculty of Engineering".to_string(),
@@ -43,6 +48,7 @@ fn test_student_creation() {
 #[test]
 fn test_remaining_semesters_standard() {
     let student = Student {
+        id: "test-student-id".to_string(),
         name: "John Doe".to_string(),
         matriculation_year: "2022".to_string(),
         faculty: "Faculty of Engineering".to_string(),
@@ -62,6 +68,7 @@ fn test_remaining_semesters_standard() {
 #[test]
 fn test_remaining_semesters_double_honours() {
     let student = Student {
+        id: "test-student-id".to_string(),
         name: "Jane Smith".to_string(),
         matriculation_year: "2022".to_string(),
         faculty: "Faculty of Engineering".to_string(),
@@ -81,6 +88,7 @@ fn test_remaining_semesters_double_honours() {
 #[test]
 fn test_remaining_semesters_double_degree() {
     let student = Student {
+        id: "test-student-id".to_string(),
         name: "Alex Johnson".to_string(),
         matriculation_year: "2022".to_string(),
         faculty: "School of Computing".to_string(),
@@ -100,6 +108,7 @@ fn test_remaining_semesters_double_degree() {
 #[test]
 fn test_remaining_semesters_concurrent_degree() {
     let student = Student {
+        id: "test-student-id".to_string(),
         name: "Sarah Lee".to_string(),
         matriculation_year: "2022".to_string(),
         faculty: "School of Computing".to_string(),
@@ -119,6 +128,7 @@ fn test_remaining_semesters_concurrent_degree() {
 #[test]
 fn test_remaining_semesters_engineering_scholars() {
     let student = Student {
+        id: "test-student-id".to_string(),
         name: "Michael Wong".to_string(),
         matriculation_year: "2022".to_string(),
         faculty: "Faculty of Engineering".to_string(),
@@ -134,3 +144,115 @@ fn test_remaining_semesters_engineering_scholars() {
 
     assert_eq!(student.remaining_semesters(), 1);
 }
+
+#[test]
+fn test_academic_term() {
+    let student = Student {
+        id: "test-student-id".to_string(),
+        name: "John Doe".to_string(),
+        matriculation_year: "2022/2023".to_string(),
+        faculty: "Faculty of Engineering".to_string(),
+        major: "Industrial Systems Engineering".to_string(),
+        second_major: None,
+        minors: vec![],
+        completed_modules: HashSet::new(),
+        exempted_modules: HashSet::new(),
+        advanced_placement_credits: 0,
+        current_semester: 1,
+        candidature_type: CandidatureType::Standard,
+    };
+
+    assert_eq!(student.academic_term(1), Some(("2022/2023".to_string(), 1)));
+    assert_eq!(student.academic_term(4), Some(("2023/2024".to_string(), 2)));
+    assert_eq!(student.academic_term(0), None);
+}
+
+#[test]
+fn test_remaining_semesters_does_not_underflow() {
+    let student = Student {
+        id: "test-student-id".to_string(),
+        name: "Extended Student".to_string(),
+        matriculation_year: "2020/2021".to_string(),
+        faculty: "Faculty of Engineering".to_string(),
+        major: "Industrial Systems Engineering".to_string(),
+        second_major: None,
+        minors: vec![],
+        completed_modules: HashSet::new(),
+        exempted_modules: HashSet::new(),
+        advanced_placement_credits: 0,
+        current_semester: 9,
+        candidature_type: CandidatureType::Standard,
+    };
+
+    assert_eq!(student.remaining_semesters(), 0);
+    assert_eq!(student.semester_number("2024/2025", 1), Some(9));
+    assert_eq!(student.semester_number("2019/2020", 2), None);
+    assert_eq!(student.semester_number("2024/2025", 3), None);
+}
+
+#[test]
+fn test_candidature_rules() {
+    let standard = CandidatureType::Standard.rules();
+    assert_eq!(standard.normal_semesters, 8);
+    assert_eq!(standard.max_semesters, 10);
+    assert!(standard.special_terms.is_empty());
+
+    let ddp = CandidatureType::DoubleDegreeProgramme.rules();
+    assert_eq!(ddp.normal_semesters, 10);
+    assert!(ddp.max_semesters > ddp.normal_semesters);
+    assert_eq!(ddp.special_terms, vec![3]);
+
+    let concurrent = CandidatureType::ConcurrentDegree.rules();
+    assert!(concurrent.max_units_per_semester >= concurrent.normal_units_per_semester);
+}
+
+#[test]
+fn test_academic_years_from_date() {
+    let july = NaiveDate::from_ymd_opt(2025, 7, 31).unwrap();
+    let august = NaiveDate::from_ymd_opt(2025, 8, 1).unwrap();
+    assert_eq!(academic_year_on(july), "2024/2025");
+    assert_eq!(academic_year_on(august), "2025/2026");
+    assert_eq!(
+        academic_years_around(august, -1, 3),
+        vec!["2024/2025", "2025/2026", "2026/2027"]
+    );
+}
+
+#[test]
+fn test_candidature_type_parsing() {
+    assert!(matches!(
+        "Double Degree Programme".parse::<CandidatureType>(),
+        Ok(CandidatureType::DoubleDegreeProgramme)
+    ));
+    assert!(matches!(
+        "double-honours".parse::<CandidatureType>(),
+        Ok(CandidatureType::DoubleHonours)
+    ));
+    for candidature in CandidatureType::ALL {
+        let label = candidature.to_string();
+        assert_eq!(label.parse::<CandidatureType>().unwrap().to_string(), label);
+    }
+    assert!("part-time".parse::<CandidatureType>().is_err());
+}
+
+#[test]
+fn test_matriculation_year_format() {
+    assert!(validate_matriculation_year("2024/2025").is_ok());
+    for invalid in ["2024", "2024/2026", "24/25", "2024-2025", "2025/2024"] {
+        assert!(validate_matriculation_year(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_target_graduation_format() {
+    assert!(validate_target_graduation("2027/2028 Semester 2").is_ok());
+    for invalid in [
+        "2027 sem 2",
+        "2027/2028",
+        "2027/2028 Semester 3",
+        "2027/2029 Semester 1",
+        "Semester 1",
+    ] {
+        assert!(validate_target_graduation(invalid).is_err(), "{}", invalid);
+    }
+}
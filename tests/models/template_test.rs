@@ -0,0 +1,133 @@
+use crate::fixtures;
+use module_planner::models::{
+    ActivityType, CandidatureType, ModuleRegistry, ModuleStatus, Plan, PlanTemplate, PlannedModule,
+    SemesterPlan, SpecialActivity, Student, TemplateSemester,
+};
+use std::collections::HashSet;
+
+fn student(current_semester: u8) -> Student {
+    Student {
+        id: "test-student-id".to_string(),
+        name: "Test Student".to_string(),
+        matriculation_year: "2024/2025".to_string(),
+        faculty: "College of Design and Engineering".to_string(),
+        major: "Industrial & Systems Engineering".to_string(),
+        second_major: None,
+        minors: Vec::new(),
+        completed_modules: HashSet::from(["MA1511".to_string()]),
+        exempted_modules: HashSet::from(["ES1103".to_string()]),
+        advanced_placement_credits: 0,
+        current_semester,
+        candidature_type: CandidatureType::Standard,
+    }
+}
+
+fn template() -> PlanTemplate {
+    let semester = |number: u8, modules: &[&str]| TemplateSemester {
+        number,
+        modules: modules.iter().map(|m| m.to_string()).collect(),
+        special_activities: Vec::new(),
+    };
+    let mut template = PlanTemplate {
+        name: "ISE AY2024 intake".to_string(),
+        curriculum: "Industrial & Systems Engineering".to_string(),
+        cohort: "2024/2025".to_string(),
+        semesters: vec![
+            semester(1, &["MA1511", "ES1103", "IE1111R"]),
+            semester(2, &["IE1113", "CS1010E"]),
+            semester(3, &["IE2101"]),
+            semester(9, &["IE4100"]),
+        ],
+    };
+    template.semesters[2]
+        .special_activities
+        .push(SpecialActivity {
+            activity_type: ActivityType::CommunityService,
+            description: "CSP".to_string(),
+            credits: None,
+        });
+    template
+}
+
+fn plan(terms: &[(&str, u8)]) -> Plan {
+    fixtures::plan(
+        "Template Plan",
+        terms
+            .iter()
+            .map(|(year, semester)| SemesterPlan::empty(year.to_string(), *semester))
+            .collect(),
+    )
+}
+
+fn codes(plan: &Plan, index: usize) -> Vec<&str> {
+    plan.semesters[index]
+        .modules
+        .iter()
+        .map(|m| m.module_code.as_str())
+        .collect()
+}
+
+#[test]
+fn test_template_fills_plan_from_matriculation() {
+    let mut plan = plan(&[("2024/2025", 1), ("2024/2025", 2), ("2025/2026", 1)]);
+    let report = template().apply(&mut plan, &student(1), &ModuleRegistry::new());
+
+    assert_eq!(codes(&plan, 0), vec!["IE1111R"]);
+    assert_eq!(codes(&plan, 1), vec!["IE1113", "CS1010E"]);
+    assert_eq!(codes(&plan, 2), vec!["IE2101"]);
+    assert_eq!(plan.semesters[2].special_activities.len(), 1);
+
+    assert_eq!(report.skipped, vec!["MA1511", "ES1103"]);
+    assert!(report.carried_forward.is_empty());
+    assert_eq!(report.unplaced, vec![9]);
+}
+
+#[test]
+fn test_template_shifts_forward_when_behind() {
+    let mut plan = plan(&[("2025/2026", 1), ("2025/2026", 2)]);
+    plan.semesters[0].modules.push(PlannedModule {
+        module_code: "CS1010E".to_string(),
+        status: ModuleStatus::Planned,
+        grade: None,
+        s_u_option: false,
+        class_selections: Default::default(),
+    });
+
+    let report = template().apply(&mut plan, &student(3), &ModuleRegistry::new());
+
+    // IE1111R (semester 1) is still to be taken, so the whole template
+    // moves two semesters later and keeps IE1113 after IE1111R
+    assert_eq!(report.shifted_by, 2);
+    assert_eq!(codes(&plan, 0), vec!["CS1010E", "IE1111R"]);
+    assert_eq!(codes(&plan, 1), vec!["IE1113"]);
+    assert_eq!(report.carried_forward, vec!["IE1111R", "IE1113"]);
+    // Template semester 3 now falls in semester 5, beyond this plan
+    assert_eq!(report.unplaced, vec![3, 9]);
+    assert!(plan.semesters[0].special_activities.is_empty());
+}
+
+#[test]
+fn test_template_skips_semesters_left_behind() {
+    let mut plan = plan(&[("2025/2026", 1), ("2025/2026", 2)]);
+    let mut template = template();
+    template.semesters[0].modules = vec!["MA1511".to_string()];
+    template.semesters[0]
+        .special_activities
+        .push(SpecialActivity {
+            activity_type: ActivityType::CommunityService,
+            description: "Orientation".to_string(),
+            credits: None,
+        });
+    template.semesters[1].modules.clear();
+
+    let report = template.apply(&mut plan, &student(3), &ModuleRegistry::new());
+
+    // Semesters 1 and 2 hold nothing left to take, so nothing shifts and
+    // their activities are not copied
+    assert_eq!(report.shifted_by, 0);
+    assert_eq!(report.skipped, vec!["MA1511"]);
+    assert_eq!(codes(&plan, 0), vec!["IE2101"]);
+    assert_eq!(plan.semesters[0].special_activities.len(), 1);
+    assert_eq!(plan.semesters[0].special_activities[0].description, "CSP");
+    assert!(report.carried_forward.is_empty());
+}
@@ -0,0 +1,72 @@
+use module_planner::models::{ModuleCredit, Units};
+
+#[test]
+fn test_units_parse_and_display() {
+    let half: Units = "2.5".parse().unwrap();
+    assert_eq!(half.hundredths(), 250);
+    assert_eq!(half.to_string(), "2.5");
+    assert_eq!(Units::from(4).to_string(), "4");
+    assert_eq!(Units::from_hundredths(125).to_string(), "1.25");
+
+    assert!("abc".parse::<Units>().is_err());
+    assert!("-1".parse::<Units>().is_err());
+}
+
+#[test]
+fn test_units_sum_fractional_credits() {
+    let total: Units = ["2.5", "2.5", "4"]
+        .iter()
+        .map(|s| s.parse::<Units>().unwrap())
+        .sum();
+    assert_eq!(total, Units::from(9));
+    assert_eq!(total.whole(), 9);
+    assert_eq!(Units::from(2).saturating_sub(total), Units::ZERO);
+}
+
+#[test]
+fn test_module_credit_parse_ranges() {
+    assert_eq!("4".parse::<ModuleCredit>().unwrap(), ModuleCredit::from(4));
+
+    let variable: ModuleCredit = "0–8".parse().unwrap();
+    assert_eq!(
+        variable,
+        ModuleCredit::Variable {
+            min: Units::ZERO,
+            max: Units::from(8),
+        }
+    );
+    assert!(variable.is_variable());
+    assert_eq!(variable.units(), Units::from(8));
+    assert_eq!(variable.to_string(), "0-8");
+
+    assert_eq!(
+        "2 to 4".parse::<ModuleCredit>().unwrap(),
+        "2-4".parse::<ModuleCredit>().unwrap()
+    );
+    assert_eq!(
+        "4-4".parse::<ModuleCredit>().unwrap(),
+        ModuleCredit::from(4)
+    );
+    assert!("8-0".parse::<ModuleCredit>().is_err());
+    assert!("four".parse::<ModuleCredit>().is_err());
+}
+
+#[test]
+fn test_units_serde_round_trip() {
+    assert_eq!(serde_json::to_string(&Units::from(4)).unwrap(), "4");
+    assert_eq!(
+        serde_json::to_string(&"2.5".parse::<Units>().unwrap()).unwrap(),
+        "2.5"
+    );
+    assert_eq!(
+        serde_json::from_str::<Units>("\"2.5\"")
+            .unwrap()
+            .hundredths(),
+        250
+    );
+
+    let credit: ModuleCredit = serde_json::from_str("\"0-8\"").unwrap();
+    assert_eq!(serde_json::to_string(&credit).unwrap(), "\"0-8\"");
+    let numeric: ModuleCredit = serde_json::from_str("4").unwrap();
+    assert_eq!(numeric, ModuleCredit::from(4));
+}
@@ -0,0 +1,60 @@
+use chrono::{Duration, Utc};
+use module_planner::models::{Plan, PlanVersion, RetentionPolicy};
+
+fn versions(ages_in_days: &[i64]) -> Vec<PlanVersion> {
+    let now = Utc::now();
+    ages_in_days
+        .iter()
+        .enumerate()
+        .map(|(i, &age)| {
+            let mut plan = Plan::new(
+                "plan1".to_string(),
+                "Versioned Plan".to_string(),
+                "Test Student".to_string(),
+            );
+            plan.modified_at = now - Duration::days(age);
+            PlanVersion::new(&plan, i as u32 + 1)
+        })
+        .collect()
+}
+
+#[test]
+fn test_retention_keep_last_and_keep_all() {
+    let versions = versions(&[40, 30, 20, 10, 0]);
+    let now = Utc::now();
+
+    assert!(RetentionPolicy::keep_all()
+        .expired(&versions, now)
+        .is_empty());
+    assert_eq!(
+        RetentionPolicy::keep_last(2).expired(&versions, now),
+        vec![1, 2, 3]
+    );
+    // The latest version survives even a policy that keeps nothing
+    assert_eq!(
+        RetentionPolicy::keep_last(0).expired(&versions, now),
+        vec![1, 2, 3, 4]
+    );
+}
+
+#[test]
+fn test_retention_rules_combine() {
+    let versions = versions(&[40, 30, 20, 10, 0]);
+    let now = Utc::now();
+
+    assert_eq!(
+        RetentionPolicy::keep_days(15).expired(&versions, now),
+        vec![1, 2, 3]
+    );
+    // The newest of the remaining versions is kept despite its age
+    assert_eq!(
+        RetentionPolicy::keep_days(15).expired(&versions[..3], now),
+        vec![1, 2]
+    );
+
+    let policy = RetentionPolicy {
+        keep_last: Some(3),
+        keep_days: Some(15),
+    };
+    assert_eq!(policy.expired(&versions, now), vec![1, 2]);
+}
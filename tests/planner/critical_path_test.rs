@@ -0,0 +1,116 @@
+use crate::fixtures::{self, module, semester};
+use module_planner::critical_path::analyse_critical_path;
+use module_planner::models::{CandidatureType, ModuleRegistry, ModuleStatus, Plan, Student};
+use module_planner::validation::{critical_path, ValidationLevel, ValidationResult};
+use std::collections::HashSet;
+
+fn registry() -> ModuleRegistry {
+    fixtures::registry([
+        module("IE1111R").offered_in(&[1, 2]),
+        module("IE2101").offered_in(&[1]).requires("IE1111R"),
+        module("IE3101").offered_in(&[2]).requires("IE2101"),
+        module("MA1511").offered_in(&[1, 2]),
+    ])
+}
+
+fn plan(target: &str) -> Plan {
+    let mut plan = fixtures::plan(
+        "Critical Plan",
+        vec![
+            semester("2023/2024", 1, &[("IE1111R", ModuleStatus::Completed)]),
+            semester("2023/2024", 2, &[("MA1511", ModuleStatus::Planned)]),
+            semester("2024/2025", 1, &[("IE2101", ModuleStatus::Planned)]),
+            semester("2024/2025", 2, &[("IE3101", ModuleStatus::Planned)]),
+        ],
+    );
+    plan.target_graduation = target.to_string();
+    plan
+}
+
+#[test]
+fn test_critical_path_slack() {
+    let report =
+        analyse_critical_path(&plan("2024/2025 Semester 2"), &registry(), &HashSet::new()).unwrap();
+
+    assert_eq!(report.deadline_index, 3);
+    assert_eq!(
+        report.longest_chain,
+        vec!["IE2101".to_string(), "IE3101".to_string()]
+    );
+
+    let ie2101 = report.get("IE2101").unwrap();
+    assert_eq!(ie2101.earliest_semester, Some(2));
+    assert_eq!(ie2101.latest_semester, Some(2));
+    assert_eq!(ie2101.chain_length, 2);
+    assert!(ie2101.is_critical());
+
+    let ma1511 = report.get("MA1511").unwrap();
+    assert_eq!(ma1511.slack(), Some(2));
+    assert!(!ma1511.is_critical());
+
+    let critical: Vec<&str> = report
+        .critical_modules()
+        .map(|m| m.module_code.as_str())
+        .collect();
+    assert_eq!(critical, vec!["IE2101", "IE3101"]);
+}
+
+#[test]
+fn test_critical_path_extends_to_later_target() {
+    let report =
+        analyse_critical_path(&plan("2025/2026 Semester 2"), &registry(), &HashSet::new()).unwrap();
+
+    assert_eq!(report.terms.len(), 6);
+    assert_eq!(report.deadline_index, 5);
+    assert_eq!(report.get("IE3101").unwrap().slack(), Some(2));
+    assert_eq!(report.get("IE2101").unwrap().latest_semester, Some(4));
+
+    assert!(analyse_critical_path(&plan(""), &registry(), &HashSet::new()).is_none());
+}
+
+#[test]
+fn test_validate_critical_path_flags_delays() {
+    let student = Student {
+        id: "test-student-id".to_string(),
+        name: "Test Student".to_string(),
+        matriculation_year: "2023/2024".to_string(),
+        faculty: "College of Design and Engineering".to_string(),
+        major: "Industrial Systems Engineering".to_string(),
+        second_major: None,
+        minors: Vec::new(),
+        completed_modules: HashSet::new(),
+        exempted_modules: HashSet::new(),
+        advanced_placement_credits: 0,
+        current_semester: 2,
+        candidature_type: CandidatureType::Standard,
+    };
+
+    let mut result = ValidationResult::new();
+    critical_path::validate_critical_path(
+        &plan("2024/2025 Semester 2"),
+        &registry(),
+        &student,
+        &mut result,
+    )
+    .unwrap();
+    assert_eq!(result.issues.len(), 1);
+    assert_eq!(result.issues[0].level, ValidationLevel::Warning);
+    assert_eq!(result.issues[0].module_code.as_deref(), Some("IE2101"));
+
+    // Graduating a semester earlier leaves no room for IE3101 after IE2101
+    let mut result = ValidationResult::new();
+    critical_path::validate_critical_path(
+        &plan("2024/2025 Semester 1"),
+        &registry(),
+        &student,
+        &mut result,
+    )
+    .unwrap();
+    let errors: Vec<&str> = result
+        .issues
+        .iter()
+        .filter(|i| i.level == ValidationLevel::Error)
+        .filter_map(|i| i.module_code.as_deref())
+        .collect();
+    assert_eq!(errors, vec!["IE2101", "IE3101"]);
+}
@@ -0,0 +1,178 @@
+use crate::fixtures::planned;
+use module_planner::diff::{
+    diff_plans, ActivityDiff, DiffSign, MetadataChange, ModuleDiff, SemesterKey,
+};
+use module_planner::models::{
+    ActivityType, ModuleStatus, Plan, PlannedModule, SemesterPlan, SpecialActivity,
+};
+
+fn graded(code: &str, status: ModuleStatus, grade: &str) -> PlannedModule {
+    PlannedModule {
+        grade: Some(grade.to_string()),
+        ..planned(code, status)
+    }
+}
+
+fn key(year: &str, semester: u8) -> SemesterKey {
+    SemesterKey {
+        year: year.to_string(),
+        semester,
+    }
+}
+
+fn base_plan() -> Plan {
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        "Diff Plan".to_string(),
+        "Test Student".to_string(),
+    );
+    plan.target_graduation = "2026/2027 Semester 2".to_string();
+    plan.semesters = vec![
+        SemesterPlan::empty("2023/2024".to_string(), 1),
+        SemesterPlan::empty("2023/2024".to_string(), 2),
+    ];
+    plan.semesters[0].modules = vec![
+        graded("IE1111R", ModuleStatus::Completed, "B"),
+        graded("MA1511", ModuleStatus::Completed, "A"),
+    ];
+    plan.semesters[1].modules = vec![
+        planned("IE2101", ModuleStatus::Planned),
+        planned("CS1010E", ModuleStatus::Planned),
+    ];
+    plan
+}
+
+#[test]
+fn test_identical_plans_have_no_diff() {
+    let plan = base_plan();
+    let diff = diff_plans(&plan, &plan.clone());
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn test_module_changes_are_reported() {
+    let old = base_plan();
+    let mut new = old.clone();
+
+    // Move CS1010E back a semester, drop MA1511, add IE2100 and regrade IE1111R
+    let moved = new.semesters[1].modules.remove(1);
+    new.semesters[0].modules.push(moved);
+    new.semesters[0]
+        .modules
+        .retain(|m| m.module_code != "MA1511");
+    new.semesters[1]
+        .modules
+        .push(planned("IE2100", ModuleStatus::Planned));
+    new.semesters[0].modules[0].grade = Some("A-".to_string());
+    new.semesters[1].modules[0].status = ModuleStatus::Current;
+
+    // Reported in module code order
+    let diff = diff_plans(&old, &new);
+    assert_eq!(
+        diff.modules,
+        vec![
+            ModuleDiff::Moved {
+                module_code: "CS1010E".to_string(),
+                from: key("2023/2024", 2),
+                to: key("2023/2024", 1),
+            },
+            ModuleDiff::GradeChanged {
+                module_code: "IE1111R".to_string(),
+                semester: key("2023/2024", 1),
+                from: Some("B".to_string()),
+                to: Some("A-".to_string()),
+            },
+            ModuleDiff::Added {
+                module_code: "IE2100".to_string(),
+                semester: key("2023/2024", 2),
+            },
+            ModuleDiff::StatusChanged {
+                module_code: "IE2101".to_string(),
+                semester: key("2023/2024", 2),
+                from: ModuleStatus::Planned,
+                to: ModuleStatus::Current,
+            },
+            ModuleDiff::Removed {
+                module_code: "MA1511".to_string(),
+                semester: key("2023/2024", 1),
+            },
+        ]
+    );
+
+    let lines = diff.lines();
+    assert_eq!(
+        lines[0],
+        (
+            DiffSign::Changed,
+            "CS1010E moved from 2023/2024 Semester 2 to 2023/2024 Semester 1".to_string()
+        )
+    );
+    assert_eq!(lines[2].0, DiffSign::Added);
+    assert_eq!(lines[4].0, DiffSign::Removed);
+}
+
+#[test]
+fn test_retake_is_matched_per_attempt() {
+    let mut old = base_plan();
+    old.semesters[0].modules[1] = graded("MA1511", ModuleStatus::Failed, "F");
+    old.semesters
+        .push(SemesterPlan::empty("2024/2025".to_string(), 1));
+
+    let mut new = old.clone();
+    new.semesters[2]
+        .modules
+        .push(planned("MA1511", ModuleStatus::Planned));
+
+    // The failed attempt stays matched; only the retake is new
+    assert_eq!(
+        diff_plans(&old, &new).modules,
+        vec![ModuleDiff::Added {
+            module_code: "MA1511".to_string(),
+            semester: key("2024/2025", 1),
+        }]
+    );
+}
+
+#[test]
+fn test_metadata_and_activity_changes_serialize() {
+    let old = base_plan();
+    let mut new = old.clone();
+    new.name = "Advisor Suggestion".to_string();
+    new.notes = Some("Consider exchange".to_string());
+    let activity = SpecialActivity {
+        activity_type: ActivityType::InternationalExchange,
+        description: "SEP".to_string(),
+        credits: Some(20),
+    };
+    new.semesters[1].special_activities.push(activity.clone());
+
+    let diff = diff_plans(&old, &new);
+    assert_eq!(
+        diff.metadata,
+        vec![
+            MetadataChange {
+                field: "name".to_string(),
+                from: Some("Diff Plan".to_string()),
+                to: Some("Advisor Suggestion".to_string()),
+            },
+            MetadataChange {
+                field: "notes".to_string(),
+                from: None,
+                to: Some("Consider exchange".to_string()),
+            },
+        ]
+    );
+    assert_eq!(
+        diff.activities,
+        vec![ActivityDiff::Added {
+            semester: key("2023/2024", 2),
+            activity,
+        }]
+    );
+
+    let json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(json["activities"][0]["change"], "added");
+    assert_eq!(json["activities"][0]["semester"]["semester"], 2);
+    assert_eq!(json["metadata"][1]["from"], serde_json::Value::Null);
+}
@@ -0,0 +1,98 @@
+use crate::fixtures::{self, module, semester};
+use module_planner::graph::{render_prereq_tree, DependencyGraph, GraphEdge};
+use module_planner::models::{ModuleRegistry, ModuleStatus, Plan, PrereqTree};
+use std::collections::HashSet;
+
+fn leaf(code: &str) -> PrereqTree {
+    PrereqTree::ModuleCode(code.to_string())
+}
+
+fn registry() -> ModuleRegistry {
+    fixtures::registry([
+        module("IE1111R"),
+        module("IE2101").requires("IE1111R:D"),
+        module("IE3101").prereq(PrereqTree::And {
+            and: vec![
+                leaf("IE2101"),
+                PrereqTree::Or {
+                    or: vec![leaf("MA1511"), leaf("MA1512")],
+                },
+            ],
+        }),
+    ])
+}
+
+fn plan() -> Plan {
+    fixtures::plan(
+        "Graph Plan",
+        vec![
+            semester("2023/2024", 1, &[("IE1111R", ModuleStatus::Completed)]),
+            semester("2023/2024", 2, &[("IE2101", ModuleStatus::Current)]),
+            semester("2024/2025", 1, &[("IE3101", ModuleStatus::Planned)]),
+        ],
+    )
+}
+
+#[test]
+fn test_dependency_graph_from_plan() {
+    let graph = DependencyGraph::from_plan(&plan(), &registry());
+
+    assert!(graph.edges.contains(&GraphEdge {
+        from: "IE1111R".to_string(),
+        to: "IE2101".to_string(),
+    }));
+    assert!(graph.edges.contains(&GraphEdge {
+        from: "IE2101".to_string(),
+        to: "IE3101".to_string(),
+    }));
+
+    // MA1511 and MA1512 are not in the plan, so they become external nodes
+    let external: Vec<&str> = graph
+        .nodes
+        .iter()
+        .filter(|n| n.semester_index.is_none())
+        .map(|n| n.module_code.as_str())
+        .collect();
+    assert_eq!(external, vec!["MA1511", "MA1512"]);
+}
+
+#[test]
+fn test_dependency_graph_exports() {
+    let plan = plan();
+    let graph = DependencyGraph::from_plan(&plan, &registry());
+
+    let dot = graph.to_dot(&plan);
+    assert!(dot.starts_with("digraph \"Graph Plan\" {"));
+    assert!(dot.contains("label=\"2023/2024 Semester 2\";"));
+    assert!(dot.contains("\"IE1111R\" [fillcolor=\"palegreen\"];"));
+    assert!(dot.contains("\"IE2101\" -> \"IE3101\";"));
+
+    let mermaid = graph.to_mermaid(&plan);
+    assert!(mermaid.starts_with("flowchart LR\n"));
+    assert!(mermaid.contains("subgraph S3[\"2024/2025 Semester 1\"]"));
+    assert!(mermaid.contains("IE1111R --> IE2101"));
+    assert!(mermaid.contains("class IE2101 current"));
+    assert!(mermaid.contains("class MA1511,MA1512 external"));
+}
+
+#[test]
+fn test_render_prereq_tree() {
+    let mut passed = HashSet::new();
+    passed.insert("IE1111R".to_string());
+
+    let tree = render_prereq_tree(&registry(), "IE3101", &passed);
+
+    let expected = "\
+IE3101
+└── all of
+    ├── IE2101
+    │   └── IE1111R ✓
+    └── one of
+        ├── MA1511
+        └── MA1512
+";
+    assert_eq!(tree, expected);
+
+    let leaf_tree = render_prereq_tree(&registry(), "IE1111R", &passed);
+    assert_eq!(leaf_tree, "IE1111R ✓\n└── (no prerequisites)\n");
+}
@@ -0,0 +1,184 @@
+use crate::fixtures::{self, module};
+use module_planner::history::{self, EditHistory, MAX_LOGGED_EDITS};
+use module_planner::models::{
+    ActivityType, ModuleRegistry, ModuleStatus, Plan, PlanChange, PlanField, SemesterPlan,
+    SpecialActivity, Units,
+};
+use module_planner::plan_ops;
+use std::collections::HashMap;
+
+fn registry() -> ModuleRegistry {
+    fixtures::registry(["IE1111R", "IE2101", "MA1511", "CS1010E"].map(module))
+}
+
+fn plan(registry: &ModuleRegistry) -> Plan {
+    let mut plan = fixtures::plan(
+        "History Plan",
+        vec![
+            SemesterPlan::empty("2023/2024".to_string(), 1),
+            SemesterPlan::empty("2023/2024".to_string(), 2),
+        ],
+    );
+    for code in ["IE1111R", "MA1511", "CS1010E"] {
+        plan_ops::add_module(&mut plan, registry, 0, code).unwrap();
+    }
+    plan
+}
+
+fn codes(plan: &Plan, semester: usize) -> Vec<&str> {
+    plan.semesters[semester]
+        .modules
+        .iter()
+        .map(|m| m.module_code.as_str())
+        .collect()
+}
+
+#[test]
+fn test_undo_and_redo_restore_plan() {
+    let registry = registry();
+    let mut plan = plan(&registry);
+    let mut history = EditHistory::new();
+
+    let change = plan_ops::remove_module(&mut plan, &registry, 0, "MA1511").unwrap();
+    history.record(&mut plan, vec![change]);
+    let change = plan_ops::move_module(&mut plan, &registry, 0, 1, "IE1111R").unwrap();
+    history.record(&mut plan, vec![change]);
+
+    assert_eq!(codes(&plan, 0), vec!["CS1010E"]);
+    assert_eq!(
+        history.next_undo(&plan).unwrap().description,
+        "Moved IE1111R from semester 1 to semester 2"
+    );
+
+    history.undo(&mut plan, &registry).unwrap();
+    history.undo(&mut plan, &registry).unwrap();
+    // The removed module returns to its original position
+    assert_eq!(codes(&plan, 0), vec!["CS1010E", "MA1511", "IE1111R"]);
+    assert_eq!(plan.semesters[0].total_units, Units::from(12));
+    assert!(history.undo(&mut plan, &registry).unwrap().is_none());
+
+    history.redo(&mut plan, &registry).unwrap();
+    assert_eq!(codes(&plan, 0), vec!["CS1010E", "IE1111R"]);
+    assert!(history.next_redo().is_some());
+
+    // A new edit discards the remaining redo
+    let change = plan_ops::add_module(&mut plan, &registry, 1, "IE2101").unwrap();
+    history.record(&mut plan, vec![change]);
+    assert!(history.next_redo().is_none());
+    assert!(history.redo(&mut plan, &registry).unwrap().is_none());
+}
+
+#[test]
+fn test_undo_compound_edit_after_reload() {
+    let registry = registry();
+    let mut plan = plan(&registry);
+
+    let mut changes = vec![
+        plan_ops::set_status(&mut plan, "IE1111R", Some(0), ModuleStatus::Failed).unwrap(),
+        plan_ops::set_grade(&mut plan, "IE1111R", Some(0), Some("F".to_string())).unwrap(),
+    ];
+    plan.push_next_semester();
+    changes.push(PlanChange::SemesterAppended {
+        year: "2024/2025".to_string(),
+        semester: 1,
+    });
+    changes.push(plan_ops::add_module(&mut plan, &registry, 2, "IE1111R").unwrap());
+    history::record(&mut plan, changes);
+
+    let activity = SpecialActivity {
+        activity_type: ActivityType::Research,
+        description: "Lab work".to_string(),
+        credits: Some(2),
+    };
+    let change = plan_ops::add_activity(&mut plan, &registry, 1, activity).unwrap();
+    history::record(&mut plan, vec![change]);
+
+    // The log is saved with the plan and still usable after loading it back
+    let json = serde_json::to_string(&plan).unwrap();
+    let mut reloaded: Plan = serde_json::from_str(&json).unwrap();
+    assert_eq!(reloaded.edit_log.len(), 2);
+    assert_eq!(
+        reloaded.edit_log[0].description,
+        "IE1111R: Planned → Failed (+3 more)"
+    );
+
+    let undone = history::undo_logged(&mut reloaded, &registry, 5).unwrap();
+    assert_eq!(undone.len(), 2);
+    assert!(reloaded.edit_log.is_empty());
+    assert_eq!(reloaded.semesters.len(), 2);
+    assert!(reloaded.semesters[1].special_activities.is_empty());
+
+    let original = &reloaded.semesters[0].modules[0];
+    assert_eq!(original.status, ModuleStatus::Planned);
+    assert_eq!(original.grade, None);
+}
+
+#[test]
+fn test_conflicting_undo_leaves_plan_untouched() {
+    let registry = registry();
+    let mut plan = plan(&registry);
+    let mut history = EditHistory::new();
+
+    let change = plan_ops::add_module(&mut plan, &registry, 1, "IE2101").unwrap();
+    history.record(&mut plan, vec![change]);
+
+    // Removed behind the history's back, so the add can no longer be undone
+    plan.semesters[1].modules.clear();
+
+    let error = history.undo(&mut plan, &registry).unwrap_err();
+    assert!(error.to_string().starts_with("Cannot undo 'Added IE2101"));
+    assert_eq!(plan.edit_log.len(), 1);
+}
+
+#[test]
+fn test_edit_log_is_capped() {
+    let registry = registry();
+    let mut plan = plan(&registry);
+
+    for _ in 0..MAX_LOGGED_EDITS + 5 {
+        let change = plan_ops::move_module(&mut plan, &registry, 0, 1, "MA1511").unwrap();
+        history::record(&mut plan, vec![change]);
+        let change = plan_ops::move_module(&mut plan, &registry, 1, 0, "MA1511").unwrap();
+        history::record(&mut plan, vec![change]);
+    }
+
+    assert_eq!(plan.edit_log.len(), MAX_LOGGED_EDITS);
+    history::record(&mut plan, Vec::new());
+    assert_eq!(plan.edit_log.len(), MAX_LOGGED_EDITS);
+}
+
+#[test]
+fn test_metadata_and_class_selections_can_be_undone() {
+    let registry = registry();
+    let mut plan = plan(&registry);
+    let mut history = EditHistory::new();
+
+    let change = plan_ops::set_field(
+        &mut plan,
+        PlanField::Notes,
+        Some("Exchange in Y3".to_string()),
+    )
+    .unwrap();
+    history.record(&mut plan, vec![change]);
+    let selections = HashMap::from([("Lecture".to_string(), "1".to_string())]);
+    let change = plan_ops::select_classes(&mut plan, 0, "CS1010E", selections).unwrap();
+    history.record(&mut plan, vec![change]);
+
+    assert_eq!(
+        history.next_undo(&plan).unwrap().description,
+        "Updated class selections for CS1010E in semester 1"
+    );
+    history.undo(&mut plan, &registry).unwrap();
+    assert!(plan.semesters[0].modules[2].class_selections.is_empty());
+    assert_eq!(
+        history.next_undo(&plan).unwrap().description,
+        "Set notes to 'Exchange in Y3'"
+    );
+    history.undo(&mut plan, &registry).unwrap();
+    assert_eq!(plan.notes, None);
+
+    history.redo(&mut plan, &registry).unwrap();
+    assert_eq!(plan.notes.as_deref(), Some("Exchange in Y3"));
+
+    assert!(plan_ops::set_field(&mut plan, PlanField::Name, None).is_err());
+}
@@ -0,0 +1,113 @@
+use crate::fixtures::{self, planned};
+use module_planner::merge::{three_way_merge, MergeConflict, MergeSide};
+use module_planner::models::{ModuleStatus, Plan, SemesterPlan};
+
+fn base_plan() -> Plan {
+    let mut plan = fixtures::plan(
+        "Merge Plan",
+        vec![
+            SemesterPlan::empty("2024/2025".to_string(), 1),
+            SemesterPlan::empty("2024/2025".to_string(), 2),
+        ],
+    );
+    plan.semesters[0].modules = vec![
+        planned("IE1111R", ModuleStatus::Planned),
+        planned("MA1511", ModuleStatus::Planned),
+    ];
+    plan.semesters[1].modules = vec![planned("IE2101", ModuleStatus::Planned)];
+    plan
+}
+
+fn codes(plan: &Plan) -> Vec<Vec<&str>> {
+    plan.semesters
+        .iter()
+        .map(|s| s.modules.iter().map(|m| m.module_code.as_str()).collect())
+        .collect()
+}
+
+#[test]
+fn test_non_conflicting_changes_merge_cleanly() {
+    let base = base_plan();
+    let mut ours = base.clone();
+    let mut theirs = base.clone();
+
+    ours.semesters[0]
+        .modules
+        .push(planned("CS1010E", ModuleStatus::Planned));
+    theirs.semesters[1]
+        .modules
+        .push(planned("IE2100", ModuleStatus::Planned));
+    theirs.name = "Advisor Plan".to_string();
+    // Both sides appending the same semester is not a conflict
+    ours.push_next_semester();
+    theirs.push_next_semester();
+
+    let merge = three_way_merge(&base, &ours, &theirs);
+    assert!(merge.is_clean());
+    assert_eq!(
+        codes(&merge.plan),
+        vec![
+            vec!["IE1111R", "MA1511", "CS1010E"],
+            vec!["IE2101", "IE2100"],
+            vec![],
+        ]
+    );
+    assert_eq!(merge.plan.name, "Advisor Plan");
+    assert_eq!(
+        merge.merged_from_theirs,
+        vec!["name".to_string(), "2024/2025 Semester 2".to_string()]
+    );
+}
+
+#[test]
+fn test_semesters_added_by_theirs_stay_in_term_order() {
+    let base = base_plan();
+    let ours = base.clone();
+    let mut theirs = base.clone();
+
+    let mut special_term = SemesterPlan::empty("2024/2025".to_string(), 3);
+    special_term
+        .modules
+        .push(planned("IE2110", ModuleStatus::Planned));
+    theirs.semesters.push(special_term);
+    theirs.semesters.remove(0);
+
+    let merge = three_way_merge(&base, &ours, &theirs);
+    assert!(merge.is_clean());
+    let terms: Vec<u8> = merge.plan.semesters.iter().map(|s| s.semester).collect();
+    assert_eq!(terms, vec![2, 3]);
+}
+
+#[test]
+fn test_conflicts_keep_ours_until_resolved() {
+    let base = base_plan();
+    let mut ours = base.clone();
+    let mut theirs = base.clone();
+
+    ours.semesters[0].modules.remove(1);
+    theirs.semesters[0]
+        .modules
+        .push(planned("CS1010E", ModuleStatus::Planned));
+    ours.notes = Some("Local".to_string());
+    theirs.notes = Some("Advisor".to_string());
+
+    let mut merge = three_way_merge(&base, &ours, &theirs);
+    assert_eq!(merge.conflicts.len(), 2);
+    assert!(matches!(
+        &merge.conflicts[0],
+        MergeConflict::Field { field, .. } if field == "notes"
+    ));
+    assert_eq!(
+        merge.conflicts[1].to_string(),
+        "2024/2025 Semester 1: local [IE1111R] vs imported [IE1111R, MA1511, CS1010E]"
+    );
+    assert_eq!(codes(&merge.plan)[0], vec!["IE1111R"]);
+
+    merge.resolve(1, MergeSide::Theirs).unwrap();
+    assert_eq!(codes(&merge.plan)[0], vec!["IE1111R", "MA1511", "CS1010E"]);
+    assert!(merge.resolve(3, MergeSide::Ours).is_err());
+
+    merge.resolve_all(MergeSide::Ours);
+    assert!(merge.is_clean());
+    assert_eq!(merge.plan.notes.as_deref(), Some("Local"));
+}
@@ -0,0 +1,65 @@
+use module_planner::migrate::assign_student_ids;
+use module_planner::models::{CandidatureType, Plan, Student};
+use std::collections::HashSet;
+
+fn student(id: &str, name: &str) -> Student {
+    Student {
+        id: id.to_string(),
+        name: name.to_string(),
+        matriculation_year: "2023/2024".to_string(),
+        faculty: "Computing".to_string(),
+        major: "Computer Science".to_string(),
+        second_major: None,
+        minors: Vec::new(),
+        completed_modules: HashSet::new(),
+        exempted_modules: HashSet::new(),
+        advanced_placement_credits: 0,
+        current_semester: 1,
+        candidature_type: CandidatureType::Standard,
+    }
+}
+
+fn plan(id: &str, student_id: &str) -> Plan {
+    Plan::new(id.to_string(), id.to_string(), student_id.to_string())
+}
+
+#[test]
+fn test_assign_student_ids_relinks_plans_by_name() {
+    let mut students = vec![student("", "Alice"), student("bob-id", "Bob")];
+    let mut plans = vec![
+        plan("p1", "Alice"),
+        plan("p2", "bob-id"),
+        plan("p3", "Carol"),
+    ];
+
+    let migration = assign_student_ids(&mut students, &mut plans);
+
+    let alice_id = students[0].id.clone();
+    assert!(!alice_id.is_empty());
+    assert_eq!(
+        migration.assigned,
+        vec![("Alice".to_string(), alice_id.clone())]
+    );
+    assert_eq!(migration.relinked, vec!["p1"]);
+    assert_eq!(plans[0].student_id, alice_id);
+    assert_eq!(plans[1].student_id, "bob-id");
+    // Plans naming no known student are left alone
+    assert_eq!(plans[2].student_id, "Carol");
+}
+
+#[test]
+fn test_assign_student_ids_reports_shared_names() {
+    let mut students = vec![student("", "Alice"), student("", "Alice")];
+    let mut plans = vec![plan("p1", "Alice")];
+
+    let migration = assign_student_ids(&mut students, &mut plans);
+
+    assert_eq!(migration.assigned.len(), 2);
+    assert_ne!(students[0].id, students[1].id);
+    assert_eq!(migration.ambiguous, vec!["p1"]);
+    assert_eq!(plans[0].student_id, "Alice");
+
+    assert!(assign_student_ids(&mut students, &mut plans)
+        .assigned
+        .is_empty());
+}
@@ -0,0 +1,8 @@
+mod critical_path_test;
+mod diff_test;
+mod graph_test;
+mod history_test;
+mod merge_test;
+mod migrate_test;
+mod repair_test;
+mod transcript_test;
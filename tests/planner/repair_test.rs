@@ -0,0 +1,89 @@
+use crate::fixtures::{module, plan, registry, semester};
+use module_planner::models::{ModuleRegistry, ModuleStatus, Plan, Units};
+use module_planner::repair::{
+    find_broken_dependents, propose_retake_semester, schedule_retake, shift_dependents,
+};
+use std::collections::HashSet;
+
+fn chain_registry() -> ModuleRegistry {
+    registry([
+        module("IE1111R").offered_in(&[1, 2]),
+        module("IE2101").offered_in(&[2]).requires("IE1111R:D"),
+        module("IE3101").offered_in(&[2]).requires("IE2101"),
+        module("MA1511").offered_in(&[1, 2]),
+    ])
+}
+
+fn chain_plan() -> Plan {
+    plan(
+        "Retake Plan",
+        vec![
+            semester(
+                "2023/2024",
+                1,
+                &[
+                    ("IE1111R", ModuleStatus::Failed),
+                    ("MA1511", ModuleStatus::Completed),
+                ],
+            ),
+            semester("2023/2024", 2, &[("IE2101", ModuleStatus::Planned)]),
+            semester("2024/2025", 1, &[]),
+            semester("2024/2025", 2, &[("IE3101", ModuleStatus::Planned)]),
+        ],
+    )
+}
+
+#[test]
+fn test_find_broken_dependents_is_transitive() {
+    let plan = chain_plan();
+    let registry = chain_registry();
+
+    let broken = find_broken_dependents(&plan, &registry, &HashSet::new(), "IE1111R", 0);
+
+    let codes: Vec<&str> = broken.iter().map(|b| b.module_code.as_str()).collect();
+    assert_eq!(codes, vec!["IE2101", "IE3101"]);
+    assert_eq!(broken[0].semester_index, 1);
+    assert_eq!(broken[1].semester_index, 3);
+}
+
+#[test]
+fn test_find_broken_dependents_ignores_unrelated_failure() {
+    let mut plan = chain_plan();
+    plan.semesters[0].modules[0].status = ModuleStatus::Completed;
+    plan.semesters[0].modules[1].status = ModuleStatus::Failed;
+    let registry = chain_registry();
+
+    let broken = find_broken_dependents(&plan, &registry, &HashSet::new(), "MA1511", 0);
+
+    assert!(broken.is_empty());
+}
+
+#[test]
+fn test_retake_and_shift_dependents() {
+    let mut plan = chain_plan();
+    let registry = chain_registry();
+
+    let broken = find_broken_dependents(&plan, &registry, &HashSet::new(), "IE1111R", 0);
+    let retake = propose_retake_semester(&plan, &registry, "IE1111R", 0).unwrap();
+    assert_eq!(retake, 1);
+
+    schedule_retake(&mut plan, &registry, "IE1111R", retake);
+    assert_eq!(plan.semesters[1].total_units, Units::from(8));
+
+    let shifts = shift_dependents(&mut plan, &registry, "IE1111R", retake, &broken);
+
+    // Both dependents are Semester 2 only, so IE2101 moves a full year later
+    // and IE3101 has to follow it into a newly appended semester.
+    assert_eq!(shifts.len(), 2);
+    assert_eq!(shifts[0].to_semester, 3);
+    assert_eq!(shifts[1].to_semester, 5);
+    assert_eq!(plan.semesters.len(), 6);
+    assert_eq!(plan.semesters[5].year, "2025/2026");
+    assert_eq!(plan.semesters[5].semester, 2);
+
+    assert_eq!(plan.semesters[1].total_units, Units::from(4));
+    assert_eq!(plan.semesters[3].total_units, Units::from(4));
+    assert_eq!(plan.semesters[4].total_units, Units::from(0));
+    assert_eq!(plan.semesters[5].total_units, Units::from(4));
+    assert_eq!(plan.total_units(), Units::from(20));
+}
@@ -0,0 +1,125 @@
+use module_planner::models::{CandidatureType, ModuleRegistry, ModuleStatus, Plan, Student, Units};
+use module_planner::transcript::{apply_transcript, parse_term, parse_transcript};
+use std::collections::HashSet;
+
+fn test_student() -> Student {
+    Student {
+        id: "test-student-id".to_string(),
+        name: "Test Student".to_string(),
+        matriculation_year: "2023/2024".to_string(),
+        faculty: "College of Design and Engineering".to_string(),
+        major: "Industrial Systems Engineering".to_string(),
+        second_major: None,
+        minors: Vec::new(),
+        completed_modules: HashSet::new(),
+        exempted_modules: HashSet::new(),
+        advanced_placement_credits: 0,
+        current_semester: 3,
+        candidature_type: CandidatureType::Standard,
+    }
+}
+
+#[test]
+fn test_parse_term_formats() {
+    assert_eq!(
+        parse_term("2023/2024 Semester 1"),
+        Some(("2023/2024".to_string(), 1))
+    );
+    assert_eq!(
+        parse_term("AY2023/24 Sem 2"),
+        Some(("2023/2024".to_string(), 2))
+    );
+    assert_eq!(
+        parse_term("2024/2025 Special Term II"),
+        Some(("2024/2025".to_string(), 4))
+    );
+    assert_eq!(parse_term("Semester 1"), None);
+}
+
+#[test]
+fn test_parse_transcript_csv_with_header() {
+    let contents = "\
+Module Code,Title,Units,Grade,Term
+IE1111R,\"Industrial Engineering, Principles and Practice\",4,A-,2023/2024 Semester 1
+MA1511,Engineering Calculus,2,B+,2023/2024 Semester 1
+
+# retaken next semester
+IE2111,Industrial Systems Engineering,4,F,AY2023/24 Sem 2
+";
+
+    let entries = parse_transcript(contents).unwrap();
+
+    assert_eq!(entries.len(), 3);
+    assert_eq!(
+        entries[0].title,
+        "Industrial Engineering, Principles and Practice"
+    );
+    assert_eq!(entries[1].units, Units::from(2));
+    assert_eq!(entries[2].semester, 2);
+    assert!(!entries[2].is_pass());
+}
+
+#[test]
+fn test_parse_transcript_rejects_bad_units() {
+    let contents = "\
+IE1111R,Industrial Engineering Principles and Practice,4,A,2023/2024 Semester 1
+MA1511,Engineering Calculus,two,B+,2023/2024 Semester 1
+";
+
+    let error = parse_transcript(contents).unwrap_err();
+    assert!(error.to_string().contains("Line 2"));
+}
+
+#[test]
+fn test_apply_transcript_creates_past_semesters() {
+    let contents = "\
+IE1111R\tIndustrial Engineering Principles and Practice\t4\tA\t2023/2024 Semester 1
+MA1511\tEngineering Calculus\t2\tB+\t2023/2024 Semester 1
+IE2111\tIndustrial Systems Engineering\t4\tF\t2023/2024 Semester 2
+";
+    let entries = parse_transcript(contents).unwrap();
+
+    let mut student = test_student();
+    let mut plan = Plan::new(
+        "plan1".to_string(),
+        "Imported".to_string(),
+        student.name.clone(),
+    );
+    plan.push_next_semester();
+    plan.semesters[0].year = "2024/2025".to_string();
+
+    let mut registry = ModuleRegistry::new();
+    registry.add_module(module_planner::models::Module {
+        module_code: "IE1111R".to_string(),
+        title: "Industrial Engineering Principles and Practice".to_string(),
+        description: None,
+        module_credit: 4.into(),
+        department: None,
+        faculty: None,
+        workload: None,
+        prerequisite: None,
+        preclusion: None,
+        corequisite: None,
+        semester_data: Vec::new(),
+        prereq_tree: None,
+        fulfill_requirements: None,
+    });
+
+    let report = apply_transcript(&mut student, &mut plan, &entries, &registry);
+
+    assert_eq!(report.semesters_created, 2);
+    assert_eq!(report.imported, vec!["IE1111R", "MA1511"]);
+    assert_eq!(report.failed, vec!["IE2111"]);
+    assert_eq!(report.missing_from_registry, vec!["MA1511", "IE2111"]);
+
+    assert_eq!(plan.semesters.len(), 3);
+    assert_eq!(plan.semesters[0].year, "2023/2024");
+    assert_eq!(plan.semesters[0].semester, 1);
+    assert_eq!(plan.semesters[0].total_units, Units::from(6));
+    assert_eq!(plan.semesters[1].modules[0].status, ModuleStatus::Failed);
+    assert_eq!(plan.semesters[2].year, "2024/2025");
+
+    assert!(student.completed_modules.contains("IE1111R"));
+    assert!(student.completed_modules.contains("MA1511"));
+    assert!(!student.completed_modules.contains("IE2111"));
+}